@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS expenses;
+DROP TABLE IF EXISTS timesheets;
+DROP TABLE IF EXISTS jobs;
+DROP DOMAIN IF EXISTS amount_of_currency;
+DROP TABLE IF EXISTS employees;
+DROP TABLE IF EXISTS contact_information;
+DROP TABLE IF EXISTS organizations;
+DROP TABLE IF EXISTS locations;
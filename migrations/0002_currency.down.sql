@@ -0,0 +1,4 @@
+-- NOTE: amounts which were not in USD are left as they are, and will be misinterpreted.
+ALTER TABLE expenses DROP COLUMN cost_currency;
+ALTER TABLE jobs DROP COLUMN invoice_hourly_rate_currency;
+DROP DOMAIN currency;
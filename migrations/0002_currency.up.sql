@@ -0,0 +1,9 @@
+CREATE DOMAIN currency AS text CHECK (VALUE ~ '^[A-Z]{3}$');
+
+-- NOTE: before this migration, every amount was exchanged into `Currency::default()` (USD) before
+--       being stored.
+ALTER TABLE jobs ADD COLUMN invoice_hourly_rate_currency currency NOT NULL DEFAULT 'USD';
+ALTER TABLE jobs ALTER COLUMN invoice_hourly_rate_currency DROP DEFAULT;
+
+ALTER TABLE expenses ADD COLUMN cost_currency currency NOT NULL DEFAULT 'USD';
+ALTER TABLE expenses ALTER COLUMN cost_currency DROP DEFAULT;
@@ -0,0 +1,8 @@
+DROP INDEX expenses__cost_idx;
+DROP INDEX jobs__invoice_hourly_rate_idx;
+
+CREATE DOMAIN amount_of_currency AS text CHECK (VALUE ~ '^\d+(\.\d+)?$');
+
+-- NOTE: fails if any amount is negative, since `amount_of_currency` cannot represent it.
+ALTER TABLE expenses ALTER COLUMN cost TYPE amount_of_currency USING cost::text;
+ALTER TABLE jobs ALTER COLUMN invoice_hourly_rate TYPE amount_of_currency USING invoice_hourly_rate::text;
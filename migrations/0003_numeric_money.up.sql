@@ -0,0 +1,9 @@
+-- NOTE: `numeric` (unlike `amount_of_currency`) allows negative amounts, such as refunds and credits.
+ALTER TABLE jobs ALTER COLUMN invoice_hourly_rate TYPE numeric USING invoice_hourly_rate::numeric;
+ALTER TABLE expenses ALTER COLUMN cost TYPE numeric USING cost::numeric;
+
+DROP DOMAIN amount_of_currency;
+
+-- NOTE: amounts are only ever compared to other amounts of the same currency.
+CREATE INDEX jobs__invoice_hourly_rate_idx ON jobs (invoice_hourly_rate_currency, invoice_hourly_rate);
+CREATE INDEX expenses__cost_idx ON expenses (cost_currency, cost);
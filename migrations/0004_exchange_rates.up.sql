@@ -0,0 +1,9 @@
+-- NOTE: like the rates published by the European Central Bank, each `rate` is the amount of
+--       `currency` which is worth 1 EUR.
+CREATE TABLE exchange_rates
+(
+	currency currency PRIMARY KEY,
+	rate numeric NOT NULL,
+
+	CONSTRAINT exchange_rates__rate_is_positive CHECK (rate > 0)
+);
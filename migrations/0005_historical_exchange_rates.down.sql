@@ -0,0 +1,7 @@
+-- NOTE: only the most recent rate of each currency is kept.
+DELETE FROM exchange_rates R
+	WHERE EXISTS (SELECT FROM exchange_rates R2 WHERE R2.currency = R.currency AND R2.date > R.date);
+
+ALTER TABLE exchange_rates DROP CONSTRAINT exchange_rates_pkey;
+ALTER TABLE exchange_rates DROP COLUMN date;
+ALTER TABLE exchange_rates ADD CONSTRAINT exchange_rates_pkey PRIMARY KEY (currency);
@@ -0,0 +1,6 @@
+-- NOTE: any rates stored before this migration are assumed to be effective as of today.
+ALTER TABLE exchange_rates ADD COLUMN date date NOT NULL DEFAULT CURRENT_DATE;
+ALTER TABLE exchange_rates ALTER COLUMN date DROP DEFAULT;
+
+ALTER TABLE exchange_rates DROP CONSTRAINT exchange_rates_pkey;
+ALTER TABLE exchange_rates ADD CONSTRAINT exchange_rates_pkey PRIMARY KEY (date, currency);
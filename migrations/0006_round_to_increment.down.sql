@@ -0,0 +1 @@
+DROP FUNCTION round_to_increment;
@@ -0,0 +1,8 @@
+-- NOTE: an `increment` of zero means that the `duration` should not be rounded.
+CREATE FUNCTION round_to_increment(duration interval, increment interval) RETURNS interval
+	LANGUAGE sql IMMUTABLE
+	AS $$
+		SELECT CASE WHEN increment = interval '0' THEN duration
+			ELSE increment * round(extract(EPOCH FROM duration) / extract(EPOCH FROM increment))
+		END;
+	$$;
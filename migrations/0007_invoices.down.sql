@@ -0,0 +1,5 @@
+DROP TRIGGER invoices__numbers_gap_free ON invoices;
+DROP FUNCTION invoices__numbers_gap_free;
+DROP TABLE invoice_lines;
+DROP TABLE invoices;
+DROP TABLE invoice_numbers;
@@ -0,0 +1,69 @@
+-- NOTE: the most recent `number` given to an invoice in each `year`. Incrementing it locks the row
+--       until the transaction ends, so concurrent invoices are numbered one after another, and a
+--       rolled-back invoice gives its number back.
+CREATE TABLE invoice_numbers
+(
+	year integer PRIMARY KEY,
+	number integer NOT NULL,
+
+	CONSTRAINT invoice_numbers__number_is_positive CHECK (number >= 0)
+);
+
+CREATE TABLE invoices
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	job_id bigint NOT NULL REFERENCES jobs(id),
+	year integer NOT NULL,
+	number integer NOT NULL,
+	date_issued timestamptz NOT NULL,
+	date_paid timestamptz,
+
+	CONSTRAINT invoices__date_integrity CHECK (date_issued < date_paid),
+	CONSTRAINT invoices__number_is_positive CHECK (number > 0),
+	CONSTRAINT invoices__year_is_issued_year CHECK
+	(
+		year = extract(YEAR FROM date_issued AT TIME ZONE 'UTC')
+	),
+	CONSTRAINT invoices__year_number_uq UNIQUE (year, number)
+);
+
+CREATE TABLE invoice_lines
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	invoice_id bigint NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+	expense_id bigint REFERENCES expenses(id),
+	timesheet_id bigint REFERENCES timesheets(id),
+	amount numeric NOT NULL,
+	amount_currency currency NOT NULL,
+
+	CONSTRAINT invoice_lines__expense_uq UNIQUE (expense_id),
+	CONSTRAINT invoice_lines__is_variant CHECK ((expense_id IS null) <> (timesheet_id IS null)),
+	CONSTRAINT invoice_lines__timesheet_uq UNIQUE (timesheet_id)
+);
+
+-- NOTE: deleting any invoice but the most recent of its year would leave a gap in the numbers.
+CREATE FUNCTION invoices__numbers_gap_free() RETURNS trigger
+	LANGUAGE plpgsql
+	AS $$
+	BEGIN
+		IF EXISTS
+		(
+			SELECT FROM deleted D
+			JOIN invoices I ON (I.year = D.year AND I.number > D.number)
+		)
+		THEN
+			RAISE EXCEPTION 'only the most recent invoices of a year can be deleted'
+				USING ERRCODE = 'restrict_violation', CONSTRAINT = 'invoices__numbers_gap_free';
+		END IF;
+
+		UPDATE invoice_numbers N
+			SET number = (SELECT COALESCE(max(I.number), 0) FROM invoices I WHERE I.year = N.year)
+			WHERE N.year IN (SELECT year FROM deleted);
+
+		RETURN NULL;
+	END;
+	$$;
+
+CREATE TRIGGER invoices__numbers_gap_free AFTER DELETE ON invoices
+	REFERENCING OLD TABLE AS deleted
+	FOR EACH STATEMENT EXECUTE FUNCTION invoices__numbers_gap_free();
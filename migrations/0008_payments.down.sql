@@ -0,0 +1,2 @@
+DROP TABLE payments;
+ALTER TABLE invoices DROP CONSTRAINT invoices__id_job_uq;
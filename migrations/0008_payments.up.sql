@@ -0,0 +1,18 @@
+-- NOTE: allows `payments` to require that the invoice they pay belongs to the same job.
+ALTER TABLE invoices ADD CONSTRAINT invoices__id_job_uq UNIQUE (id, job_id);
+
+CREATE TABLE payments
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	job_id bigint NOT NULL REFERENCES jobs(id),
+	invoice_id bigint,
+	amount numeric NOT NULL,
+	amount_currency currency NOT NULL,
+	date timestamptz NOT NULL,
+	method text NOT NULL,
+	reference text NOT NULL,
+
+	CONSTRAINT payments__amount_is_positive CHECK (amount > 0),
+	CONSTRAINT payments__invoice_of_job_fk FOREIGN KEY (invoice_id, job_id)
+		REFERENCES invoices(id, job_id)
+);
@@ -0,0 +1,3 @@
+DROP TABLE expense_tax_rates;
+DROP TABLE job_tax_rates;
+DROP TABLE tax_rates;
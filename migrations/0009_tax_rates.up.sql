@@ -0,0 +1,25 @@
+CREATE TABLE tax_rates
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	location_id bigint REFERENCES locations(id),
+	name text NOT NULL,
+	percentage numeric NOT NULL,
+
+	CONSTRAINT tax_rates__percentage_is_not_negative CHECK (percentage >= 0)
+);
+
+CREATE TABLE job_tax_rates
+(
+	job_id bigint NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+	tax_rate_id bigint NOT NULL REFERENCES tax_rates(id),
+
+	PRIMARY KEY (job_id, tax_rate_id)
+);
+
+CREATE TABLE expense_tax_rates
+(
+	expense_id bigint NOT NULL REFERENCES expenses(id) ON DELETE CASCADE,
+	tax_rate_id bigint NOT NULL REFERENCES tax_rates(id),
+
+	PRIMARY KEY (expense_id, tax_rate_id)
+);
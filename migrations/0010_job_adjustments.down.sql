@@ -0,0 +1,10 @@
+-- NOTE: invoice lines which billed an adjustment cannot be represented without it.
+DELETE FROM invoice_lines WHERE adjustment_id IS NOT NULL;
+
+ALTER TABLE invoice_lines
+	DROP CONSTRAINT invoice_lines__is_variant,
+	DROP COLUMN adjustment_id,
+	ADD CONSTRAINT invoice_lines__is_variant CHECK ((expense_id IS null) <> (timesheet_id IS null));
+
+DROP TABLE job_adjustments;
+ALTER TABLE jobs DROP CONSTRAINT jobs__id_currency_uq;
@@ -0,0 +1,25 @@
+-- NOTE: allows `job_adjustments` to require that fixed amounts are in the currency of their job.
+ALTER TABLE jobs ADD CONSTRAINT jobs__id_currency_uq UNIQUE (id, invoice_hourly_rate_currency);
+
+CREATE TABLE job_adjustments
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	job_id bigint NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+	amount numeric,
+	amount_currency currency,
+	percentage numeric,
+	reason text NOT NULL,
+
+	CONSTRAINT job_adjustments__amount_has_currency CHECK ((amount IS NULL) = (amount_currency IS NULL)),
+	CONSTRAINT job_adjustments__is_variant CHECK ((amount IS NULL) <> (percentage IS NULL)),
+	CONSTRAINT job_adjustments__percentage_is_at_least_minus_100 CHECK (percentage >= -100),
+	-- NOTE: deferred so that a job and its adjustments can change currency in the same transaction.
+	CONSTRAINT job_adjustments__currency_of_job_fk FOREIGN KEY (job_id, amount_currency)
+		REFERENCES jobs(id, invoice_hourly_rate_currency) DEFERRABLE INITIALLY DEFERRED
+);
+
+ALTER TABLE invoice_lines
+	ADD COLUMN adjustment_id bigint REFERENCES job_adjustments(id),
+	DROP CONSTRAINT invoice_lines__is_variant,
+	ADD CONSTRAINT invoice_lines__is_variant
+		CHECK (num_nonnulls(adjustment_id, expense_id, timesheet_id) = 1);
@@ -0,0 +1,2 @@
+DROP VIEW timesheet_rates;
+DROP TABLE billing_rates;
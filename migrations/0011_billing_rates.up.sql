@@ -0,0 +1,31 @@
+CREATE TABLE billing_rates
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	job_id bigint NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+	employee_id bigint REFERENCES employees(id) ON DELETE CASCADE,
+	employee_title text,
+	hourly_rate numeric NOT NULL,
+	hourly_rate_currency currency NOT NULL,
+
+	CONSTRAINT billing_rates__hourly_rate_is_not_negative CHECK (hourly_rate >= 0),
+	CONSTRAINT billing_rates__is_variant CHECK ((employee_id IS NULL) <> (employee_title IS NULL)),
+	CONSTRAINT billing_rates__job_employee_uq UNIQUE (job_id, employee_id),
+	CONSTRAINT billing_rates__job_title_uq UNIQUE (job_id, employee_title),
+	-- NOTE: deferred so that a job and its rates can change currency in the same transaction.
+	CONSTRAINT billing_rates__currency_of_job_fk FOREIGN KEY (job_id, hourly_rate_currency)
+		REFERENCES jobs(id, invoice_hourly_rate_currency) DEFERRABLE INITIALLY DEFERRED
+);
+
+-- NOTE: the hourly rate which each timesheet is billed at. A rate for the specific employee takes
+--       precedence over a rate for their title, which takes precedence over the rate of the job.
+CREATE VIEW timesheet_rates AS
+	SELECT
+		T.id AS timesheet_id,
+		COALESCE(BE.hourly_rate, BT.hourly_rate, J.invoice_hourly_rate) AS hourly_rate,
+		COALESCE(BE.hourly_rate_currency, BT.hourly_rate_currency, J.invoice_hourly_rate_currency)
+			AS hourly_rate_currency
+	FROM timesheets T
+	JOIN jobs J ON (J.id = T.job_id)
+	JOIN employees E ON (E.id = T.employee_id)
+	LEFT JOIN billing_rates BE ON (BE.job_id = T.job_id AND BE.employee_id = T.employee_id)
+	LEFT JOIN billing_rates BT ON (BT.job_id = T.job_id AND BT.employee_title = E.title);
@@ -0,0 +1,2 @@
+-- NOTE: `btree_gist` is left installed, since it may be used by something other than this schema.
+ALTER TABLE timesheets DROP CONSTRAINT timesheets__no_overlap;
@@ -0,0 +1,8 @@
+-- NOTE: provides the `=` operator for `bigint` in a GiST index, which the exclusion needs.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+-- NOTE: a timesheet without a `time_end` is still being worked on, so it overlaps every timesheet
+--       which begins after it. This also means that each employee has at most one running
+--       timesheet.
+ALTER TABLE timesheets ADD CONSTRAINT timesheets__no_overlap
+	EXCLUDE USING gist (employee_id WITH =, tstzrange(time_begin, time_end) WITH &&);
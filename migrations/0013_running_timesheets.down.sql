@@ -0,0 +1 @@
+DROP INDEX timesheets__running;
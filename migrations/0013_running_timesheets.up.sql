@@ -0,0 +1,4 @@
+-- NOTE: a timesheet without a `time_end` is a timer which is still running. `timesheets__no_overlap`
+--       already allows each employee at most one, since it overlaps every timesheet which begins
+--       after it, so this only finds that timer quickly.
+CREATE INDEX timesheets__running ON timesheets (employee_id) WHERE (time_end IS NULL);
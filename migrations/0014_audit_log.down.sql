@@ -0,0 +1,10 @@
+DROP TRIGGER timesheets__audit_update ON timesheets;
+DROP TRIGGER timesheets__audit ON timesheets;
+DROP TRIGGER organizations__audit_update ON organizations;
+DROP TRIGGER organizations__audit ON organizations;
+DROP TRIGGER jobs__audit_update ON jobs;
+DROP TRIGGER jobs__audit ON jobs;
+DROP TRIGGER expenses__audit_update ON expenses;
+DROP TRIGGER expenses__audit ON expenses;
+DROP FUNCTION audit_log__record;
+DROP TABLE audit_log;
@@ -0,0 +1,65 @@
+-- NOTE: every change to an audited table. `row_id` is not a foreign key, so that the history of a
+--       row outlives it.
+CREATE TABLE audit_log
+(
+	id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	table_name text NOT NULL,
+	row_id bigint NOT NULL,
+	operation text NOT NULL,
+	old_values jsonb,
+	new_values jsonb,
+	changed_at timestamptz NOT NULL DEFAULT now(),
+	actor text,
+
+	CONSTRAINT audit_log__operation_is_known CHECK (operation IN ('DELETE', 'INSERT', 'UPDATE')),
+	CONSTRAINT audit_log__values_of_operation CHECK (
+		(old_values IS NULL) = (operation = 'INSERT') AND
+		(new_values IS NULL) = (operation = 'DELETE')
+	)
+);
+
+CREATE INDEX audit_log__table_row_idx ON audit_log (table_name, row_id);
+
+-- NOTE: the actor is whatever the caller set `clinvoice.actor` to for the current transaction.
+CREATE FUNCTION audit_log__record() RETURNS trigger
+	LANGUAGE plpgsql
+	AS $$
+	DECLARE
+		actor text := nullif(current_setting('clinvoice.actor', true), '');
+	BEGIN
+		IF TG_OP = 'INSERT' THEN
+			INSERT INTO audit_log (table_name, row_id, operation, new_values, actor)
+				VALUES (TG_TABLE_NAME, NEW.id, TG_OP, to_jsonb(NEW), actor);
+		ELSIF TG_OP = 'UPDATE' THEN
+			INSERT INTO audit_log (table_name, row_id, operation, old_values, new_values, actor)
+				VALUES (TG_TABLE_NAME, NEW.id, TG_OP, to_jsonb(OLD), to_jsonb(NEW), actor);
+		ELSE
+			INSERT INTO audit_log (table_name, row_id, operation, old_values, actor)
+				VALUES (TG_TABLE_NAME, OLD.id, TG_OP, to_jsonb(OLD), actor);
+		END IF;
+
+		RETURN NULL;
+	END;
+	$$;
+
+-- NOTE: `PgSchema::update` rewrites every row it is given, so updates which change nothing are
+--       not recorded.
+CREATE TRIGGER expenses__audit AFTER INSERT OR DELETE ON expenses
+	FOR EACH ROW EXECUTE FUNCTION audit_log__record();
+CREATE TRIGGER expenses__audit_update AFTER UPDATE ON expenses
+	FOR EACH ROW WHEN (OLD IS DISTINCT FROM NEW) EXECUTE FUNCTION audit_log__record();
+
+CREATE TRIGGER jobs__audit AFTER INSERT OR DELETE ON jobs
+	FOR EACH ROW EXECUTE FUNCTION audit_log__record();
+CREATE TRIGGER jobs__audit_update AFTER UPDATE ON jobs
+	FOR EACH ROW WHEN (OLD IS DISTINCT FROM NEW) EXECUTE FUNCTION audit_log__record();
+
+CREATE TRIGGER organizations__audit AFTER INSERT OR DELETE ON organizations
+	FOR EACH ROW EXECUTE FUNCTION audit_log__record();
+CREATE TRIGGER organizations__audit_update AFTER UPDATE ON organizations
+	FOR EACH ROW WHEN (OLD IS DISTINCT FROM NEW) EXECUTE FUNCTION audit_log__record();
+
+CREATE TRIGGER timesheets__audit AFTER INSERT OR DELETE ON timesheets
+	FOR EACH ROW EXECUTE FUNCTION audit_log__record();
+CREATE TRIGGER timesheets__audit_update AFTER UPDATE ON timesheets
+	FOR EACH ROW WHEN (OLD IS DISTINCT FROM NEW) EXECUTE FUNCTION audit_log__record();
@@ -0,0 +1,19 @@
+DROP TRIGGER timesheets__soft_delete ON timesheets;
+DROP TRIGGER organizations__soft_delete ON organizations;
+DROP TRIGGER locations__soft_delete ON locations;
+DROP TRIGGER jobs__soft_delete ON jobs;
+DROP TRIGGER employees__soft_delete ON employees;
+DROP FUNCTION soft_delete__cascade;
+
+-- NOTE: rows which were soft deleted become visible again.
+ALTER TABLE timesheets DROP CONSTRAINT timesheets__no_overlap;
+ALTER TABLE timesheets ADD CONSTRAINT timesheets__no_overlap
+	EXCLUDE USING gist (employee_id WITH =, tstzrange(time_begin, time_end) WITH &&);
+
+ALTER TABLE timesheets DROP COLUMN deleted_at;
+ALTER TABLE organizations DROP COLUMN deleted_at;
+ALTER TABLE locations DROP COLUMN deleted_at;
+ALTER TABLE jobs DROP COLUMN deleted_at;
+ALTER TABLE expenses DROP COLUMN deleted_at;
+ALTER TABLE employees DROP COLUMN deleted_at;
+ALTER TABLE contact_information DROP COLUMN deleted_at;
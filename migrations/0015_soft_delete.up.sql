@@ -0,0 +1,49 @@
+-- NOTE: a row whose `deleted_at` is set has been soft deleted, and may be restored by setting it
+--       back to `NULL`.
+ALTER TABLE contact_information ADD COLUMN deleted_at timestamptz;
+ALTER TABLE employees ADD COLUMN deleted_at timestamptz;
+ALTER TABLE expenses ADD COLUMN deleted_at timestamptz;
+ALTER TABLE jobs ADD COLUMN deleted_at timestamptz;
+ALTER TABLE locations ADD COLUMN deleted_at timestamptz;
+ALTER TABLE organizations ADD COLUMN deleted_at timestamptz;
+ALTER TABLE timesheets ADD COLUMN deleted_at timestamptz;
+
+-- NOTE: a soft deleted timesheet no longer conflicts with the other timesheets of its employee.
+ALTER TABLE timesheets DROP CONSTRAINT timesheets__no_overlap;
+ALTER TABLE timesheets ADD CONSTRAINT timesheets__no_overlap
+	EXCLUDE USING gist (employee_id WITH =, tstzrange(time_begin, time_end) WITH &&)
+	WHERE (deleted_at IS NULL);
+
+-- NOTE: soft deleting a row also soft deletes its children which were not already soft deleted, and
+--       restoring it restores the children which were soft deleted along with it.
+--
+--       `TG_ARGV` is the `(table, column)` of the children which reference the row.
+CREATE FUNCTION soft_delete__cascade() RETURNS trigger
+	LANGUAGE plpgsql
+	AS $$
+	BEGIN
+		EXECUTE format(
+			'UPDATE %I SET deleted_at = $1 WHERE %I = $2 AND deleted_at IS NOT DISTINCT FROM $3',
+			TG_ARGV[0],
+			TG_ARGV[1]
+		) USING NEW.deleted_at, NEW.id, OLD.deleted_at;
+
+		RETURN NULL;
+	END;
+	$$;
+
+CREATE TRIGGER employees__soft_delete AFTER UPDATE OF deleted_at ON employees
+	FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+	EXECUTE FUNCTION soft_delete__cascade('timesheets', 'employee_id');
+CREATE TRIGGER jobs__soft_delete AFTER UPDATE OF deleted_at ON jobs
+	FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+	EXECUTE FUNCTION soft_delete__cascade('timesheets', 'job_id');
+CREATE TRIGGER locations__soft_delete AFTER UPDATE OF deleted_at ON locations
+	FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+	EXECUTE FUNCTION soft_delete__cascade('locations', 'outer_id');
+CREATE TRIGGER organizations__soft_delete AFTER UPDATE OF deleted_at ON organizations
+	FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+	EXECUTE FUNCTION soft_delete__cascade('jobs', 'client_id');
+CREATE TRIGGER timesheets__soft_delete AFTER UPDATE OF deleted_at ON timesheets
+	FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+	EXECUTE FUNCTION soft_delete__cascade('expenses', 'timesheet_id');
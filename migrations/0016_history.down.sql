@@ -0,0 +1,2 @@
+DROP INDEX audit_log__table_row_changed_idx;
+DELETE FROM audit_log WHERE changed_at = '0001-01-01 00:00:00+00';
@@ -0,0 +1,36 @@
+-- NOTE: rows which existed before their changes were recorded are given an `INSERT` at the earliest
+--       time which can be stored, so that they can be viewed as of any point in the past. They
+--       are given the oldest values which are known of them.
+INSERT INTO audit_log (table_name, row_id, operation, new_values, changed_at)
+	SELECT table_name, row_id, 'INSERT', old_values, '0001-01-01 00:00:00+00'
+	FROM (
+		SELECT DISTINCT ON (table_name, row_id) table_name, row_id, operation, old_values
+		FROM audit_log
+		ORDER BY table_name, row_id, id
+	) A
+	WHERE operation <> 'INSERT';
+
+INSERT INTO audit_log (table_name, row_id, operation, new_values, changed_at)
+	SELECT 'expenses', X.id, 'INSERT', to_jsonb(X), '0001-01-01 00:00:00+00'
+	FROM expenses X
+	WHERE NOT EXISTS (SELECT FROM audit_log A WHERE A.table_name = 'expenses' AND A.row_id = X.id);
+
+INSERT INTO audit_log (table_name, row_id, operation, new_values, changed_at)
+	SELECT 'jobs', J.id, 'INSERT', to_jsonb(J), '0001-01-01 00:00:00+00'
+	FROM jobs J
+	WHERE NOT EXISTS (SELECT FROM audit_log A WHERE A.table_name = 'jobs' AND A.row_id = J.id);
+
+INSERT INTO audit_log (table_name, row_id, operation, new_values, changed_at)
+	SELECT 'organizations', O.id, 'INSERT', to_jsonb(O), '0001-01-01 00:00:00+00'
+	FROM organizations O
+	WHERE NOT EXISTS (
+		SELECT FROM audit_log A WHERE A.table_name = 'organizations' AND A.row_id = O.id
+	);
+
+INSERT INTO audit_log (table_name, row_id, operation, new_values, changed_at)
+	SELECT 'timesheets', T.id, 'INSERT', to_jsonb(T), '0001-01-01 00:00:00+00'
+	FROM timesheets T
+	WHERE NOT EXISTS (SELECT FROM audit_log A WHERE A.table_name = 'timesheets' AND A.row_id = T.id);
+
+-- NOTE: finds the latest change to each row as of some time.
+CREATE INDEX audit_log__table_row_changed_idx ON audit_log (table_name, row_id, changed_at);
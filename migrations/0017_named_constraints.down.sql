@@ -0,0 +1,45 @@
+ALTER TABLE audit_log RENAME CONSTRAINT audit_log__pk TO audit_log_pkey;
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates__pk TO billing_rates_pkey;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information__pk TO contact_information_pkey;
+ALTER TABLE employees RENAME CONSTRAINT employees__pk TO employees_pkey;
+ALTER TABLE exchange_rates RENAME CONSTRAINT exchange_rates__pk TO exchange_rates_pkey;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates__pk TO expense_tax_rates_pkey;
+ALTER TABLE expenses RENAME CONSTRAINT expenses__pk TO expenses_pkey;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines__pk TO invoice_lines_pkey;
+ALTER TABLE invoice_numbers RENAME CONSTRAINT invoice_numbers__pk TO invoice_numbers_pkey;
+ALTER TABLE invoices RENAME CONSTRAINT invoices__pk TO invoices_pkey;
+ALTER TABLE job_adjustments RENAME CONSTRAINT job_adjustments__pk TO job_adjustments_pkey;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates__pk TO job_tax_rates_pkey;
+ALTER TABLE jobs RENAME CONSTRAINT jobs__pk TO jobs_pkey;
+ALTER TABLE locations RENAME CONSTRAINT locations__pk TO locations_pkey;
+ALTER TABLE organizations RENAME CONSTRAINT organizations__pk TO organizations_pkey;
+ALTER TABLE payments RENAME CONSTRAINT payments__pk TO payments_pkey;
+ALTER TABLE tax_rates RENAME CONSTRAINT tax_rates__pk TO tax_rates_pkey;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets__pk TO timesheets_pkey;
+
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates__employee_fk TO billing_rates_employee_id_fkey;
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates__job_fk TO billing_rates_job_id_fkey;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information__address_fk TO contact_information_address_id_fkey;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates__expense_fk TO expense_tax_rates_expense_id_fkey;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates__tax_rate_fk TO expense_tax_rates_tax_rate_id_fkey;
+ALTER TABLE expenses RENAME CONSTRAINT expenses__timesheet_fk TO expenses_timesheet_id_fkey;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines__adjustment_fk TO invoice_lines_adjustment_id_fkey;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines__expense_fk TO invoice_lines_expense_id_fkey;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines__invoice_fk TO invoice_lines_invoice_id_fkey;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines__timesheet_fk TO invoice_lines_timesheet_id_fkey;
+ALTER TABLE invoices RENAME CONSTRAINT invoices__job_fk TO invoices_job_id_fkey;
+ALTER TABLE job_adjustments RENAME CONSTRAINT job_adjustments__job_fk TO job_adjustments_job_id_fkey;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates__job_fk TO job_tax_rates_job_id_fkey;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates__tax_rate_fk TO job_tax_rates_tax_rate_id_fkey;
+ALTER TABLE jobs RENAME CONSTRAINT jobs__client_fk TO jobs_client_id_fkey;
+ALTER TABLE locations RENAME CONSTRAINT locations__outer_fk TO locations_outer_id_fkey;
+ALTER TABLE organizations RENAME CONSTRAINT organizations__location_fk TO organizations_location_id_fkey;
+ALTER TABLE payments RENAME CONSTRAINT payments__job_fk TO payments_job_id_fkey;
+ALTER TABLE tax_rates RENAME CONSTRAINT tax_rates__location_fk TO tax_rates_location_id_fkey;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets__employee_fk TO timesheets_employee_id_fkey;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets__job_fk TO timesheets_job_id_fkey;
+
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information__email_is_valid TO contact_information_email_check;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information__phone_is_valid TO contact_information_phone_check;
+
+ALTER DOMAIN currency RENAME CONSTRAINT currency__is_code TO currency_check;
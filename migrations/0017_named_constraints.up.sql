@@ -0,0 +1,48 @@
+-- NOTE: every constraint is named `table__description`, so that a violation of it can be turned into a
+--       `ValidationError`.
+
+ALTER TABLE audit_log RENAME CONSTRAINT audit_log_pkey TO audit_log__pk;
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates_pkey TO billing_rates__pk;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information_pkey TO contact_information__pk;
+ALTER TABLE employees RENAME CONSTRAINT employees_pkey TO employees__pk;
+ALTER TABLE exchange_rates RENAME CONSTRAINT exchange_rates_pkey TO exchange_rates__pk;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates_pkey TO expense_tax_rates__pk;
+ALTER TABLE expenses RENAME CONSTRAINT expenses_pkey TO expenses__pk;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines_pkey TO invoice_lines__pk;
+ALTER TABLE invoice_numbers RENAME CONSTRAINT invoice_numbers_pkey TO invoice_numbers__pk;
+ALTER TABLE invoices RENAME CONSTRAINT invoices_pkey TO invoices__pk;
+ALTER TABLE job_adjustments RENAME CONSTRAINT job_adjustments_pkey TO job_adjustments__pk;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates_pkey TO job_tax_rates__pk;
+ALTER TABLE jobs RENAME CONSTRAINT jobs_pkey TO jobs__pk;
+ALTER TABLE locations RENAME CONSTRAINT locations_pkey TO locations__pk;
+ALTER TABLE organizations RENAME CONSTRAINT organizations_pkey TO organizations__pk;
+ALTER TABLE payments RENAME CONSTRAINT payments_pkey TO payments__pk;
+ALTER TABLE tax_rates RENAME CONSTRAINT tax_rates_pkey TO tax_rates__pk;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets_pkey TO timesheets__pk;
+
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates_employee_id_fkey TO billing_rates__employee_fk;
+ALTER TABLE billing_rates RENAME CONSTRAINT billing_rates_job_id_fkey TO billing_rates__job_fk;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information_address_id_fkey TO contact_information__address_fk;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates_expense_id_fkey TO expense_tax_rates__expense_fk;
+ALTER TABLE expense_tax_rates RENAME CONSTRAINT expense_tax_rates_tax_rate_id_fkey TO expense_tax_rates__tax_rate_fk;
+ALTER TABLE expenses RENAME CONSTRAINT expenses_timesheet_id_fkey TO expenses__timesheet_fk;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines_adjustment_id_fkey TO invoice_lines__adjustment_fk;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines_expense_id_fkey TO invoice_lines__expense_fk;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines_invoice_id_fkey TO invoice_lines__invoice_fk;
+ALTER TABLE invoice_lines RENAME CONSTRAINT invoice_lines_timesheet_id_fkey TO invoice_lines__timesheet_fk;
+ALTER TABLE invoices RENAME CONSTRAINT invoices_job_id_fkey TO invoices__job_fk;
+ALTER TABLE job_adjustments RENAME CONSTRAINT job_adjustments_job_id_fkey TO job_adjustments__job_fk;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates_job_id_fkey TO job_tax_rates__job_fk;
+ALTER TABLE job_tax_rates RENAME CONSTRAINT job_tax_rates_tax_rate_id_fkey TO job_tax_rates__tax_rate_fk;
+ALTER TABLE jobs RENAME CONSTRAINT jobs_client_id_fkey TO jobs__client_fk;
+ALTER TABLE locations RENAME CONSTRAINT locations_outer_id_fkey TO locations__outer_fk;
+ALTER TABLE organizations RENAME CONSTRAINT organizations_location_id_fkey TO organizations__location_fk;
+ALTER TABLE payments RENAME CONSTRAINT payments_job_id_fkey TO payments__job_fk;
+ALTER TABLE tax_rates RENAME CONSTRAINT tax_rates_location_id_fkey TO tax_rates__location_fk;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets_employee_id_fkey TO timesheets__employee_fk;
+ALTER TABLE timesheets RENAME CONSTRAINT timesheets_job_id_fkey TO timesheets__job_fk;
+
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information_email_check TO contact_information__email_is_valid;
+ALTER TABLE contact_information RENAME CONSTRAINT contact_information_phone_check TO contact_information__phone_is_valid;
+
+ALTER DOMAIN currency RENAME CONSTRAINT currency_check TO currency__is_code;
@@ -1,9 +1,11 @@
 mod date_time_ext;
 mod interval;
 mod location_recursive_cte;
+mod money;
 mod timestamptz;
 
 pub(crate) use date_time_ext::DateTimeExt;
 pub(crate) use interval::PgInterval;
 pub(crate) use location_recursive_cte::PgLocationRecursiveCte;
+pub(crate) use money::PgMoney;
 pub(crate) use timestamptz::PgTimestampTz;
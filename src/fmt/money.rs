@@ -0,0 +1,9 @@
+use clinvoice_finance::Money;
+
+/// A [`Money`] which is stored on the database as two columns: an amount, and the
+/// [`util::currency_column`](crate::schema::util::currency_column) of that amount.
+///
+/// Unlike the other types in this module this does not implement [`Display`](core::fmt::Display),
+/// as it cannot be written as a single value.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct PgMoney(pub(crate) Money);
@@ -3,34 +3,90 @@
 //! This module implements adapters (and associated adapter types such as
 //! [`Deletable`](clinvoice_adapter::Deletable)) for a Postgres filesystem.
 
+mod adjustment;
+mod audit;
+mod billable_time;
+mod billing_rate;
+mod columns;
 mod contact;
 mod employee;
+mod error;
+mod exchange_rates;
+mod exchange_rates_source;
+mod expense_total;
 mod expenses;
 mod initializable;
+mod invoice;
+mod issued_invoice;
 mod job;
 mod location;
+mod migration;
 mod organization;
+mod outstanding_balance;
+mod payment;
+mod rated_timesheet;
+mod receivables_aging;
+mod retrieve_options;
+mod soft_deletable;
+mod tax_rate;
+mod taxed_amount;
 mod timesheet;
+mod timesheet_overlap;
 mod util;
+mod validation_error;
 mod write_where_clause;
 
+use core::fmt::Display;
+
+pub use adjustment::{Adjustment, AdjustmentKind, MatchAdjustment, PgAdjustment};
+pub use audit::{AuditChange, AuditEntry, AuditValues, AuditedTable, PgAudit};
+pub use billable_time::BillableTime;
+pub use billing_rate::{BillingRate, BillingRateTarget, MatchBillingRate, PgBillingRate};
 use clinvoice_adapter::{
 	fmt::{sql, As, ColumnsToSql, QueryBuilderExt, SnakeCase, TableToSql},
 	WriteWhereClause,
 };
 use clinvoice_match::Match;
 use clinvoice_schema::Id;
+pub use columns::{
+	AdjustmentColumns,
+	BillingRateColumns,
+	IssuedInvoiceColumns,
+	PaymentColumns,
+	TaxRateColumns,
+};
 pub use contact::PgContact;
 pub use employee::PgEmployee;
+pub use error::Error;
+pub use exchange_rates::PgExchangeRates;
+pub use exchange_rates_source::{EcbExchangeRates, ExchangeRatesFile, ExchangeRatesSource};
+pub use expense_total::{ExpenseGroupBy, ExpenseTotal};
 pub use expenses::PgExpenses;
+pub use invoice::PgInvoice;
+pub use issued_invoice::{InvoiceItem, InvoiceLine, IssuedInvoice, MatchIssuedInvoice};
 pub use job::PgJob;
 pub use location::PgLocation;
+pub use migration::{SchemaDifference, SchemaDrift, SchemaTooNew};
 pub use organization::PgOrganization;
+pub use outstanding_balance::OutstandingBalance;
+pub use payment::{MatchPayment, Payment, PgPayment};
+pub use rated_timesheet::RatedTimesheet;
+pub use receivables_aging::ReceivablesAging;
+pub use retrieve_options::{Order, OrderBy, Page, RetrieveOptions, SortValue};
+pub use soft_deletable::SoftDeletable;
 use sqlx::{Executor, Postgres, QueryBuilder, Result, Transaction};
+pub use tax_rate::{MatchTaxRate, PgTaxRate, TaxRate};
+pub use taxed_amount::TaxedAmount;
 pub use timesheet::PgTimesheet;
+pub use timesheet_overlap::TimesheetOverlap;
+pub use validation_error::{ValidationError, ValidationRule};
 
 /// The struct which implements several [`clinvoice_adapter`] traits to allow CLInvoice to function
 /// within a Postgres database environment.
+///
+/// Before any of them are used, the database should be initialized (see
+/// [`Initializable`](clinvoice_adapter::Initializable)) or connected to via [`PgSchema::connect`],
+/// both of which refuse to work with a database that was migrated by a newer version of this crate.
 pub struct PgSchema;
 
 impl PgSchema
@@ -61,7 +117,69 @@ impl PgSchema
 			&mut query,
 		);
 
-		query.prepare().execute(connection).await?;
+		// NOTE: the value of a row which is still referred to is reported by the database.
+		query
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+
+	/// Via `connection`, execute `UPDATE {table} SET deleted_at = now() WHERE ((id = №) OR … OR
+	/// (id = №)) AND deleted_at IS NULL` for each [`Id`] in `ids` when `deleted` is `true`, or
+	/// `UPDATE {table} SET deleted_at = NULL WHERE (id = №) OR … OR (id = №)` otherwise.
+	///
+	/// Rows which were already [soft deleted](SoftDeletable::soft_delete) keep the time at which
+	/// that first happened.
+	async fn set_deleted<'args, TConn, TIter, TTable>(
+		connection: TConn,
+		ids: TIter,
+		deleted: bool,
+	) -> Result<()>
+	where
+		TConn: Executor<'args, Database = Postgres>,
+		TIter: Iterator<Item = Id>,
+		TTable: TableToSql,
+	{
+		let mut peekable_entities = ids.peekable();
+
+		// There is nothing to do
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(sql::UPDATE);
+		query
+			.push(TTable::TABLE_NAME)
+			.push(sql::SET)
+			.push(util::DELETED_AT)
+			.push(" = ")
+			.push(if deleted { "now()" } else { sql::NULL });
+
+		let context = PgSchema::write_where_clause(
+			Default::default(),
+			"id",
+			&Match::Or(peekable_entities.map(Match::from).collect()),
+			&mut query,
+		);
+
+		if deleted
+		{
+			query
+				.push(context)
+				.push(util::DELETED_AT)
+				.push(sql::IS)
+				.push(sql::NULL);
+		}
+
+		query
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
 
 		Ok(())
 	}
@@ -75,6 +193,7 @@ impl PgSchema
 	/// * [`ColumnsToSql::push_columns`] for how the order of columns to bind in `push_values`.
 	/// * [`ColumnsToSql::push_set`] for how the `SET` clause is generated.
 	/// * [`ColumnsToSql::push_update_where`] for how the `WHERE` condition is generated.
+	/// * [`PgSchema::update_with`] to update columns which are not part of `TColumns`.
 	/// * [`QueryBuilder::push_values`] for what function to use for `push_values`.
 	async fn update<'args, TColumns, TFn>(
 		connection: &mut Transaction<'_, Postgres>,
@@ -84,6 +203,23 @@ impl PgSchema
 	where
 		TColumns: ColumnsToSql,
 		TFn: FnOnce(&mut QueryBuilder<'args, Postgres>),
+	{
+		Self::update_with::<_, _, &str>(connection, columns, &[], push_values).await
+	}
+
+	/// The same as [`PgSchema::update`], except that the `extra_columns` (which are not part of
+	/// `TColumns`) are also updated. `push_values` must bind the `extra_columns` after the
+	/// `columns`, in the order they were given.
+	async fn update_with<'args, TColumns, TFn, TExtra>(
+		connection: &mut Transaction<'_, Postgres>,
+		columns: TColumns,
+		extra_columns: &[TExtra],
+		push_values: TFn,
+	) -> Result<()>
+	where
+		TColumns: ColumnsToSql,
+		TExtra: Display,
+		TFn: FnOnce(&mut QueryBuilder<'args, Postgres>),
 	{
 		let mut query = QueryBuilder::new(sql::UPDATE);
 
@@ -93,6 +229,15 @@ impl PgSchema
 
 		let values_alias = SnakeCase::from((TColumns::DEFAULT_ALIAS, 'V'));
 		columns.push_set_to(&mut query, values_alias);
+		extra_columns.iter().for_each(|c| {
+			query
+				.push(',')
+				.push(c)
+				.push('=')
+				.push(values_alias)
+				.push('.')
+				.push(c);
+		});
 
 		query.push(sql::FROM).push('(');
 
@@ -103,9 +248,13 @@ impl PgSchema
 			.push(sql::AS)
 			.push(values_alias)
 			.push(" (")
-			.push_columns(&columns)
-			.push(')')
-			.push(sql::WHERE);
+			.push_columns(&columns);
+
+		extra_columns.iter().for_each(|c| {
+			query.push(',').push(c);
+		});
+
+		query.push(')').push(sql::WHERE);
 
 		columns.push_update_where_to(&mut query, TColumns::DEFAULT_ALIAS, values_alias);
 
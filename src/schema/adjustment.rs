@@ -0,0 +1,283 @@
+mod deletable;
+mod retrievable;
+mod updatable;
+
+use clinvoice_finance::{Decimal, Money};
+use clinvoice_match::{Match, MatchStr};
+use clinvoice_schema::{Id, Job};
+use sqlx::{postgres::PgRow, Executor, Postgres, Result, Row};
+
+use super::{util, Error};
+
+/// How an [`Adjustment`] changes the amount billed for a [`Job`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AdjustmentKind
+{
+	/// Add this [`Money`] to the amount billed (e.g. `-200` for a goodwill credit). It must be in
+	/// the same currency as the `invoice.hourly_rate` of the [`Job`].
+	Fixed(Money),
+
+	/// Add this percentage of the amount billed for the time worked on the [`Job`] (e.g. `-10`
+	/// for a 10% discount). It may not be less than `-100`.
+	Percentage(Decimal),
+}
+
+/// A discount or surcharge which was agreed upon for a [`Job`].
+///
+/// [`Adjustment`]s are applied to the time worked on a [`Job`], but not its
+/// [`Expense`](clinvoice_schema::Expense)s.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Adjustment
+{
+	/// The reference number of this [`Adjustment`], which is unique among all [`Adjustment`]s.
+	pub id: Id,
+
+	/// The [`Id`] of the [`Job`] which is adjusted.
+	pub job_id: Id,
+
+	/// How the [`Job`] is adjusted.
+	pub kind: AdjustmentKind,
+
+	/// Why the [`Job`] is adjusted (e.g. "Loyalty discount"), as it should appear on an invoice.
+	pub reason: String,
+}
+
+/// An [`Adjustment`] with [matchable](clinvoice_match) fields.
+///
+/// The `kind` of an [`Adjustment`] cannot be matched.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchAdjustment
+{
+	#[allow(missing_docs)]
+	pub id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub job_id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub reason: MatchStr<String>,
+}
+
+impl From<Id> for MatchAdjustment
+{
+	fn from(id: Id) -> Self
+	{
+		Self {
+			id: id.into(),
+			..Default::default()
+		}
+	}
+}
+
+/// Implementor of the [`Deletable`](clinvoice_adapter::Deletable),
+/// [`Retrievable`](clinvoice_adapter::Retrievable), and [`Updatable`](clinvoice_adapter::Updatable)
+/// traits for [`Adjustment`]s stored in the [`Postgres`] database.
+pub struct PgAdjustment;
+
+impl PgAdjustment
+{
+	/// Adjust the `job` (via `connection`) by the `kind`, for the given `reason`.
+	///
+	/// # Errors
+	///
+	/// * [`Error::CheckViolation`] if the `kind` is an [`AdjustmentKind::Percentage`] less than
+	///   `-100`.
+	///
+	/// When the `kind` is an [`AdjustmentKind::Fixed`] in a different currency than the `job`,
+	/// the `connection` will fail to commit instead, so that a [`Job`] and its [`Adjustment`]s may
+	/// change currency together.
+	pub async fn create<'c, TConn>(
+		connection: TConn,
+		job: &Job,
+		kind: AdjustmentKind,
+		reason: String,
+	) -> Result<Adjustment, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let (amount, currency, percentage) = Self::kind_to_columns(kind);
+		let row = sqlx::query!(
+			"INSERT INTO job_adjustments
+				(job_id, amount, amount_currency, percentage, reason)
+			VALUES
+				($1,     $2,     $3,              $4,         $5)
+			RETURNING id;",
+			job.id,
+			amount,
+			currency as _,
+			percentage,
+			reason,
+		)
+		.fetch_one(connection)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"amount" => amount.map(|d| d.to_string()),
+				"amount_currency" => currency.clone(),
+				"job_id" => Some(job.id.to_string()),
+				"percentage" => percentage.map(|p| p.to_string()),
+				_ => None,
+			})
+		})?;
+
+		Ok(Adjustment {
+			id: row.id,
+			job_id: job.id,
+			kind,
+			reason,
+		})
+	}
+
+	/// Split the `kind` into the `amount`, `amount_currency`, and `percentage` columns.
+	pub(super) fn kind_to_columns(
+		kind: AdjustmentKind,
+	) -> (Option<Decimal>, Option<String>, Option<Decimal>)
+	{
+		match kind
+		{
+			AdjustmentKind::Fixed(money) =>
+			{
+				(Some(money.amount), Some(money.currency.to_string()), None)
+			},
+			AdjustmentKind::Percentage(percentage) => (None, None, Some(percentage)),
+		}
+	}
+
+	/// Create an [`Adjustment`] from some `row` which [`Retrievable::retrieve`] selected.
+	///
+	/// [`Retrievable::retrieve`]: clinvoice_adapter::Retrievable::retrieve
+	pub(super) fn row_to_view(row: &PgRow) -> Result<Adjustment>
+	{
+		let kind = match (
+			row.try_get::<Option<Decimal>, _>("amount")?,
+			row.try_get::<Option<String>, _>("amount_currency")?,
+			row.try_get::<Option<Decimal>, _>("percentage")?,
+		)
+		{
+			(Some(amount), Some(currency), None) =>
+			{
+				AdjustmentKind::Fixed(util::money_from(amount, &currency)?)
+			},
+			(None, None, Some(percentage)) => AdjustmentKind::Percentage(percentage),
+			_ =>
+			{
+				return Err(sqlx::Error::Decode(
+					"an adjustment must be either a fixed amount or a percentage".into(),
+				))
+			},
+		};
+
+		Ok(Adjustment {
+			id: row.try_get("id")?,
+			job_id: row.try_get("job_id")?,
+			kind,
+			reason: row.try_get("reason")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::schema::JobAdapter;
+	use clinvoice_finance::Decimal;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::{AdjustmentKind, PgAdjustment};
+	use crate::schema::{util, PgJob};
+
+	#[tokio::test]
+	async fn create()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		let (discount, credit) = futures::try_join!(
+			PgAdjustment::create(
+				&connection,
+				&job,
+				AdjustmentKind::Percentage(Decimal::new(-10, 0)),
+				"Loyalty discount".into(),
+			),
+			PgAdjustment::create(
+				&connection,
+				&job,
+				AdjustmentKind::Fixed(Money::new(-200_00, 2, Currency::Usd)),
+				"Goodwill credit".into(),
+			),
+		)
+		.unwrap();
+
+		let rows = sqlx::query!(
+			r#"SELECT id, amount, amount_currency AS "amount_currency?", percentage, reason
+				FROM job_adjustments
+				WHERE job_id = $1
+				ORDER BY id;"#,
+			job.id,
+		)
+		.fetch_all(&connection)
+		.await
+		.unwrap();
+
+		assert_eq!(rows.len(), 2);
+		assert_eq!(rows[0].id, discount.id);
+		assert_eq!(rows[0].amount, None);
+		assert_eq!(rows[0].percentage, Some(Decimal::new(-10, 0)));
+		assert_eq!(rows[0].reason, discount.reason);
+		assert_eq!(rows[1].id, credit.id);
+		assert_eq!(rows[1].amount, Some(Decimal::new(-200_00, 2)));
+		assert_eq!(rows[1].amount_currency, Some(Currency::Usd.to_string()));
+		assert_eq!(rows[1].percentage, None);
+		assert_eq!(rows[1].reason, credit.reason);
+
+		// A fixed adjustment must be in the job's currency.
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgAdjustment::create(
+				&mut transaction,
+				&job,
+				AdjustmentKind::Fixed(Money::new(-200_00, 2, Currency::Eur)),
+				"Goodwill credit".into(),
+			)
+			.await
+			.unwrap();
+			assert!(transaction.commit().await.is_err());
+		}
+
+		// A job cannot be discounted by more than 100%.
+		assert!(PgAdjustment::create(
+			&connection,
+			&job,
+			AdjustmentKind::Percentage(Decimal::new(-101, 0)),
+			"Too generous".into(),
+		)
+		.await
+		.is_err());
+	}
+}
@@ -0,0 +1,35 @@
+use clinvoice_adapter::Deletable;
+use sqlx::{Executor, Postgres, Result};
+
+use super::{Adjustment, PgAdjustment};
+use crate::schema::util;
+
+#[async_trait::async_trait]
+impl Deletable for PgAdjustment
+{
+	type Db = Postgres;
+	type Entity = Adjustment;
+
+	async fn delete<'c, 'e, 'i, TConn, TIter>(connection: TConn, entities: TIter) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Self::Db>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let ids: Vec<_> = entities.map(|e| e.id).collect();
+
+		// There is nothing to do
+		if ids.is_empty()
+		{
+			return Ok(());
+		}
+
+		sqlx::query!("DELETE FROM job_adjustments WHERE id = ANY($1);", &ids)
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,42 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Retrievable, WriteWhereClause};
+use futures::{future, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result};
+
+use super::{Adjustment, MatchAdjustment, PgAdjustment};
+use crate::PgSchema;
+
+/// Implementors of this trait are capable of being retrieved from a [`Database`].
+#[async_trait::async_trait]
+impl Retrievable for PgAdjustment
+{
+	/// The [`Database`] where data of type [`Updatable::Entity`] is being stored.
+	type Db = Postgres;
+	/// The type of data that is to be [`update`](Deletable::update)d.
+	type Entity = Adjustment;
+	/// The type used for [match](clinvoice_match)ing.
+	type Match = MatchAdjustment;
+
+	/// Retrieve all [`Adjustment`]s (via `connection`) that match the `match_condition`.
+	///
+	/// The [`Adjustment`]s are sorted in the order they were created.
+	async fn retrieve(
+		connection: &Pool<Postgres>,
+		match_condition: &Self::Match,
+	) -> Result<Vec<Self::Entity>>
+	{
+		let mut query = QueryBuilder::new(
+			"SELECT A.id, A.job_id, A.amount, A.amount_currency, A.percentage, A.reason
+			FROM job_adjustments A",
+		);
+
+		PgSchema::write_where_clause(Default::default(), 'A', match_condition, &mut query);
+
+		query
+			.push(" ORDER BY A.id;")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgAdjustment::row_to_view(&row)))
+			.try_collect()
+			.await
+	}
+}
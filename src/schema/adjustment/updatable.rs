@@ -0,0 +1,181 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Updatable};
+use sqlx::{Postgres, QueryBuilder, Result, Transaction};
+
+use super::{Adjustment, PgAdjustment};
+use crate::schema::util;
+
+#[async_trait::async_trait]
+impl Updatable for PgAdjustment
+{
+	type Db = Postgres;
+	type Entity = Adjustment;
+
+	async fn update<'e, 'i, TIter>(
+		connection: &mut Transaction<Self::Db>,
+		entities: TIter,
+	) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.clone().peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(
+			"UPDATE job_adjustments AS A SET
+				amount = V.amount,
+				amount_currency = V.amount_currency,
+				job_id = V.job_id,
+				percentage = V.percentage,
+				reason = V.reason
+			FROM (",
+		);
+
+		query
+			.push_values(peekable_entities, |mut q, e| {
+				let (amount, currency, percentage) = PgAdjustment::kind_to_columns(e.kind);
+				q.push_bind(e.id)
+					.push_bind(amount)
+					.push_bind(currency)
+					.push_bind(e.job_id)
+					.push_bind(percentage)
+					.push_bind(&e.reason);
+			})
+			.push(
+				") AS V (id, amount, amount_currency, job_id, percentage, reason)
+				WHERE A.id = V.id;",
+			)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities, |a| {
+						let (amount, currency, percentage) = PgAdjustment::kind_to_columns(a.kind);
+						match field
+						{
+							"amount" => amount.map(|d| d.to_string()),
+							"amount_currency" => currency,
+							"job_id" => Some(a.job_id.to_string()),
+							"percentage" => percentage.map(|p| p.to_string()),
+							_ => None,
+						}
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{schema::JobAdapter, Deletable, Retrievable, Updatable};
+	use clinvoice_finance::Decimal;
+	use clinvoice_match::MatchStr;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, Adjustment, AdjustmentKind, MatchAdjustment, PgAdjustment, PgJob};
+
+	#[tokio::test]
+	async fn update()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		let (discount, credit) = futures::try_join!(
+			PgAdjustment::create(
+				&connection,
+				&job,
+				AdjustmentKind::Percentage(Decimal::new(-10, 0)),
+				"Loyalty discount".into(),
+			),
+			PgAdjustment::create(
+				&connection,
+				&job,
+				AdjustmentKind::Fixed(Money::new(-200_00, 2, Currency::Usd)),
+				"Goodwill credit".into(),
+			),
+		)
+		.unwrap();
+
+		let match_adjustment = MatchAdjustment {
+			job_id: job.id.into(),
+			..Default::default()
+		};
+
+		let mut expected = [discount.clone(), credit.clone()];
+		expected.sort_by_key(|a| a.id);
+		assert_eq!(
+			PgAdjustment::retrieve(&connection, &match_adjustment)
+				.await
+				.unwrap(),
+			expected,
+		);
+
+		let updated = Adjustment {
+			kind: AdjustmentKind::Fixed(Money::new(-50_00, 2, Currency::Usd)),
+			reason: "Late delivery".into(),
+			..discount
+		};
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgAdjustment::update(&mut transaction, [&updated].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		assert_eq!(
+			PgAdjustment::retrieve(&connection, &MatchAdjustment {
+				reason: MatchStr::Contains("Late".into()),
+				..match_adjustment.clone()
+			})
+			.await
+			.unwrap(),
+			[updated],
+		);
+
+		PgAdjustment::delete(&connection, [&credit].into_iter())
+			.await
+			.unwrap();
+
+		assert!(PgAdjustment::retrieve(&connection, &credit.id.into())
+			.await
+			.unwrap()
+			.is_empty());
+	}
+}
@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Id,
+};
+use sqlx::{Executor, Pool, Postgres, QueryBuilder, Result, Transaction};
+
+use super::Error;
+
+/// The value of each column of an audited row, as text. A column which was `NULL` has [`None`] as
+/// its value.
+pub type AuditValues = BTreeMap<String, Option<String>>;
+
+/// A change which was made to an audited row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AuditChange
+{
+	/// The row was deleted. These were its values beforehand.
+	Delete(AuditValues),
+
+	/// The row was inserted with these values.
+	Insert(AuditValues),
+
+	/// The row was updated, and at least one of its values changed.
+	Update
+	{
+		/// The values of the row after the update.
+		new: AuditValues,
+
+		/// The values of the row before the update.
+		old: AuditValues,
+	},
+}
+
+/// A single change to some row of an [`AuditedTable`].
+///
+/// # See also
+///
+/// * [`PgAudit::retrieve_history`], which retrieves these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct AuditEntry
+{
+	/// Who made the change, as given to [`PgAudit::set_actor`] (if at all).
+	pub actor: Option<String>,
+
+	/// What was changed.
+	pub change: AuditChange,
+
+	/// When the transaction which made the change began.
+	pub date: DateTime<Utc>,
+
+	/// The reference number of this [`AuditEntry`], which is unique among all [`AuditEntry`]s and
+	/// increases with each change.
+	pub id: Id,
+}
+
+/// A table whose changes are recorded as [`AuditEntry`]s.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AuditedTable
+{
+	/// The table of [`Expense`](clinvoice_schema::Expense)s.
+	Expenses,
+
+	/// The table of [`Job`](clinvoice_schema::Job)s.
+	Jobs,
+
+	/// The table of [`Organization`](clinvoice_schema::Organization)s.
+	Organizations,
+
+	/// The table of [`Timesheet`](clinvoice_schema::Timesheet)s.
+	Timesheets,
+}
+
+impl AuditedTable
+{
+	/// Every [`AuditedTable`].
+	pub(super) const ALL: [Self; 4] = [
+		Self::Expenses,
+		Self::Jobs,
+		Self::Organizations,
+		Self::Timesheets,
+	];
+
+	/// The name of this table in the database.
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Self::Expenses => "expenses",
+			Self::Jobs => "jobs",
+			Self::Organizations => "organizations",
+			Self::Timesheets => "timesheets",
+		}
+	}
+}
+
+/// Records who changed the [`AuditedTable`]s, and retrieves the [`AuditEntry`]s of those changes
+/// from the [`Postgres`] database.
+///
+/// Changes are recorded whether they were made by this crate or not.
+pub struct PgAudit;
+
+impl PgAudit
+{
+	/// Retrieve every [`AuditEntry`] for the row of the `table` which has the `id` (via
+	/// `connection`), from oldest to newest.
+	///
+	/// The history of a row is kept after it is deleted. Rows which existed before their changes
+	/// were recorded begin with an [`AuditChange::Insert`] of the oldest values known of them, dated
+	/// to the first year of the common era.
+	pub async fn retrieve_history<'c, TConn>(
+		connection: TConn,
+		table: AuditedTable,
+		id: Id,
+	) -> Result<Vec<AuditEntry>, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		sqlx::query!(
+			r#"SELECT
+					id,
+					actor,
+					changed_at,
+					operation,
+					ARRAY(SELECT (key, value) FROM jsonb_each_text(new_values))
+						AS "new_values!: Vec<(String, Option<String>)>",
+					ARRAY(SELECT (key, value) FROM jsonb_each_text(old_values))
+						AS "old_values!: Vec<(String, Option<String>)>"
+				FROM audit_log
+				WHERE table_name = $1 AND row_id = $2
+				ORDER BY changed_at, id;"#,
+			table.name(),
+			id,
+		)
+		.fetch_all(connection)
+		.await?
+		.into_iter()
+		.map(|row| {
+			let new = row.new_values.into_iter().collect();
+			let old = row.old_values.into_iter().collect();
+
+			Ok(AuditEntry {
+				actor: row.actor,
+				change: match row.operation.as_str()
+				{
+					"DELETE" => AuditChange::Delete(old),
+					"INSERT" => AuditChange::Insert(new),
+					"UPDATE" => AuditChange::Update { new, old },
+					o =>
+					{
+						return Err(
+							sqlx::Error::Decode(format!("unknown audit operation {o:?}").into()).into(),
+						)
+					},
+				},
+				date: row.changed_at,
+				id: row.id,
+			})
+		})
+		.collect()
+	}
+
+	/// Write a CTE to the `query` for each of the [`AuditedTable`]s, separated by commas. Each CTE
+	/// has the same name as its table, and contains the rows of that table `as_of` some time, so
+	/// that the rest of the `query` sees those rows instead of the current ones.
+	pub(super) fn push_as_of(query: &mut QueryBuilder<Postgres>, as_of: DateTime<Utc>)
+	{
+		AuditedTable::ALL
+			.into_iter()
+			.enumerate()
+			.for_each(|(i, table)| {
+				if i > 0
+				{
+					query.push(',');
+				}
+
+				// NOTE: the latest change to each row as of the time determines its values, unless
+				//       that change deleted it.
+				query
+					.push(table.name())
+					.push(" AS (SELECT (jsonb_populate_record(NULL::")
+					.push(table.name())
+					.push(
+						", A.new_values)).* FROM (SELECT DISTINCT ON (row_id) operation, new_values \
+						 FROM audit_log WHERE table_name = '",
+					)
+					.push(table.name())
+					.push("' AND changed_at <= ")
+					.push_bind(as_of)
+					.push(
+						" ORDER BY row_id, changed_at DESC, id DESC) A WHERE A.operation <> 'DELETE')",
+					);
+			});
+	}
+
+	/// Begin a transaction (via `connection`) in which every change is recorded as made by the
+	/// `actor`.
+	pub async fn begin_as<'c>(
+		connection: &'c Pool<Postgres>,
+		actor: &str,
+	) -> Result<Transaction<'c, Postgres>, Error>
+	{
+		let mut transaction = connection.begin().await?;
+		Self::set_actor(&mut transaction, actor).await?;
+		Ok(transaction)
+	}
+
+	/// Record the `actor` as the one who made every change for the rest of the transaction (via
+	/// `connection`).
+	///
+	/// Changes made before this is called, or in other transactions, are not affected, so it must
+	/// be called before the changes which it should cover. Prefer [`PgAudit::begin_as`] unless the
+	/// transaction has already begun.
+	pub async fn set_actor(
+		connection: &mut Transaction<'_, Postgres>,
+		actor: &str,
+	) -> Result<(), Error>
+	{
+		sqlx::query!("SELECT set_config('clinvoice.actor', $1, true);", actor)
+			.execute(connection)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{schema::JobAdapter, Deletable, Updatable};
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::{AuditChange, AuditValues, AuditedTable, PgAudit};
+	use crate::schema::{util, PgJob};
+
+	#[tokio::test]
+	async fn retrieve_history()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let mut job = PgJob::create(
+			&connection,
+			organization.clone(),
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		job.invoice.hourly_rate = Money::new(25_00, 2, Currency::Usd);
+
+		// {{{
+		let mut transaction = PgAudit::begin_as(&connection, "Bookkeeper").await.unwrap();
+		PgJob::update(&mut transaction, [&job].into_iter())
+			.await
+			.unwrap();
+
+		// Nothing changed, so nothing is recorded.
+		PgJob::update(&mut transaction, [&job].into_iter())
+			.await
+			.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		PgJob::delete(&connection, [&job].into_iter())
+			.await
+			.unwrap();
+
+		let history = PgAudit::retrieve_history(&connection, AuditedTable::Jobs, job.id)
+			.await
+			.unwrap();
+
+		assert_eq!(history.len(), 3);
+		assert!(history.windows(2).all(|w| w[0].id < w[1].id));
+		assert_eq!(
+			history
+				.iter()
+				.map(|e| e.actor.as_deref())
+				.collect::<Vec<_>>(),
+			[None, Some("Bookkeeper"), None],
+		);
+
+		let hourly_rate = |values: &AuditValues| values["invoice_hourly_rate"].clone();
+
+		match &history[0].change
+		{
+			AuditChange::Insert(new) => assert_eq!(hourly_rate(new), Some("20.00".into())),
+			c => panic!("expected an insert, got {c:?}"),
+		}
+
+		match &history[1].change
+		{
+			AuditChange::Update { new, old } =>
+			{
+				assert_eq!(hourly_rate(old), Some("20.00".into()));
+				assert_eq!(hourly_rate(new), Some("25.00".into()));
+			},
+			c => panic!("expected an update, got {c:?}"),
+		}
+
+		match &history[2].change
+		{
+			AuditChange::Delete(old) => assert_eq!(hourly_rate(old), Some("25.00".into())),
+			c => panic!("expected a delete, got {c:?}"),
+		}
+
+		assert_eq!(
+			PgAudit::retrieve_history(&connection, AuditedTable::Organizations, organization.id)
+				.await
+				.unwrap()
+				.len(),
+			1,
+		);
+	}
+}
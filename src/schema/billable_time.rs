@@ -0,0 +1,36 @@
+use core::time::Duration;
+
+use clinvoice_finance::Money;
+use clinvoice_schema::Id;
+
+/// The time which has been worked on some [`Job`](clinvoice_schema::Job), and what it is worth.
+///
+/// Only [`Timesheet`](clinvoice_schema::Timesheet)s which have a `time_end` are counted, since the
+/// rest are still being worked on.
+///
+/// # See also
+///
+/// * [`PgJob::retrieve_billable_time`](crate::schema::PgJob::retrieve_billable_time), which
+///   computes these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BillableTime
+{
+	/// The [`Money`] owed for the time worked, at the rate of each
+	/// [`Timesheet`](clinvoice_schema::Timesheet) (see
+	/// [`RatedTimesheet`](crate::schema::RatedTimesheet)), after the
+	/// [`Adjustment`](crate::schema::Adjustment)s of the [`Job`](clinvoice_schema::Job).
+	pub amount: Money,
+
+	/// The total time which was worked.
+	pub duration: Duration,
+
+	/// The `duration`, rounded to the nearest `increment` of the
+	/// [`Job`](clinvoice_schema::Job).
+	///
+	/// The time worked at each hourly rate is rounded separately, and then summed. This is the time
+	/// which the `amount` is billed for.
+	pub duration_rounded: Duration,
+
+	/// The [`Id`] of the [`Job`](clinvoice_schema::Job) which the time was worked on.
+	pub job_id: Id,
+}
@@ -0,0 +1,255 @@
+mod deletable;
+mod retrievable;
+mod updatable;
+
+use clinvoice_finance::Money;
+use clinvoice_match::{Match, MatchOption};
+use clinvoice_schema::{Id, Job};
+use sqlx::{postgres::PgRow, Executor, Postgres, Result, Row};
+
+use super::{util, Error};
+
+/// Who a [`BillingRate`] applies to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum BillingRateTarget
+{
+	/// The [`Employee`](clinvoice_schema::Employee) with this [`Id`].
+	Employee(Id),
+
+	/// Every [`Employee`](clinvoice_schema::Employee) with this `title`.
+	Title(String),
+}
+
+/// The hourly rate which some [`Employee`](clinvoice_schema::Employee)s bill at while working on a
+/// [`Job`], instead of the `invoice.hourly_rate` of the [`Job`].
+///
+/// When more than one [`BillingRate`] could apply to a
+/// [`Timesheet`](clinvoice_schema::Timesheet), a [`BillingRateTarget::Employee`] takes precedence
+/// over a [`BillingRateTarget::Title`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BillingRate
+{
+	/// The rate which is billed for each hour of work. It must be in the same currency as the
+	/// `invoice.hourly_rate` of the [`Job`].
+	pub hourly_rate: Money,
+
+	/// The reference number of this [`BillingRate`], which is unique among all [`BillingRate`]s.
+	pub id: Id,
+
+	/// The [`Id`] of the [`Job`] which this [`BillingRate`] is for.
+	pub job_id: Id,
+
+	/// Who this [`BillingRate`] applies to. Each [`Job`] may have one [`BillingRate`] per
+	/// [`BillingRateTarget`].
+	pub target: BillingRateTarget,
+}
+
+/// A [`BillingRate`] with [matchable](clinvoice_match) fields.
+///
+/// The `target` of a [`BillingRate`] can only be matched by its `employee_id`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchBillingRate
+{
+	#[allow(missing_docs)]
+	pub employee_id: MatchOption<Id>,
+
+	#[allow(missing_docs)]
+	pub hourly_rate: Match<Money>,
+
+	#[allow(missing_docs)]
+	pub id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub job_id: Match<Id>,
+}
+
+impl From<Id> for MatchBillingRate
+{
+	fn from(id: Id) -> Self
+	{
+		Self {
+			id: id.into(),
+			..Default::default()
+		}
+	}
+}
+
+/// Implementor of the [`Deletable`](clinvoice_adapter::Deletable),
+/// [`Retrievable`](clinvoice_adapter::Retrievable), and [`Updatable`](clinvoice_adapter::Updatable)
+/// traits for [`BillingRate`]s stored in the [`Postgres`] database.
+pub struct PgBillingRate;
+
+impl PgBillingRate
+{
+	/// Bill the `target` at the `hourly_rate` while they work on the `job` (via `connection`).
+	///
+	/// # Errors
+	///
+	/// * [`Error::UniqueViolation`] if the `target` already has a [`BillingRate`] for the `job`.
+	///
+	/// When the `hourly_rate` is in a different currency than the `job`, the `connection` will fail
+	/// to commit instead, so that a [`Job`] and its [`BillingRate`]s may change currency together.
+	pub async fn create<'c, TConn>(
+		connection: TConn,
+		job: &Job,
+		target: BillingRateTarget,
+		hourly_rate: Money,
+	) -> Result<BillingRate, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let (employee_id, employee_title) = Self::target_to_columns(&target);
+		let row = sqlx::query!(
+			"INSERT INTO billing_rates
+				(job_id, employee_id, employee_title, hourly_rate, hourly_rate_currency)
+			VALUES
+				($1,     $2,          $3,             $4,          $5)
+			RETURNING id;",
+			job.id,
+			employee_id,
+			employee_title,
+			hourly_rate.amount,
+			hourly_rate.currency.to_string() as _,
+		)
+		.fetch_one(connection)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"employee_id" => employee_id.map(|id| id.to_string()),
+				"employee_title" => employee_title.map(ToString::to_string),
+				"hourly_rate" => Some(hourly_rate.amount.to_string()),
+				"hourly_rate_currency" => Some(hourly_rate.currency.to_string()),
+				"job_id" => Some(job.id.to_string()),
+				_ => None,
+			})
+		})?;
+
+		Ok(BillingRate {
+			hourly_rate,
+			id: row.id,
+			job_id: job.id,
+			target,
+		})
+	}
+
+	/// Split the `target` into the `employee_id` and `employee_title` columns.
+	pub(super) fn target_to_columns(target: &BillingRateTarget) -> (Option<Id>, Option<&str>)
+	{
+		match target
+		{
+			BillingRateTarget::Employee(id) => (Some(*id), None),
+			BillingRateTarget::Title(title) => (None, Some(title)),
+		}
+	}
+
+	/// Create a [`BillingRate`] from some `row` which [`Retrievable::retrieve`] selected.
+	///
+	/// [`Retrievable::retrieve`]: clinvoice_adapter::Retrievable::retrieve
+	pub(super) fn row_to_view(row: &PgRow) -> Result<BillingRate>
+	{
+		let target = match (
+			row.try_get::<Option<Id>, _>("employee_id")?,
+			row.try_get::<Option<String>, _>("employee_title")?,
+		)
+		{
+			(Some(id), None) => BillingRateTarget::Employee(id),
+			(None, Some(title)) => BillingRateTarget::Title(title),
+			_ =>
+			{
+				return Err(sqlx::Error::Decode(
+					"a billing rate must be for either an employee or a title".into(),
+				))
+			},
+		};
+
+		Ok(BillingRate {
+			hourly_rate: util::money_from_row(row, "hourly_rate")?,
+			id: row.try_get("id")?,
+			job_id: row.try_get("job_id")?,
+			target,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::schema::EmployeeAdapter;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::{BillingRateTarget, PgBillingRate};
+	use crate::schema::{util, PgEmployee};
+
+	#[tokio::test]
+	async fn create()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let employee = PgEmployee::create(
+			&connection,
+			"My Name".into(),
+			"Employed".into(),
+			"Senior Engineer".into(),
+		)
+		.await
+		.unwrap();
+		let job = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		let rate = PgBillingRate::create(
+			&connection,
+			&job,
+			BillingRateTarget::Employee(employee.id),
+			Money::new(50_00, 2, Currency::Usd),
+		)
+		.await
+		.unwrap();
+
+		let row = sqlx::query!(
+			r#"SELECT
+					employee_id,
+					employee_title,
+					hourly_rate,
+					hourly_rate_currency AS "hourly_rate_currency!",
+					job_id
+				FROM billing_rates
+				WHERE id = $1;"#,
+			rate.id,
+		)
+		.fetch_one(&connection)
+		.await
+		.unwrap();
+
+		assert_eq!(row.employee_id, Some(employee.id));
+		assert_eq!(row.employee_title, None);
+		assert_eq!(rate.hourly_rate.amount, row.hourly_rate);
+		assert_eq!(
+			rate.hourly_rate.currency.to_string(),
+			row.hourly_rate_currency
+		);
+		assert_eq!(rate.job_id, row.job_id);
+
+		// An employee can only have one rate per job.
+		assert!(PgBillingRate::create(
+			&connection,
+			&job,
+			BillingRateTarget::Employee(employee.id),
+			Money::new(60_00, 2, Currency::Usd),
+		)
+		.await
+		.is_err());
+	}
+}
@@ -0,0 +1,43 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Retrievable, WriteWhereClause};
+use futures::{future, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result};
+
+use super::{BillingRate, MatchBillingRate, PgBillingRate};
+use crate::PgSchema;
+
+/// Implementors of this trait are capable of being retrieved from a [`Database`].
+#[async_trait::async_trait]
+impl Retrievable for PgBillingRate
+{
+	/// The [`Database`] where data of type [`Updatable::Entity`] is being stored.
+	type Db = Postgres;
+	/// The type of data that is to be [`update`](Deletable::update)d.
+	type Entity = BillingRate;
+	/// The type used for [match](clinvoice_match)ing.
+	type Match = MatchBillingRate;
+
+	/// Retrieve all [`BillingRate`]s (via `connection`) that match the `match_condition`.
+	///
+	/// The [`BillingRate`]s are sorted in the order they were created.
+	async fn retrieve(
+		connection: &Pool<Postgres>,
+		match_condition: &Self::Match,
+	) -> Result<Vec<Self::Entity>>
+	{
+		let mut query = QueryBuilder::new(
+			"SELECT B.id, B.job_id, B.employee_id, B.employee_title, B.hourly_rate,
+				B.hourly_rate_currency
+			FROM billing_rates B",
+		);
+
+		PgSchema::write_where_clause(Default::default(), 'B', match_condition, &mut query);
+
+		query
+			.push(" ORDER BY B.id;")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgBillingRate::row_to_view(&row)))
+			.try_collect()
+			.await
+	}
+}
@@ -0,0 +1,186 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Updatable};
+use sqlx::{Postgres, QueryBuilder, Result, Transaction};
+
+use super::{BillingRate, PgBillingRate};
+use crate::schema::util;
+
+#[async_trait::async_trait]
+impl Updatable for PgBillingRate
+{
+	type Db = Postgres;
+	type Entity = BillingRate;
+
+	async fn update<'e, 'i, TIter>(
+		connection: &mut Transaction<Self::Db>,
+		entities: TIter,
+	) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.clone().peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(
+			"UPDATE billing_rates AS B SET
+				employee_id = V.employee_id,
+				employee_title = V.employee_title,
+				hourly_rate = V.hourly_rate,
+				hourly_rate_currency = V.hourly_rate_currency,
+				job_id = V.job_id
+			FROM (",
+		);
+
+		query
+			.push_values(peekable_entities, |mut q, e| {
+				let (employee_id, employee_title) = PgBillingRate::target_to_columns(&e.target);
+				q.push_bind(e.id)
+					.push_bind(employee_id)
+					.push_bind(employee_title)
+					.push_bind(e.hourly_rate.amount)
+					.push_bind(e.hourly_rate.currency.to_string())
+					.push_bind(e.job_id);
+			})
+			.push(
+				") AS V (id, employee_id, employee_title, hourly_rate, hourly_rate_currency, job_id)
+				WHERE B.id = V.id;",
+			)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities, |r| {
+						let (employee_id, employee_title) = PgBillingRate::target_to_columns(&r.target);
+						match field
+						{
+							"employee_id" => employee_id.map(|id| id.to_string()),
+							"employee_title" => employee_title.map(ToString::to_string),
+							"hourly_rate" => Some(r.hourly_rate.amount.to_string()),
+							"hourly_rate_currency" => Some(r.hourly_rate.currency.to_string()),
+							"job_id" => Some(r.job_id.to_string()),
+							_ => None,
+						}
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::{schema::EmployeeAdapter, Deletable, Retrievable, Updatable};
+	use clinvoice_match::{Match, MatchOption};
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{
+		util,
+		BillingRate,
+		BillingRateTarget,
+		MatchBillingRate,
+		PgBillingRate,
+		PgEmployee,
+	};
+
+	#[tokio::test]
+	async fn update()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let employee = PgEmployee::create(
+			&connection,
+			"My Name".into(),
+			"Employed".into(),
+			"Junior Engineer".into(),
+		)
+		.await
+		.unwrap();
+		let job = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		let rate = PgBillingRate::create(
+			&connection,
+			&job,
+			BillingRateTarget::Title("Senior Engineer".into()),
+			Money::new(50_00, 2, Currency::Usd),
+		)
+		.await
+		.unwrap();
+
+		let rate2 = PgBillingRate::create(
+			&connection,
+			&job,
+			BillingRateTarget::Employee(employee.id),
+			Money::new(15_00, 2, Currency::Usd),
+		)
+		.await
+		.unwrap();
+
+		let match_rate = MatchBillingRate {
+			job_id: job.id.into(),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgBillingRate::retrieve(&connection, &match_rate)
+				.await
+				.unwrap(),
+			[rate.clone(), rate2.clone()],
+		);
+
+		let updated = BillingRate {
+			hourly_rate: Money::new(55_00, 2, Currency::Usd),
+			target: BillingRateTarget::Title("Principal Engineer".into()),
+			..rate
+		};
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgBillingRate::update(&mut transaction, [&updated].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		assert_eq!(
+			PgBillingRate::retrieve(&connection, &MatchBillingRate {
+				employee_id: MatchOption::None,
+				hourly_rate: Match::GreaterThan(Money::new(50_00, 2, Currency::Usd)),
+				..match_rate.clone()
+			})
+			.await
+			.unwrap(),
+			[updated],
+		);
+
+		PgBillingRate::delete(&connection, [&rate2].into_iter())
+			.await
+			.unwrap();
+
+		assert!(PgBillingRate::retrieve(&connection, &rate2.id.into())
+			.await
+			.unwrap()
+			.is_empty());
+	}
+}
@@ -0,0 +1,11 @@
+mod adjustment;
+mod billing_rate;
+mod issued_invoice;
+mod payment;
+mod tax_rate;
+
+pub use adjustment::AdjustmentColumns;
+pub use billing_rate::BillingRateColumns;
+pub use issued_invoice::IssuedInvoiceColumns;
+pub use payment::PaymentColumns;
+pub use tax_rate::TaxRateColumns;
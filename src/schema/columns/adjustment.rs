@@ -0,0 +1,66 @@
+use clinvoice_adapter::fmt::{TableToSql, WithIdentifier};
+
+/// The names of the columns of the `job_adjustments` table.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AdjustmentColumns<T>
+{
+	#[allow(missing_docs)]
+	pub amount: T,
+
+	#[allow(missing_docs)]
+	pub id: T,
+
+	#[allow(missing_docs)]
+	pub job_id: T,
+
+	#[allow(missing_docs)]
+	pub percentage: T,
+
+	#[allow(missing_docs)]
+	pub reason: T,
+}
+
+impl<T> AdjustmentColumns<T>
+{
+	/// Same as [`AdjustmentColumns::scope`], using the [`TableToSql::DEFAULT_ALIAS`].
+	pub fn default_scope(self) -> AdjustmentColumns<WithIdentifier<char, T>>
+	{
+		self.scope(Self::DEFAULT_ALIAS)
+	}
+
+	/// Wrap each of the columns so that they are [displayed](core::fmt::Display) as
+	/// `{alias}.{column}`.
+	pub fn scope<TAlias>(self, alias: TAlias) -> AdjustmentColumns<WithIdentifier<TAlias, T>>
+	where
+		TAlias: Copy,
+	{
+		AdjustmentColumns {
+			amount: WithIdentifier(alias, self.amount),
+			id: WithIdentifier(alias, self.id),
+			job_id: WithIdentifier(alias, self.job_id),
+			percentage: WithIdentifier(alias, self.percentage),
+			reason: WithIdentifier(alias, self.reason),
+		}
+	}
+}
+
+impl AdjustmentColumns<&'static str>
+{
+	/// The names of the columns in `job_adjustments` without any aliasing.
+	pub const fn default() -> Self
+	{
+		Self {
+			amount: "amount",
+			id: "id",
+			job_id: "job_id",
+			percentage: "percentage",
+			reason: "reason",
+		}
+	}
+}
+
+impl<T> TableToSql for AdjustmentColumns<T>
+{
+	const DEFAULT_ALIAS: char = 'A';
+	const TABLE_NAME: &'static str = "job_adjustments";
+}
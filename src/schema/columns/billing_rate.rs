@@ -0,0 +1,66 @@
+use clinvoice_adapter::fmt::{TableToSql, WithIdentifier};
+
+/// The names of the columns of the `billing_rates` table.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BillingRateColumns<T>
+{
+	#[allow(missing_docs)]
+	pub employee_id: T,
+
+	#[allow(missing_docs)]
+	pub employee_title: T,
+
+	#[allow(missing_docs)]
+	pub hourly_rate: T,
+
+	#[allow(missing_docs)]
+	pub id: T,
+
+	#[allow(missing_docs)]
+	pub job_id: T,
+}
+
+impl<T> BillingRateColumns<T>
+{
+	/// Same as [`BillingRateColumns::scope`], using the [`TableToSql::DEFAULT_ALIAS`].
+	pub fn default_scope(self) -> BillingRateColumns<WithIdentifier<char, T>>
+	{
+		self.scope(Self::DEFAULT_ALIAS)
+	}
+
+	/// Wrap each of the columns so that they are [displayed](core::fmt::Display) as
+	/// `{alias}.{column}`.
+	pub fn scope<TAlias>(self, alias: TAlias) -> BillingRateColumns<WithIdentifier<TAlias, T>>
+	where
+		TAlias: Copy,
+	{
+		BillingRateColumns {
+			employee_id: WithIdentifier(alias, self.employee_id),
+			employee_title: WithIdentifier(alias, self.employee_title),
+			hourly_rate: WithIdentifier(alias, self.hourly_rate),
+			id: WithIdentifier(alias, self.id),
+			job_id: WithIdentifier(alias, self.job_id),
+		}
+	}
+}
+
+impl BillingRateColumns<&'static str>
+{
+	/// The names of the columns in `billing_rates` without any aliasing.
+	pub const fn default() -> Self
+	{
+		Self {
+			employee_id: "employee_id",
+			employee_title: "employee_title",
+			hourly_rate: "hourly_rate",
+			id: "id",
+			job_id: "job_id",
+		}
+	}
+}
+
+impl<T> TableToSql for BillingRateColumns<T>
+{
+	const DEFAULT_ALIAS: char = 'B';
+	const TABLE_NAME: &'static str = "billing_rates";
+}
@@ -0,0 +1,71 @@
+use clinvoice_adapter::fmt::{TableToSql, WithIdentifier};
+
+/// The names of the columns of the `invoices` table.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IssuedInvoiceColumns<T>
+{
+	#[allow(missing_docs)]
+	pub date_issued: T,
+
+	#[allow(missing_docs)]
+	pub date_paid: T,
+
+	#[allow(missing_docs)]
+	pub id: T,
+
+	#[allow(missing_docs)]
+	pub job_id: T,
+
+	#[allow(missing_docs)]
+	pub number: T,
+
+	#[allow(missing_docs)]
+	pub year: T,
+}
+
+impl<T> IssuedInvoiceColumns<T>
+{
+	/// Same as [`IssuedInvoiceColumns::scope`], using the [`TableToSql::DEFAULT_ALIAS`].
+	pub fn default_scope(self) -> IssuedInvoiceColumns<WithIdentifier<char, T>>
+	{
+		self.scope(Self::DEFAULT_ALIAS)
+	}
+
+	/// Wrap each of the columns so that they are [displayed](core::fmt::Display) as
+	/// `{alias}.{column}`.
+	pub fn scope<TAlias>(self, alias: TAlias) -> IssuedInvoiceColumns<WithIdentifier<TAlias, T>>
+	where
+		TAlias: Copy,
+	{
+		IssuedInvoiceColumns {
+			date_issued: WithIdentifier(alias, self.date_issued),
+			date_paid: WithIdentifier(alias, self.date_paid),
+			id: WithIdentifier(alias, self.id),
+			job_id: WithIdentifier(alias, self.job_id),
+			number: WithIdentifier(alias, self.number),
+			year: WithIdentifier(alias, self.year),
+		}
+	}
+}
+
+impl IssuedInvoiceColumns<&'static str>
+{
+	/// The names of the columns in `invoices` without any aliasing.
+	pub const fn default() -> Self
+	{
+		Self {
+			date_issued: "date_issued",
+			date_paid: "date_paid",
+			id: "id",
+			job_id: "job_id",
+			number: "number",
+			year: "year",
+		}
+	}
+}
+
+impl<T> TableToSql for IssuedInvoiceColumns<T>
+{
+	const DEFAULT_ALIAS: char = 'I';
+	const TABLE_NAME: &'static str = "invoices";
+}
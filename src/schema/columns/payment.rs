@@ -0,0 +1,76 @@
+use clinvoice_adapter::fmt::{TableToSql, WithIdentifier};
+
+/// The names of the columns of the `payments` table.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PaymentColumns<T>
+{
+	#[allow(missing_docs)]
+	pub amount: T,
+
+	#[allow(missing_docs)]
+	pub date: T,
+
+	#[allow(missing_docs)]
+	pub id: T,
+
+	#[allow(missing_docs)]
+	pub invoice_id: T,
+
+	#[allow(missing_docs)]
+	pub job_id: T,
+
+	#[allow(missing_docs)]
+	pub method: T,
+
+	#[allow(missing_docs)]
+	pub reference: T,
+}
+
+impl<T> PaymentColumns<T>
+{
+	/// Same as [`PaymentColumns::scope`], using the [`TableToSql::DEFAULT_ALIAS`].
+	pub fn default_scope(self) -> PaymentColumns<WithIdentifier<char, T>>
+	{
+		self.scope(Self::DEFAULT_ALIAS)
+	}
+
+	/// Wrap each of the columns so that they are [displayed](core::fmt::Display) as
+	/// `{alias}.{column}`.
+	pub fn scope<TAlias>(self, alias: TAlias) -> PaymentColumns<WithIdentifier<TAlias, T>>
+	where
+		TAlias: Copy,
+	{
+		PaymentColumns {
+			amount: WithIdentifier(alias, self.amount),
+			date: WithIdentifier(alias, self.date),
+			id: WithIdentifier(alias, self.id),
+			invoice_id: WithIdentifier(alias, self.invoice_id),
+			job_id: WithIdentifier(alias, self.job_id),
+			method: WithIdentifier(alias, self.method),
+			reference: WithIdentifier(alias, self.reference),
+		}
+	}
+}
+
+impl PaymentColumns<&'static str>
+{
+	/// The names of the columns in `payments` without any aliasing.
+	pub const fn default() -> Self
+	{
+		Self {
+			amount: "amount",
+			date: "date",
+			id: "id",
+			invoice_id: "invoice_id",
+			job_id: "job_id",
+			method: "method",
+			reference: "reference",
+		}
+	}
+}
+
+impl<T> TableToSql for PaymentColumns<T>
+{
+	const DEFAULT_ALIAS: char = 'P';
+	const TABLE_NAME: &'static str = "payments";
+}
@@ -0,0 +1,61 @@
+use clinvoice_adapter::fmt::{TableToSql, WithIdentifier};
+
+/// The names of the columns of the `tax_rates` table.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TaxRateColumns<T>
+{
+	#[allow(missing_docs)]
+	pub id: T,
+
+	#[allow(missing_docs)]
+	pub location_id: T,
+
+	#[allow(missing_docs)]
+	pub name: T,
+
+	#[allow(missing_docs)]
+	pub percentage: T,
+}
+
+impl<T> TaxRateColumns<T>
+{
+	/// Same as [`TaxRateColumns::scope`], using the [`TableToSql::DEFAULT_ALIAS`].
+	pub fn default_scope(self) -> TaxRateColumns<WithIdentifier<char, T>>
+	{
+		self.scope(Self::DEFAULT_ALIAS)
+	}
+
+	/// Wrap each of the columns so that they are [displayed](core::fmt::Display) as
+	/// `{alias}.{column}`.
+	pub fn scope<TAlias>(self, alias: TAlias) -> TaxRateColumns<WithIdentifier<TAlias, T>>
+	where
+		TAlias: Copy,
+	{
+		TaxRateColumns {
+			id: WithIdentifier(alias, self.id),
+			location_id: WithIdentifier(alias, self.location_id),
+			name: WithIdentifier(alias, self.name),
+			percentage: WithIdentifier(alias, self.percentage),
+		}
+	}
+}
+
+impl TaxRateColumns<&'static str>
+{
+	/// The names of the columns in `tax_rates` without any aliasing.
+	pub const fn default() -> Self
+	{
+		Self {
+			id: "id",
+			location_id: "location_id",
+			name: "name",
+			percentage: "percentage",
+		}
+	}
+}
+
+impl<T> TableToSql for TaxRateColumns<T>
+{
+	const DEFAULT_ALIAS: char = 'R';
+	const TABLE_NAME: &'static str = "tax_rates";
+}
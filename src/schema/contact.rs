@@ -3,12 +3,11 @@ mod deletable;
 mod retrievable;
 mod updatable;
 
-use clinvoice_adapter::schema::columns::ContactColumns;
-use clinvoice_schema::{Contact, ContactKind};
-use futures::TryFutureExt;
-use sqlx::{postgres::PgRow, Error, Executor, Postgres, Result, Row};
+use std::collections::HashMap;
 
-use super::PgLocation;
+use clinvoice_adapter::schema::columns::ContactColumns;
+use clinvoice_schema::{Contact, ContactKind, Id, Location};
+use sqlx::{postgres::PgRow, Error, Result, Row};
 
 /// Implementor of the [`ContactAdapter`](clinvoice_adapter::schema::ContactAdapter) for the
 /// [`Postgres`](sqlx::Postgres) database.
@@ -16,24 +15,23 @@ pub struct PgContact;
 
 impl PgContact
 {
-	pub(super) async fn row_to_view<'c, TConn>(
-		connection: TConn,
+	/// Create a [`Contact`] from some `row`. If it is an address, its [`Location`] must be one of
+	/// the `locations`.
+	pub(super) fn row_to_view(
 		columns: ContactColumns<&str>,
+		locations: &HashMap<Id, Location>,
 		row: &PgRow,
 	) -> Result<Contact>
-	where
-		TConn: Executor<'c, Database = Postgres>,
 	{
 		Ok(Contact {
 			label: row.get(columns.label),
 			kind: match row.get::<Option<_>, _>(columns.address_id)
 			{
-				Some(id) =>
-				{
-					PgLocation::retrieve_by_id(connection, id)
-						.map_ok(ContactKind::Address)
-						.await?
-				},
+				Some(id) => locations
+					.get(&id)
+					.cloned()
+					.map(ContactKind::Address)
+					.ok_or(Error::RowNotFound)?,
 				_ => row
 					.get::<Option<_>, _>(columns.email)
 					.map(ContactKind::Email)
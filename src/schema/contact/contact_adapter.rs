@@ -3,6 +3,7 @@ use clinvoice_schema::{Contact, ContactKind};
 use sqlx::{Executor, Postgres, Result};
 
 use super::PgContact;
+use crate::schema::util;
 
 #[async_trait::async_trait]
 impl ContactAdapter for PgContact
@@ -25,7 +26,17 @@ impl ContactAdapter for PgContact
 			kind.phone(),
 		)
 		.execute(connection)
-		.await?;
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"address_id" => kind.address().map(|a| a.id.to_string()),
+				"email" => kind.email().map(ToString::to_string),
+				"label" => Some(label.clone()),
+				"phone" => kind.phone().map(ToString::to_string),
+				_ => None,
+			})
+		})?;
 
 		Ok(Contact { kind, label })
 	}
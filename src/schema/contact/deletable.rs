@@ -6,9 +6,40 @@ use clinvoice_adapter::{
 	Deletable,
 };
 use clinvoice_schema::Contact;
-use sqlx::{query_builder::Separated, Executor, Postgres, QueryBuilder, Result};
+use sqlx::{query_builder::Separated, Executor, Postgres, QueryBuilder, Result, Transaction};
 
 use super::PgContact;
+use crate::schema::{util, Error, SoftDeletable};
+
+/// Write `({label} = $1) OR … OR ({label} = $n)` for each of the `entities` to the `query`.
+fn write_labels<'args, 'i, TIter>(query: &mut QueryBuilder<'args, Postgres>, mut entities: TIter)
+where
+	'i: 'args,
+	TIter: Iterator<Item = &'i Contact>,
+{
+	fn write<'query, 'args, T>(s: &mut Separated<'query, 'args, Postgres, T>, c: &'args Contact)
+	where
+		T: Display,
+	{
+		s.push('(')
+			.push_unseparated(ContactColumns::default().label)
+			.push_unseparated('=')
+			.push_bind(&c.label)
+			.push_unseparated(')');
+	}
+
+	let mut separated = query.separated(' ');
+
+	if let Some(e) = entities.next()
+	{
+		write(&mut separated, e);
+	}
+
+	entities.for_each(|e| {
+		separated.push_unseparated(sql::OR);
+		write(&mut separated, e);
+	});
+}
 
 #[async_trait::async_trait]
 impl Deletable for PgContact
@@ -23,17 +54,6 @@ impl Deletable for PgContact
 		TConn: Executor<'c, Database = Self::Db>,
 		TIter: Iterator<Item = &'i Self::Entity> + Send,
 	{
-		fn write<'query, 'args, T>(s: &mut Separated<'query, 'args, Postgres, T>, c: &'args Contact)
-		where
-			T: Display,
-		{
-			s.push('(')
-				.push_unseparated(ContactColumns::default().label)
-				.push_unseparated('=')
-				.push_bind(&c.label)
-				.push_unseparated(')');
-		}
-
 		let mut peekable_entities = entities.peekable();
 
 		// There is nothing to do.
@@ -48,21 +68,96 @@ impl Deletable for PgContact
 			.push(ContactColumns::<&str>::TABLE_NAME)
 			.push(sql::WHERE);
 
+		write_labels(&mut query, peekable_entities);
+		query
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+}
+
+#[async_trait::async_trait]
+impl SoftDeletable for PgContact
+{
+	type Entity = Contact;
+
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
 		{
-			let mut separated = query.separated(' ');
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(sql::UPDATE);
+		query
+			.push(ContactColumns::<&str>::TABLE_NAME)
+			.push(sql::SET)
+			.push(util::DELETED_AT)
+			.push(" = now()")
+			.push(sql::WHERE)
+			.push('(');
+
+		write_labels(&mut query, peekable_entities);
+		query
+			.push(')')
+			.push(sql::AND)
+			.push(util::DELETED_AT)
+			.push(sql::IS)
+			.push(sql::NULL)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
 
-			if let Some(e) = peekable_entities.next()
-			{
-				write(&mut separated, e);
-			}
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.peekable();
 
-			peekable_entities.for_each(|e| {
-				separated.push_unseparated(sql::OR);
-				write(&mut separated, e);
-			});
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
 		}
 
-		query.prepare().execute(connection).await?;
+		let mut query = QueryBuilder::new(sql::UPDATE);
+		query
+			.push(ContactColumns::<&str>::TABLE_NAME)
+			.push(sql::SET)
+			.push(util::DELETED_AT)
+			.push(" = ")
+			.push(sql::NULL)
+			.push(sql::WHERE);
+
+		write_labels(&mut query, peekable_entities);
+		query
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| util::validation_or(e, |_| None))?;
 
 		Ok(())
 	}
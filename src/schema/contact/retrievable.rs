@@ -2,14 +2,17 @@ use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
 	schema::columns::ContactColumns,
 	Retrievable,
+	WriteContext,
 };
 use clinvoice_match::MatchContact;
 use clinvoice_schema::Contact;
-use futures::TryStreamExt;
-use sqlx::{Pool, Postgres, QueryBuilder, Result};
+use futures::stream::BoxStream;
+use sqlx::{postgres::PgRow, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgContact;
-use crate::schema::write_where_clause;
+use crate::schema::{util, write_where_clause, Error, PgLocation, RetrieveOptions};
+
+const COLUMNS: ContactColumns<&'static str> = ContactColumns::default();
 
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
@@ -28,15 +31,156 @@ impl Retrievable for PgContact
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: ContactColumns<&'static str> = ContactColumns::default();
+		Self::retrieve_with_options(connection, match_condition, &Default::default())
+			.await
+			.map_err(sqlx::Error::from)
+	}
+}
 
-		let mut query = QueryBuilder::new(sql::SELECT);
+impl PgContact
+{
+	/// Count the [`Contact`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchContact,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(connection, match_condition, false, |q| {
+			q.push(util::COUNT);
+		})
+		.await?;
 
 		query
-			.push_columns(&COLUMNS.default_scope())
-			.push_default_from::<ContactColumns<char>>();
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Check whether any [`Contact`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchContact,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(connection, match_condition, false, |q| {
+			q.push(util::EXISTS);
+		})
+		.await?;
+
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Generate the query which selects every [`Contact`] that matches the `match_condition`,
+	/// according to the `options`.
+	///
+	/// The `connection` is used to find the [`Location`](clinvoice_schema::Location)s which may be
+	/// the address of a [`Contact`].
+	async fn retrieve_query<'a>(
+		connection: &Pool<Postgres>,
+		match_condition: &'a MatchContact,
+		options: &RetrieveOptions<ContactColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) =
+			Self::select_matching(connection, match_condition, options.include_deleted, |q| {
+				q.push_columns(&COLUMNS.default_scope());
+			})
+			.await?;
+
+		options.write_where(
+			context,
+			COLUMNS,
+			ContactColumns::<char>::DEFAULT_ALIAS,
+			|c| c.label,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			ContactColumns::<char>::DEFAULT_ALIAS,
+			|c| c.label,
+			&mut query,
+		);
+
+		Ok(query)
+	}
 
-		write_where_clause::write_match_contact(
+	/// Same as [`PgContact::retrieve_with_options`], except that the [`Contact`]s are yielded as
+	/// they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchContact,
+		options: &'a RetrieveOptions<ContactColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Contact, Error>>
+	{
+		util::stream_views(
+			connection,
+			Self::retrieve_query(connection, match_condition, options),
+			move |rows| async move { Self::rows_to_views(connection, &rows).await },
+		)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Contact`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchContact,
+		options: &RetrieveOptions<ContactColumns<&'static str>>,
+	) -> Result<Vec<Contact>, Error>
+	{
+		let rows = Self::retrieve_query(connection, match_condition, options)
+			.await?
+			.prepare()
+			.fetch_all(connection)
+			.await?;
+
+		Self::rows_to_views(connection, &rows)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Convert the `rows` which the [`PgContact::retrieve_query`] selected into [`Contact`]s,
+	/// retrieving their addresses via `connection`.
+	async fn rows_to_views(connection: &Pool<Postgres>, rows: &[PgRow]) -> Result<Vec<Contact>>
+	{
+		PgLocation::rows_to_views(
+			connection,
+			rows,
+			|row| row.try_get(COLUMNS.address_id),
+			|locations, row| Self::row_to_view(COLUMNS, locations, row),
+		)
+		.await
+	}
+
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Contact`] that
+	/// matches the `match_condition`.
+	///
+	/// The `connection` is used to find the [`Location`](clinvoice_schema::Location)s which may be
+	/// the address of a [`Contact`]. [Soft deleted](crate::schema::SoftDeletable) [`Contact`]s are
+	/// excluded, unless `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is
+	/// returned so that more conditions can be added.
+	async fn select_matching<'a, TSelect>(
+		connection: &Pool<Postgres>,
+		match_condition: &'a MatchContact,
+		include_deleted: bool,
+		select: TSelect,
+	) -> Result<(QueryBuilder<'a, Postgres>, WriteContext)>
+	where
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
+		let mut query = QueryBuilder::new(sql::SELECT);
+
+		select(&mut query);
+		query.push_default_from::<ContactColumns<char>>();
+
+		let context = write_where_clause::write_match_contact(
 			connection,
 			Default::default(),
 			ContactColumns::<char>::DEFAULT_ALIAS,
@@ -45,11 +189,13 @@ impl Retrievable for PgContact
 		)
 		.await?;
 
-		query
-			.prepare()
-			.fetch(connection)
-			.and_then(|row| async move { PgContact::row_to_view(connection, COLUMNS, &row).await })
-			.try_collect()
-			.await
+		let context = util::write_not_deleted(
+			context,
+			ContactColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
+
+		Ok((query, context))
 	}
 }
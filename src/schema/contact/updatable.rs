@@ -3,7 +3,7 @@ use clinvoice_schema::Contact;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgContact;
-use crate::PgSchema;
+use crate::{schema::util, PgSchema};
 
 #[async_trait::async_trait]
 impl Updatable for PgContact
@@ -20,7 +20,7 @@ impl Updatable for PgContact
 		Self::Entity: 'e,
 		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
 	{
-		let mut peekable_entities = entities.peekable();
+		let mut peekable_entities = entities.clone().peekable();
 
 		// There is nothing to do.
 		if peekable_entities.peek().is_none()
@@ -38,6 +38,18 @@ impl Updatable for PgContact
 			});
 		})
 		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| {
+				util::sole_value_of(entities, |c| match field
+				{
+					"address_id" => c.kind.address().map(|a| a.id.to_string()),
+					"email" => c.kind.email().map(ToString::to_string),
+					"label" => Some(c.label.clone()),
+					"phone" => c.kind.phone().map(ToString::to_string),
+					_ => None,
+				})
+			})
+		})
 	}
 }
 
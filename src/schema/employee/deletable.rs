@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::EmployeeColumns, Deletable};
 use clinvoice_schema::{Employee, Id};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Executor, Postgres, Result, Transaction};
 
 use super::PgEmployee;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, PgTimesheet, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgEmployee
@@ -28,6 +31,73 @@ impl Deletable for PgEmployee
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgEmployee
+{
+	type Entity = Employee;
+
+	/// Soft delete the `entities` (via `connection`), along with their
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s (and their children) which were not already soft
+	/// deleted.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(e: &Employee) -> Id
+		{
+			e.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Employee| e.id`
+		PgSchema::set_deleted::<_, _, EmployeeColumns<char>>(connection, entities.map(mapper), true)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Restore the `entities` (via `connection`), along with the
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s (and their children) which were soft deleted at
+	/// the same time as them.
+	///
+	/// # Errors
+	///
+	/// * A [`TimesheetOverlap`](crate::schema::TimesheetOverlap) if any of the
+	///   [`Timesheet`](clinvoice_schema::Timesheet)s which are restored would overlap another
+	///   [`Timesheet`](clinvoice_schema::Timesheet) of the same
+	///   [`Employee`](clinvoice_schema::Employee).
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(e: &Employee) -> Id
+		{
+			e.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Employee| e.id`
+		let ids: Vec<_> = entities.map(mapper).collect();
+		PgTimesheet::restore_cascading::<EmployeeColumns<char>>(
+			connection,
+			&ids,
+			"SELECT T.id, T.employee_id, T.time_begin, T.time_end
+			FROM timesheets T JOIN employees E ON (E.id = T.employee_id)
+			WHERE E.id = ANY($1) AND T.deleted_at = E.deleted_at;",
+		)
+		.await
+		.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -75,7 +145,8 @@ mod tests
 					employee.id.into(),
 					employee2.id.into(),
 					employee3.id.into()
-				]).into()
+				])
+				.into()
 			)
 			.await
 			.unwrap()
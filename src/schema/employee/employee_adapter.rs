@@ -3,6 +3,7 @@ use clinvoice_schema::Employee;
 use sqlx::{Executor, Postgres, Result};
 
 use super::PgEmployee;
+use crate::schema::util;
 
 #[async_trait::async_trait]
 impl EmployeeAdapter for PgEmployee
@@ -23,7 +24,8 @@ impl EmployeeAdapter for PgEmployee
 			title,
 		)
 		.fetch_one(connection)
-		.await?;
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
 
 		Ok(Employee {
 			id: row.id,
@@ -2,15 +2,21 @@ use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
 	schema::columns::EmployeeColumns,
 	Retrievable,
+	WriteContext,
 	WriteWhereClause,
 };
 use clinvoice_match::MatchEmployee;
 use clinvoice_schema::Employee;
-use futures::TryStreamExt;
-use sqlx::{Pool, Postgres, QueryBuilder, Result};
+use futures::{future, stream::BoxStream, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgEmployee;
-use crate::PgSchema;
+use crate::{
+	schema::{util, Error, RetrieveOptions},
+	PgSchema,
+};
+
+const COLUMNS: EmployeeColumns<&'static str> = EmployeeColumns::default();
 
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
@@ -29,27 +35,153 @@ impl Retrievable for PgEmployee
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: EmployeeColumns<&'static str> = EmployeeColumns::default();
+		Self::retrieve_with_options(connection, match_condition, &Default::default())
+			.await
+			.map_err(sqlx::Error::from)
+	}
+}
+
+impl PgEmployee
+{
+	/// Count the [`Employee`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchEmployee,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, |q| {
+			q.push(util::COUNT);
+		});
 
-		let mut query = QueryBuilder::new(sql::SELECT);
+		query
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Check whether any [`Employee`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchEmployee,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, |q| {
+			q.push(util::EXISTS);
+		});
 
 		query
-			.push_columns(&COLUMNS.default_scope())
-			.push_default_from::<EmployeeColumns<char>>();
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
 
-		PgSchema::write_where_clause(
-			Default::default(),
+	/// Generate the query which selects every [`Employee`] that matches the `match_condition`,
+	/// according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchEmployee,
+		options: &RetrieveOptions<EmployeeColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) =
+			Self::select_matching(match_condition, options.include_deleted, |q| {
+				q.push_columns(&COLUMNS.default_scope());
+			});
+
+		options.write_where(
+			context,
+			COLUMNS,
 			EmployeeColumns::<char>::DEFAULT_ALIAS,
-			match_condition,
+			|c| c.id,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			EmployeeColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
 			&mut query,
 		);
 
-		query
+		Ok(query)
+	}
+
+	/// Same as [`PgEmployee::retrieve_with_options`], except that the [`Employee`]s are yielded as
+	/// they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchEmployee,
+		options: &'a RetrieveOptions<EmployeeColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Employee, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			|rows| {
+				future::ok(
+					rows
+						.iter()
+						.map(|row| PgEmployee::row_to_view(COLUMNS, row))
+						.collect(),
+				)
+			},
+		)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Employee`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchEmployee,
+		options: &RetrieveOptions<EmployeeColumns<&'static str>>,
+	) -> Result<Vec<Employee>, Error>
+	{
+		Self::retrieve_query(match_condition, options)?
 			.prepare()
 			.fetch(connection)
 			.map_ok(|row| PgEmployee::row_to_view(COLUMNS, &row))
 			.try_collect()
 			.await
+			.map_err(Error::from)
+	}
+
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Employee`] that
+	/// matches the `match_condition`.
+	///
+	/// [Soft deleted](crate::schema::SoftDeletable) [`Employee`]s are excluded, unless
+	/// `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is returned so that
+	/// more conditions can be added.
+	fn select_matching<'a, TSelect>(
+		match_condition: &'a MatchEmployee,
+		include_deleted: bool,
+		select: TSelect,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
+		let mut query = QueryBuilder::new(sql::SELECT);
+
+		select(&mut query);
+		query.push_default_from::<EmployeeColumns<char>>();
+
+		let context = PgSchema::write_where_clause(
+			Default::default(),
+			EmployeeColumns::<char>::DEFAULT_ALIAS,
+			match_condition,
+			&mut query,
+		);
+
+		let context = util::write_not_deleted(
+			context,
+			EmployeeColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
+
+		(query, context)
 	}
 }
 
@@ -60,8 +192,9 @@ mod tests
 
 	use clinvoice_adapter::{schema::EmployeeAdapter, Retrievable};
 	use clinvoice_match::{Match, MatchEmployee, MatchStr};
+	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgEmployee};
+	use crate::schema::{util, Error, OrderBy, Page, PgEmployee, RetrieveOptions, SortValue};
 
 	#[tokio::test]
 	async fn retrieve()
@@ -109,4 +242,95 @@ mod tests
 			[employee, employee2].into_iter().collect()
 		);
 	}
+
+	#[tokio::test]
+	async fn retrieve_with_options()
+	{
+		let connection = util::connect().await;
+
+		// NOTE: created one after another so that their `id`s are in order
+		let first = PgEmployee::create(
+			&connection,
+			"Aaron".into(),
+			"Employed".into(),
+			"Janitor".into(),
+		)
+		.await
+		.unwrap();
+
+		let second = PgEmployee::create(
+			&connection,
+			"Bella".into(),
+			"Employed".into(),
+			"Janitor".into(),
+		)
+		.await
+		.unwrap();
+
+		let third = PgEmployee::create(
+			&connection,
+			"Bella".into(),
+			"Employed".into(),
+			"Manager".into(),
+		)
+		.await
+		.unwrap();
+
+		let match_condition = MatchEmployee {
+			id: Match::Or(vec![first.id.into(), second.id.into(), third.id.into()]),
+			..Default::default()
+		};
+
+		// `second` and `third` have the same name, so they are sorted by `id`
+		let mut options = RetrieveOptions {
+			order_by: vec![OrderBy::descending(|c| c.name)],
+			page: None,
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgEmployee::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[second.clone(), third.clone(), first.clone()],
+		);
+
+		options.page = Some(Page::Offset {
+			limit: 2,
+			offset: 1,
+		});
+
+		assert_eq!(
+			PgEmployee::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[third.clone(), first.clone()],
+		);
+
+		options.page = Some(Page::Keyset {
+			after: vec![
+				SortValue::Text(second.name.clone()),
+				SortValue::Id(second.id),
+			],
+			limit: 1,
+		});
+
+		assert_eq!(
+			PgEmployee::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[third],
+		);
+
+		// There must be a value for each sort key, plus the `id`
+		options.page = Some(Page::Keyset {
+			after: vec![SortValue::Id(second.id)],
+			limit: 1,
+		});
+
+		assert!(matches!(
+			PgEmployee::retrieve_with_options(&connection, &match_condition, &options).await,
+			Err(Error::Other(sqlx::Error::Configuration(_)))
+		));
+	}
 }
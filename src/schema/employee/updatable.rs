@@ -3,7 +3,7 @@ use clinvoice_schema::Employee;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgEmployee;
-use crate::PgSchema;
+use crate::{schema::util, PgSchema};
 
 #[async_trait::async_trait]
 impl Updatable for PgEmployee
@@ -37,6 +37,7 @@ impl Updatable for PgEmployee
 			});
 		})
 		.await
+		.map_err(|e| util::validation_or(e, |_| None))
 	}
 }
 
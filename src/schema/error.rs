@@ -0,0 +1,374 @@
+use clinvoice_finance::Error as FinanceError;
+use sqlx::{error::DatabaseError, migrate::MigrateError};
+
+use super::{util, SchemaTooNew, ValidationError};
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of a
+/// `check_violation`.
+const CHECK_VIOLATION: &str = "23514";
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of an
+/// `exclusion_violation`.
+pub(super) const EXCLUSION_VIOLATION: &str = "23P01";
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of a
+/// `foreign_key_violation`.
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of a
+/// `unique_violation`.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// An [`sqlx::Error`] which has been sorted by what caused it.
+///
+/// Methods of this crate return this, except for the adapter traits (such as
+/// [`Deletable`](clinvoice_adapter::Deletable)) which must return an [`sqlx::Error`]. Either can be
+/// converted into the other using [`From`] without losing anything.
+#[derive(Debug, thiserror::Error)]
+pub enum Error
+{
+	/// A row did not satisfy the `CHECK` constraint with the given name (e.g.
+	/// `jobs__date_integrity`).
+	#[error("{source}")]
+	CheckViolation
+	{
+		/// The name of the constraint which was violated.
+		constraint: String,
+
+		/// The error which the database returned.
+		source: sqlx::Error,
+	},
+
+	/// The database could not be reached, or the connection to it failed.
+	#[error("could not communicate with the database: {0}")]
+	Connection(#[source] sqlx::Error),
+
+	/// A row conflicted with another row according to the `EXCLUDE` constraint with the given name
+	/// (e.g. a [`TimesheetOverlap`](super::TimesheetOverlap) of `timesheets__no_overlap`).
+	#[error("{source}")]
+	ExclusionViolation
+	{
+		/// The name of the constraint which was violated.
+		constraint: String,
+
+		/// The error which the database returned.
+		source: sqlx::Error,
+	},
+
+	/// [`Money`](clinvoice_finance::Money) could not be exchanged, or the
+	/// [`ExchangeRates`](clinvoice_finance::ExchangeRates) to do so could not be retrieved.
+	#[error(transparent)]
+	Finance(#[from] FinanceError),
+
+	/// A row referred to a row which does not exist, or a row which was still referred to (e.g. a
+	/// [`Location`](clinvoice_schema::Location) of an
+	/// [`Organization`](clinvoice_schema::Organization)) was deleted.
+	#[error("{source}")]
+	ForeignKeyViolation
+	{
+		/// The name of the constraint which was violated.
+		constraint: String,
+
+		/// The error which the database returned.
+		source: sqlx::Error,
+	},
+
+	/// A row which was expected to exist could not be found.
+	#[error("a row which was expected to exist was not found")]
+	NotFound,
+
+	/// Any other error.
+	#[error(transparent)]
+	Other(sqlx::Error),
+
+	/// The database was migrated by a newer version of this crate, so it cannot be used.
+	#[error(transparent)]
+	SchemaTooNew(#[from] SchemaTooNew),
+
+	/// A row had the same value as another row where only one is allowed (e.g. the `label` of a
+	/// [`Contact`](clinvoice_schema::Contact)).
+	#[error("{source}")]
+	UniqueViolation
+	{
+		/// The name of the constraint which was violated.
+		constraint: String,
+
+		/// The error which the database returned.
+		source: sqlx::Error,
+	},
+}
+
+impl Error
+{
+	/// The error which the database returned, if any.
+	///
+	/// Errors which this crate describes further (e.g. a
+	/// [`TimesheetOverlap`](super::TimesheetOverlap)) may be retrieved from it using
+	/// [`DatabaseError::try_downcast_ref`].
+	pub fn as_database_error(&self) -> Option<&(dyn DatabaseError + 'static)>
+	{
+		match self
+		{
+			Self::CheckViolation { source, .. } |
+			Self::ExclusionViolation { source, .. } |
+			Self::ForeignKeyViolation { source, .. } |
+			Self::Other(source) |
+			Self::UniqueViolation { source, .. } => source.as_database_error(),
+			_ => None,
+		}
+	}
+
+	/// The name of the constraint which was violated, if any.
+	pub fn constraint(&self) -> Option<&str>
+	{
+		match self
+		{
+			Self::CheckViolation { constraint, .. } |
+			Self::ExclusionViolation { constraint, .. } |
+			Self::ForeignKeyViolation { constraint, .. } |
+			Self::UniqueViolation { constraint, .. } => Some(constraint),
+			_ => self.as_database_error().and_then(|e| e.constraint()),
+		}
+	}
+
+	/// The [`ValidationError`] which describes this error, if it was caused by violating one of the
+	/// constraints of the schema.
+	pub fn validation(&self) -> Option<ValidationError>
+	{
+		let error = self.as_database_error()?;
+
+		error
+			.try_downcast_ref::<ValidationError>()
+			.cloned()
+			.or_else(|| ValidationError::from_database_error(error))
+	}
+}
+
+impl From<sqlx::Error> for Error
+{
+	fn from(error: sqlx::Error) -> Self
+	{
+		match error
+		{
+			// NOTE: see `util::finance_err_to_sqlx`
+			sqlx::Error::Decode(e) => match e.downcast::<FinanceError>()
+			{
+				Ok(e) => Self::Finance(*e),
+				Err(e) => Self::Other(sqlx::Error::Decode(e)),
+			},
+			sqlx::Error::Io(e) if e.get_ref().map_or(false, |e| e.is::<FinanceError>()) =>
+			{
+				let e = e.into_inner().expect("`get_ref` returned `Some`");
+				Self::Finance(*e.downcast::<FinanceError>().expect("`is` returned `true`"))
+			},
+
+			sqlx::Error::Migrate(e) => match *e
+			{
+				MigrateError::Source(e) => match e.downcast::<SchemaTooNew>()
+				{
+					Ok(e) => Self::SchemaTooNew(*e),
+					Err(e) => Self::Other(MigrateError::Source(e).into()),
+				},
+				e => Self::Other(e.into()),
+			},
+
+			sqlx::Error::Database(ref e) =>
+			{
+				let code = e.code().map(|c| c.into_owned());
+				let constraint = e.constraint().unwrap_or_default().to_owned();
+				match code.as_deref()
+				{
+					Some(CHECK_VIOLATION) => Self::CheckViolation {
+						constraint,
+						source: error,
+					},
+					Some(EXCLUSION_VIOLATION) => Self::ExclusionViolation {
+						constraint,
+						source: error,
+					},
+					Some(FOREIGN_KEY_VIOLATION) => Self::ForeignKeyViolation {
+						constraint,
+						source: error,
+					},
+					Some(UNIQUE_VIOLATION) => Self::UniqueViolation {
+						constraint,
+						source: error,
+					},
+					_ => Self::Other(error),
+				}
+			},
+
+			sqlx::Error::Io(_) |
+			sqlx::Error::PoolClosed |
+			sqlx::Error::PoolTimedOut |
+			sqlx::Error::Tls(_) |
+			sqlx::Error::WorkerCrashed => Self::Connection(error),
+
+			sqlx::Error::RowNotFound => Self::NotFound,
+
+			_ => Self::Other(error),
+		}
+	}
+}
+
+impl From<Error> for sqlx::Error
+{
+	fn from(error: Error) -> Self
+	{
+		match error
+		{
+			Error::CheckViolation { source, .. } |
+			Error::Connection(source) |
+			Error::ExclusionViolation { source, .. } |
+			Error::ForeignKeyViolation { source, .. } |
+			Error::Other(source) |
+			Error::UniqueViolation { source, .. } => source,
+			Error::Finance(e) => util::finance_err_to_sqlx(e),
+			Error::NotFound => Self::RowNotFound,
+			Error::SchemaTooNew(e) => MigrateError::Source(e.into()).into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::io;
+
+	use clinvoice_adapter::{
+		schema::{ContactAdapter, TimesheetAdapter},
+		Deletable,
+	};
+	use clinvoice_finance::{Currency, Error as FinanceError, Money};
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		ContactKind,
+	};
+	use sqlx::Connection;
+
+	use super::Error;
+	use crate::schema::{
+		util,
+		PgContact,
+		PgLocation,
+		PgTimesheet,
+		SchemaTooNew,
+		TimesheetOverlap,
+		ValidationRule,
+	};
+
+	#[tokio::test]
+	async fn from()
+	{
+		let connection = util::connect().await;
+		let organization = util::create_organization(&connection).await;
+
+		match PgLocation::delete(&connection, [&organization.location].into_iter())
+			.await
+			.map_err(Error::from)
+		{
+			Err(e @ Error::ForeignKeyViolation { .. }) =>
+			{
+				assert_eq!(e.constraint(), Some("organizations__location_fk"));
+				assert_eq!(
+					e.validation().map(|v| v.rule),
+					Some(ValidationRule::NotReferenced)
+				);
+			},
+			r => panic!("expected a foreign key violation, got {r:?}"),
+		}
+
+		match PgContact::create(
+			&connection,
+			ContactKind::Phone("not a phone number".into()),
+			"Phone".into(),
+		)
+		.await
+		.map_err(Error::from)
+		{
+			Err(Error::CheckViolation { constraint, .. }) =>
+			{
+				assert_eq!(constraint, "contact_information__phone_is_valid")
+			},
+			r => panic!("expected a check violation, got {r:?}"),
+		}
+
+		let organization = util::create_organization(&connection).await;
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		let mut transaction = connection.begin().await.unwrap();
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		match PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			Utc.ymd(1990, 07, 12).and_hms(16, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.map_err(Error::from)
+		{
+			Err(e @ Error::ExclusionViolation { .. }) =>
+			{
+				assert_eq!(e.constraint(), Some(TimesheetOverlap::CONSTRAINT));
+				assert_eq!(
+					e.as_database_error()
+						.unwrap()
+						.downcast_ref::<TimesheetOverlap>(),
+					&TimesheetOverlap {
+						conflicting_id: timesheet.id,
+						employee_id: employee.id,
+						timesheet_id: None,
+					},
+				);
+			},
+			r => panic!("expected an exclusion violation, got {r:?}"),
+		}
+
+		assert!(matches!(
+			Error::from(util::finance_err_to_sqlx(FinanceError::Io(io::Error::new(
+				io::ErrorKind::ConnectionRefused,
+				"could not download the exchange rates",
+			)))),
+			Error::Finance(FinanceError::Io(_)),
+		));
+		assert!(matches!(
+			Error::from(sqlx::Error::RowNotFound),
+			Error::NotFound
+		));
+		assert!(matches!(
+			Error::from(sqlx::Error::PoolClosed),
+			Error::Connection(_)
+		));
+
+		let too_new = SchemaTooNew {
+			found: 2,
+			latest: 1,
+		};
+		assert!(matches!(
+			Error::from(sqlx::Error::from(Error::SchemaTooNew(too_new))),
+			Error::SchemaTooNew(e) if e == too_new,
+		));
+	}
+}
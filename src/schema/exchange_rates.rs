@@ -0,0 +1,234 @@
+mod source;
+
+use std::collections::{HashMap, HashSet};
+
+use clinvoice_adapter::fmt::QueryBuilderExt;
+use clinvoice_finance::{Currency, Decimal, ExchangeRates, Exchangeable, Money};
+use clinvoice_schema::chrono::NaiveDate;
+use sqlx::{Executor, Pool, Postgres, QueryBuilder, Result};
+
+use super::{util, Error, ExchangeRatesSource};
+
+/// An [`ExchangeRatesSource`] which reads the rates stored in the `exchange_rates` table of the
+/// [`Postgres`] database.
+///
+/// The table is also where the adapters record the rates that they used to exchange an entity, so
+/// that the entity is valued the same way every time it is exchanged.
+#[derive(Clone, Debug)]
+pub struct PgExchangeRates(pub Pool<Postgres>);
+
+impl PgExchangeRates
+{
+	/// Get the [`ExchangeRates`] which were effective on the `date`, making sure that they include
+	/// every one of the `currencies`.
+	///
+	/// If the `exchange_rates` table (via `connection`) does not have a rate for each of the
+	/// `currencies` on the `date`, the missing rates are taken from the `source` and stored.
+	async fn effective_on<TSource>(
+		connection: &Pool<Postgres>,
+		date: NaiveDate,
+		currencies: &HashSet<Currency>,
+		source: &TSource,
+	) -> Result<ExchangeRates, Error>
+	where
+		TSource: ExchangeRatesSource + ?Sized,
+	{
+		/// Select every rate stored for some `date`.
+		macro_rules! select {
+			() => {
+				sqlx::query!(
+					r#"SELECT currency AS "currency!", rate FROM exchange_rates WHERE date = $1;"#,
+					date,
+				)
+				.fetch_all(connection)
+				.await?
+				.into_iter()
+				.map(|row| (row.currency, row.rate))
+				.collect::<Vec<_>>()
+			};
+		}
+
+		let stored = select!();
+		if currencies.iter().all(|c| {
+			stored
+				.iter()
+				.any(|(currency, _)| *currency == c.to_string())
+		})
+		{
+			return Self::parse(date, stored);
+		}
+
+		let rates = source.exchange_rates(date).await?;
+
+		// NOTE: rates which were already stored for this date are kept, so that anything exchanged
+		//       before is still valued the same way.
+		Self::insert(
+			connection,
+			date,
+			currencies.iter().map(|c| (*c, Self::rate_of(*c, &rates))),
+			" ON CONFLICT DO NOTHING",
+		)
+		.await?;
+
+		Self::parse(date, select!())
+	}
+
+	/// Exchange each of the `entities` into the `currency`, using the [`ExchangeRates`] which were
+	/// effective on the date that `date_of` the entity returns.
+	///
+	/// `currencies_of` must return every [`Currency`] which an entity has [`Money`] in.
+	///
+	/// # See also
+	///
+	/// * [`PgExchangeRates::effective_on`], for how the [`ExchangeRates`] on each date are found.
+	pub(super) async fn exchange<T, TSource, TDate, TCurrencies>(
+		connection: &Pool<Postgres>,
+		entities: Vec<T>,
+		currency: Currency,
+		source: &TSource,
+		date_of: TDate,
+		currencies_of: TCurrencies,
+	) -> Result<Vec<T>, Error>
+	where
+		T: Exchangeable,
+		TCurrencies: Fn(&T) -> Vec<Currency>,
+		TDate: Fn(&T) -> NaiveDate,
+		TSource: ExchangeRatesSource + ?Sized,
+	{
+		let len = entities.len();
+		let mut entities_on = HashMap::<_, (HashSet<_>, Vec<_>)>::new();
+		entities.into_iter().enumerate().for_each(|(index, e)| {
+			let (currencies, group) = entities_on.entry(date_of(&e)).or_default();
+			currencies.insert(currency);
+			currencies.extend(currencies_of(&e));
+			group.push((index, e));
+		});
+
+		let mut exchanged = Vec::with_capacity(len);
+		for (date, (currencies, group)) in entities_on
+		{
+			let rates = Self::effective_on(connection, date, &currencies, source).await?;
+			exchanged.extend(
+				group
+					.into_iter()
+					.map(|(index, e)| (index, e.exchange(currency, &rates))),
+			);
+		}
+
+		// NOTE: the entities were grouped by date, so they are put back in the order they were given.
+		exchanged.sort_unstable_by_key(|(index, _)| *index);
+		Ok(exchanged.into_iter().map(|(_, e)| e).collect())
+	}
+
+	/// Insert each `(currency, rate)` in `rates` (via `connection`) for the `date`, followed by
+	/// the `on_conflict` clause.
+	async fn insert<'c, TConn, TIter>(
+		connection: TConn,
+		date: NaiveDate,
+		rates: TIter,
+		on_conflict: &str,
+	) -> Result<()>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = (Currency, Decimal)>,
+	{
+		let rates: Vec<_> = rates.collect();
+
+		// There is nothing to do
+		if rates.is_empty()
+		{
+			return Ok(());
+		}
+
+		QueryBuilder::new("INSERT INTO exchange_rates (date, currency, rate) ")
+			.push_values(rates.iter(), |mut q, (currency, rate)| {
+				q.push_bind(date)
+					.push_bind(currency.to_string())
+					.push_bind(*rate);
+			})
+			.push(on_conflict)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(rates.iter(), |(currency, rate)| match field
+					{
+						"currency" => Some(currency.to_string()),
+						"rate" => Some(rate.to_string()),
+						_ => None,
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+
+	/// Create [`ExchangeRates`] from `(currency, rate)` pairs which were stored for the `date`.
+	fn parse<TIter>(date: NaiveDate, rates: TIter) -> Result<ExchangeRates, Error>
+	where
+		TIter: IntoIterator<Item = (String, Decimal)>,
+	{
+		// NOTE: `ExchangeRates` can only be created by parsing the format published by the European
+		//       Central Bank, so the rates are written in that format first.
+		let (currencies, values) = rates.into_iter().fold(
+			(
+				String::from("Date, "),
+				format!("{}, ", date.format("%d %B %Y")),
+			),
+			|(mut currencies, mut values), (currency, rate)| {
+				currencies.push_str(&currency);
+				currencies.push_str(", ");
+				values.push_str(&rate.to_string());
+				values.push_str(", ");
+				(currencies, values)
+			},
+		);
+
+		format!("{currencies}\n{values}\n")
+			.parse()
+			.map_err(Error::from)
+	}
+
+	/// Get the amount of the `currency` which is worth 1 [`Currency::Eur`] according to the
+	/// `rates`.
+	fn rate_of(currency: Currency, rates: &ExchangeRates) -> Decimal
+	{
+		// The rates are published relative to the base currency, so its own rate is always 1.
+		if currency == Currency::Eur
+		{
+			return Decimal::ONE;
+		}
+
+		// NOTE: `ExchangeRates` does not expose its rates directly, and exchanging `Money` may round
+		//       the result. Exchanging a large amount and scaling it back down keeps the precision.
+		const SCALE: i64 = 1_000_000_000;
+
+		let exchanged = Money::new(SCALE, 0, Currency::Eur).exchange(currency, rates);
+		(exchanged.amount / Decimal::from(SCALE)).normalize()
+	}
+
+	/// Store each `(currency, rate)` in `rates` (via `connection`) as being effective on the
+	/// `date`, replacing any rate which was already stored for that currency on that date.
+	///
+	/// Each rate is the amount of its [`Currency`] which is worth 1 [`Currency::Eur`], which is how
+	/// the European Central Bank publishes them.
+	pub async fn store<'c, TConn, TIter>(
+		connection: TConn,
+		date: NaiveDate,
+		rates: TIter,
+	) -> Result<(), Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = (Currency, Decimal)>,
+	{
+		Self::insert(
+			connection,
+			date,
+			rates,
+			" ON CONFLICT (date, currency) DO UPDATE SET rate = EXCLUDED.rate",
+		)
+		.await
+		.map_err(Error::from)
+	}
+}
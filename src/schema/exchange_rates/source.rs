@@ -0,0 +1,94 @@
+use clinvoice_finance::ExchangeRates;
+use clinvoice_schema::chrono::NaiveDate;
+
+use super::PgExchangeRates;
+use crate::schema::{Error, ExchangeRatesSource};
+
+#[async_trait::async_trait]
+impl ExchangeRatesSource for PgExchangeRates
+{
+	/// Read the most recent rate of each currency which was [stored](PgExchangeRates::store) on or
+	/// before the `date`.
+	async fn exchange_rates(&self, date: NaiveDate) -> Result<ExchangeRates, Error>
+	{
+		let rates = sqlx::query!(
+			r#"SELECT DISTINCT ON (currency) currency AS "currency!", rate
+				FROM exchange_rates
+				WHERE date <= $1
+				ORDER BY currency, date DESC;"#,
+			date,
+		)
+		.fetch_all(&self.0)
+		.await?;
+
+		Self::parse(date, rates.into_iter().map(|row| (row.currency, row.rate)))
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_finance::{Currency, Decimal, ExchangeRates, Exchangeable, Money};
+	use clinvoice_schema::chrono::NaiveDate;
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, ExchangeRatesSource, PgExchangeRates};
+
+	#[tokio::test]
+	async fn exchange_rates()
+	{
+		let connection = util::connect().await;
+
+		let (earlier, later) = (
+			NaiveDate::from_ymd(1880, 03, 01),
+			NaiveDate::from_ymd(1880, 03, 05),
+		);
+
+		PgExchangeRates::store(
+			&connection,
+			earlier,
+			[
+				(Currency::Eur, Decimal::ONE),
+				(Currency::Jpy, Decimal::new(145_65, 2)),
+				(Currency::Usd, Decimal::new(0_9745, 4)),
+			]
+			.into_iter(),
+		)
+		.await
+		.unwrap();
+
+		PgExchangeRates::store(
+			&connection,
+			later,
+			[(Currency::Jpy, Decimal::new(150_00, 2))].into_iter(),
+		)
+		.await
+		.unwrap();
+
+		let source = PgExchangeRates(connection);
+		let money = Money::new(20_00, 2, Currency::Usd);
+
+		let expected = "Date, EUR, JPY, USD, \n01 March 1880, 1, 145.65, 0.9745, \n"
+			.parse::<ExchangeRates>()
+			.unwrap();
+		let actual = source
+			.exchange_rates(NaiveDate::from_ymd(1880, 03, 03))
+			.await
+			.unwrap();
+
+		assert_eq!(
+			money.exchange(Currency::Jpy, &actual),
+			money.exchange(Currency::Jpy, &expected),
+		);
+
+		let expected = "Date, EUR, JPY, USD, \n05 March 1880, 1, 150.00, 0.9745, \n"
+			.parse::<ExchangeRates>()
+			.unwrap();
+		let actual = source.exchange_rates(later).await.unwrap();
+
+		assert_eq!(
+			money.exchange(Currency::Jpy, &actual),
+			money.exchange(Currency::Jpy, &expected),
+		);
+	}
+}
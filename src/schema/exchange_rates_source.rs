@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use clinvoice_finance::{Error as FinanceError, ExchangeRates};
+use clinvoice_schema::chrono::NaiveDate;
+use tokio::fs;
+
+use super::Error;
+
+/// Implementors of this trait are able to provide the [`ExchangeRates`] which are used to convert
+/// [`Money`](clinvoice_finance::Money) from one [`Currency`](clinvoice_finance::Currency) to
+/// another.
+///
+/// No adapter converts [`Money`](clinvoice_finance::Money) on its own; conversion only happens
+/// when one of these is given to a function such as
+/// [`PgJob::retrieve_exchanged`](super::PgJob::retrieve_exchanged).
+#[async_trait::async_trait]
+pub trait ExchangeRatesSource: Send + Sync
+{
+	/// Get the [`ExchangeRates`] which were effective on the given `date`.
+	///
+	/// Sources which do not keep a history may return the same [`ExchangeRates`] for every `date`.
+	async fn exchange_rates(&self, date: NaiveDate) -> Result<ExchangeRates, Error>;
+}
+
+/// An [`ExchangeRatesSource`] which downloads the latest [`ExchangeRates`] from the European
+/// Central Bank every time they are requested, regardless of the date they are requested for.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EcbExchangeRates;
+
+#[async_trait::async_trait]
+impl ExchangeRatesSource for EcbExchangeRates
+{
+	async fn exchange_rates(&self, _: NaiveDate) -> Result<ExchangeRates, Error>
+	{
+		ExchangeRates::new().await.map_err(Error::from)
+	}
+}
+
+/// A fixed, in-memory table of [`ExchangeRates`], which is used for every date.
+#[async_trait::async_trait]
+impl ExchangeRatesSource for ExchangeRates
+{
+	async fn exchange_rates(&self, _: NaiveDate) -> Result<ExchangeRates, Error>
+	{
+		Ok(self.clone())
+	}
+}
+
+/// An [`ExchangeRatesSource`] which reads a file in the format published by the European Central
+/// Bank (e.g. one which was downloaded earlier, for use on a machine which is offline). The file
+/// is used for every date.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ExchangeRatesFile(pub PathBuf);
+
+#[async_trait::async_trait]
+impl ExchangeRatesSource for ExchangeRatesFile
+{
+	async fn exchange_rates(&self, _: NaiveDate) -> Result<ExchangeRates, Error>
+	{
+		fs::read_to_string(&self.0)
+			.await
+			.map_err(FinanceError::Io)?
+			.parse()
+			.map_err(Error::from)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::{env, fs};
+
+	use clinvoice_finance::{Currency, ExchangeRates, Exchangeable, Money};
+	use clinvoice_schema::chrono::Utc;
+	use pretty_assertions::assert_eq;
+
+	use super::{ExchangeRatesFile, ExchangeRatesSource};
+
+	#[tokio::test]
+	async fn exchange_rates_file()
+	{
+		const RATES: &str = "Date, USD, JPY, \n17 October 2022, 0.9745, 145.65, \n";
+
+		let path = env::temp_dir().join("clinvoice_adapter_postgres--exchange_rates_file.csv");
+		fs::write(&path, RATES).unwrap();
+
+		let expected = RATES.parse::<ExchangeRates>().unwrap();
+		let actual = ExchangeRatesFile(path)
+			.exchange_rates(Utc::today().naive_utc())
+			.await
+			.unwrap();
+
+		let money = Money::new(20_00, 2, Currency::Usd);
+		assert_eq!(
+			money.exchange(Currency::Jpy, &actual),
+			money.exchange(Currency::Jpy, &expected),
+		);
+
+		assert!(
+			ExchangeRatesFile(env::temp_dir().join("does-not-exist.csv"))
+				.exchange_rates(Utc::today().naive_utc())
+				.await
+				.is_err()
+		);
+	}
+}
@@ -0,0 +1,61 @@
+use clinvoice_finance::Money;
+use clinvoice_schema::{chrono::NaiveDate, Id};
+
+/// Which properties of an [`Expense`](clinvoice_schema::Expense) should be used to group them
+/// into [`ExpenseTotal`]s.
+///
+/// [`Expense`]s are always grouped by the currency of their `cost` as well, since amounts of
+/// different currencies cannot be added without being exchanged first.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ExpenseGroupBy
+{
+	/// Group by the `category` of the [`Expense`](clinvoice_schema::Expense).
+	pub category: bool,
+
+	/// Group by the `client` of the [`Job`](clinvoice_schema::Job) which the
+	/// [`Expense`](clinvoice_schema::Expense) was incurred for.
+	pub client: bool,
+
+	/// Group by the [`Job`](clinvoice_schema::Job) which the
+	/// [`Expense`](clinvoice_schema::Expense) was incurred for.
+	pub job: bool,
+
+	/// Group by the month (in UTC) of the `time_begin` of the
+	/// [`Timesheet`](clinvoice_schema::Timesheet) which the
+	/// [`Expense`](clinvoice_schema::Expense) belongs to.
+	pub month: bool,
+}
+
+/// The sum of the `cost` of some group of [`Expense`](clinvoice_schema::Expense)s.
+///
+/// Each property is [`Some`] only if the [`Expense`](clinvoice_schema::Expense)s were grouped by
+/// it.
+///
+/// # See also
+///
+/// * [`PgExpenses::retrieve_totals`](crate::schema::PgExpenses::retrieve_totals), which computes
+///   these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExpenseTotal
+{
+	/// The `category` shared by the [`Expense`](clinvoice_schema::Expense)s.
+	pub category: Option<String>,
+
+	/// The [`Id`] of the [`Organization`](clinvoice_schema::Organization) which the
+	/// [`Expense`](clinvoice_schema::Expense)s were incurred for.
+	pub client_id: Option<Id>,
+
+	/// The number of [`Expense`](clinvoice_schema::Expense)s in the group.
+	pub count: i64,
+
+	/// The [`Id`] of the [`Job`](clinvoice_schema::Job) which the
+	/// [`Expense`](clinvoice_schema::Expense)s were incurred for.
+	pub job_id: Option<Id>,
+
+	/// The first day of the month which the [`Expense`](clinvoice_schema::Expense)s were incurred
+	/// in.
+	pub month: Option<NaiveDate>,
+
+	/// The sum of the `cost` of the [`Expense`](clinvoice_schema::Expense)s.
+	pub total: Money,
+}
@@ -4,7 +4,6 @@ mod retrievable;
 mod updatable;
 
 use clinvoice_adapter::schema::columns::ExpenseColumns;
-use clinvoice_finance::{Decimal, Money};
 use clinvoice_schema::Expense;
 use sqlx::{postgres::PgRow, Result, Row};
 
@@ -22,14 +21,7 @@ impl PgExpenses
 			id: row.try_get(columns.id)?,
 			timesheet_id: row.try_get(columns.timesheet_id)?,
 			category: row.try_get(columns.category)?,
-			cost: Money {
-				amount: row.try_get::<String, _>(columns.cost).and_then(|cost| {
-					cost
-						.parse::<Decimal>()
-						.map_err(|e| util::finance_err_to_sqlx(e.into()))
-				})?,
-				..Default::default()
-			},
+			cost: util::money_from_row(row, columns.cost)?,
 			description: row.try_get(columns.description)?,
 		})
 	}
@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::ExpenseColumns, Deletable};
 use clinvoice_schema::{Expense, Id};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Executor, Postgres, Result, Transaction};
 
 use super::PgExpenses;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgExpenses
@@ -28,6 +31,53 @@ impl Deletable for PgExpenses
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgExpenses
+{
+	type Entity = Expense;
+
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(x: &Expense) -> Id
+		{
+			x.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Expense| e.id`
+		PgSchema::set_deleted::<_, _, ExpenseColumns<char>>(connection, entities.map(mapper), true)
+			.await
+			.map_err(Error::from)
+	}
+
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(x: &Expense) -> Id
+		{
+			x.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Expense| e.id`
+		PgSchema::set_deleted::<_, _, ExpenseColumns<char>>(connection, entities.map(mapper), false)
+			.await
+			.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -44,7 +94,7 @@ mod tests
 		Deletable,
 		Retrievable,
 	};
-	use clinvoice_finance::{Currency, ExchangeRates, Exchangeable, Money};
+	use clinvoice_finance::{Currency, Money};
 	use clinvoice_match::MatchExpense;
 	use clinvoice_schema::{
 		chrono::{TimeZone, Utc},
@@ -141,8 +191,6 @@ mod tests
 		.await
 		.unwrap();
 
-		let exchange_rates = ExchangeRates::new().await.unwrap();
-
 		assert_eq!(
 			PgExpenses::retrieve(&connection, &MatchExpense {
 				timesheet_id: timesheet.id.into(),
@@ -154,7 +202,7 @@ mod tests
 			.filter(|x| x.timesheet_id == timesheet.id)
 			.collect::<Vec<_>>()
 			.as_slice(),
-			&[timesheet.expenses[2].exchange_ref(Default::default(), &exchange_rates)],
+			&[timesheet.expenses[2].clone()],
 		);
 	}
 }
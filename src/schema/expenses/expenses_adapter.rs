@@ -2,9 +2,9 @@ use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt},
 	schema::{columns::ExpenseColumns, ExpensesAdapter},
 };
-use clinvoice_finance::{ExchangeRates, Exchangeable, Money};
+use clinvoice_finance::Money;
 use clinvoice_schema::{Expense, Id};
-use futures::{stream, StreamExt, TryFutureExt, TryStreamExt};
+use futures::{stream, StreamExt, TryStreamExt};
 use sqlx::{Executor, Postgres, QueryBuilder, Result, Row};
 
 use super::PgExpenses;
@@ -28,23 +28,15 @@ impl ExpensesAdapter for PgExpenses
 
 		const COLUMNS: ExpenseColumns<&'static str> = ExpenseColumns::default();
 
-		let exchange_rates = ExchangeRates::new()
-			.map_err(util::finance_err_to_sqlx)
-			.await?;
-
 		QueryBuilder::new(
 			"INSERT INTO expenses
-				(timesheet_id, category, cost, description) ",
+				(timesheet_id, category, cost, cost_currency, description) ",
 		)
 		.push_values(expenses.iter(), |mut q, (category, cost, description)| {
 			q.push_bind(timesheet_id)
 				.push_bind(category)
-				.push_bind(
-					cost
-						.exchange(Default::default(), &exchange_rates)
-						.amount
-						.to_string(),
-				)
+				.push_bind(cost.amount)
+				.push_bind(cost.currency.to_string())
 				.push_bind(description);
 		})
 		.push(sql::RETURNING)
@@ -63,5 +55,15 @@ impl ExpensesAdapter for PgExpenses
 		})
 		.try_collect::<Vec<_>>()
 		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"currency" => util::sole_value_of(expenses.iter(), |(_, cost, _)| {
+					Some(cost.currency.to_string())
+				}),
+				"timesheet_id" => Some(timesheet_id.to_string()),
+				_ => None,
+			})
+		})
 	}
 }
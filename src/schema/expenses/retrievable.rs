@@ -1,17 +1,54 @@
+use std::collections::HashMap;
+
 use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
-	schema::columns::ExpenseColumns,
+	schema::columns::{ExpenseColumns, JobColumns, TimesheetColumns},
 	Retrievable,
+	WriteContext,
 	WriteWhereClause,
 };
-use clinvoice_finance::{ExchangeRates, Exchangeable};
+use clinvoice_finance::Currency;
 use clinvoice_match::MatchExpense;
-use clinvoice_schema::Expense;
-use futures::{future, TryFutureExt, TryStreamExt};
-use sqlx::{Pool, Postgres, QueryBuilder, Result};
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Expense,
+};
+use futures::{future, stream::BoxStream, TryStreamExt};
+use sqlx::{postgres::PgRow, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgExpenses;
-use crate::{schema::util, PgSchema};
+use crate::{
+	schema::{
+		util,
+		Error,
+		ExchangeRatesSource,
+		ExpenseGroupBy,
+		ExpenseTotal,
+		PgAudit,
+		PgExchangeRates,
+		PgTaxRate,
+		RetrieveOptions,
+		TaxedAmount,
+	},
+	PgSchema,
+};
+
+const COLUMNS: ExpenseColumns<&str> = ExpenseColumns::default();
+
+/// The alias of the [`ExpenseTotal::client_id`] column.
+const CLIENT_ID: &str = "client_id";
+
+/// The alias of the [`ExpenseTotal::count`] column.
+const COUNT: &str = "count";
+
+/// The alias of the [`ExpenseTotal::job_id`] column.
+const JOB_ID: &str = "job_id";
+
+/// The alias of the [`ExpenseTotal::month`] column.
+const MONTH: &str = "month";
+
+/// The alias of the [`ExpenseTotal::total`] column.
+const TOTAL: &str = "total";
 
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
@@ -30,29 +67,841 @@ impl Retrievable for PgExpenses
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: ExpenseColumns<&str> = ExpenseColumns::default();
+		Self::retrieve_with_options(connection, match_condition, &Default::default())
+			.await
+			.map_err(sqlx::Error::from)
+	}
+}
 
-		let columns = COLUMNS.default_scope();
-		let exchange_rates_fut = ExchangeRates::new().map_err(util::finance_err_to_sqlx);
-		let mut query = QueryBuilder::new(sql::SELECT);
+impl PgExpenses
+{
+	/// Count the [`Expense`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(util::COUNT);
+			},
+			|_| (),
+		);
 
 		query
-			.push_columns(&columns)
-			.push_default_from::<ExpenseColumns<char>>();
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
 
-		let exchange_rates = exchange_rates_fut.await?;
-		PgSchema::write_where_clause(
-			Default::default(),
+	/// Check whether any [`Expense`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(util::EXISTS);
+			},
+			|_| (),
+		);
+
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Expense`]s are retrieved as they
+	/// were `as_of` some time (via `connection`), rather than as they are now.
+	///
+	/// # See also
+	///
+	/// * [`RetrieveOptions::as_of`], to sort and paginate them as well.
+	pub async fn retrieve_as_of(
+		connection: &Pool<Postgres>,
+		as_of: DateTime<Utc>,
+		match_condition: &MatchExpense,
+	) -> Result<Vec<Expense>, Error>
+	{
+		Self::retrieve_with_options(connection, match_condition, &RetrieveOptions {
+			as_of: Some(as_of),
+			..Default::default()
+		})
+		.await
+	}
+
+	/// Generate the query which selects every [`Expense`] that matches the `match_condition`,
+	/// according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchExpense,
+		options: &RetrieveOptions<ExpenseColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) = Self::select_matching(
+			match_condition,
+			options.include_deleted,
+			options.as_of,
+			|q| {
+				let columns = COLUMNS.default_scope();
+				q.push_columns(&columns)
+					.push(',')
+					.push(util::currency_column(columns.cost));
+			},
+			|_| (),
+		);
+
+		options.write_where(
+			context,
+			COLUMNS,
 			ExpenseColumns::<char>::DEFAULT_ALIAS,
-			&match_condition.exchange_ref(Default::default(), &exchange_rates),
+			|c| c.id,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			ExpenseColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
 			&mut query,
 		);
 
+		Ok(query)
+	}
+
+	/// [Retrieve](Retrievable::retrieve) all [`Expense`]s (via `connection`) that match the
+	/// `match_condition`, and then exchange them into the `currency` using the rates which were
+	/// effective on the `time_begin` of the [`Timesheet`](clinvoice_schema::Timesheet) which each
+	/// [`Expense`] belongs to.
+	///
+	/// # See also
+	///
+	/// * [`PgExchangeRates`], where the rates that were used are stored.
+	pub async fn retrieve_exchanged<TSource>(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+		currency: Currency,
+		source: &TSource,
+	) -> Result<Vec<Expense>, Error>
+	where
+		TSource: ExchangeRatesSource + ?Sized,
+	{
+		let columns = COLUMNS.default_scope();
+		let timesheet_columns = TimesheetColumns::default().default_scope();
+
+		// NOTE: the `time_begin` is selected alongside each `Expense`, so that every `Expense` which
+		//       is retrieved has a date to be exchanged on.
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push_columns(&columns)
+					.push(',')
+					.push(util::currency_column(columns.cost))
+					.push(',')
+					.push(timesheet_columns.time_begin);
+			},
+			|q| {
+				q.push_default_equijoin::<TimesheetColumns<char>, _, _>(
+					timesheet_columns.id,
+					columns.timesheet_id,
+				);
+			},
+		);
+
+		let (expenses, dates): (Vec<_>, HashMap<_, _>) = query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| {
+				future::ready(PgExpenses::row_to_view(COLUMNS, &row).and_then(|x| {
+					row.try_get::<DateTime<Utc>, _>(TimesheetColumns::default().time_begin)
+						.map(|time_begin| {
+							let id = x.id;
+							(x, (id, time_begin.naive_utc().date()))
+						})
+				}))
+			})
+			.try_collect::<Vec<_>>()
+			.await?
+			.into_iter()
+			.unzip();
+
+		PgExchangeRates::exchange(
+			connection,
+			expenses,
+			currency,
+			source,
+			|x| dates[&x.id],
+			|x| vec![x.cost.currency],
+		)
+		.await
+	}
+
+	/// Retrieve the [`TaxedAmount`] of every [`Expense`] (via `connection`) that matches the
+	/// `match_condition`.
+	///
+	/// The `net` amount is the `cost` of the [`Expense`], which is taxed by every
+	/// [`TaxRate`](crate::schema::TaxRate) attached to it.
+	pub async fn retrieve_taxed_amounts(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+	) -> Result<Vec<TaxedAmount>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(columns.id).push(',');
+				PgTaxRate::push_taxed_amount_columns(
+					q,
+					columns.cost,
+					util::currency_column(columns.cost),
+				);
+			},
+			|q| PgTaxRate::push_percentage_join(q, "expense_tax_rates", "expense_id", columns.id),
+		);
+
 		query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgTaxRate::row_to_taxed_amount(&row, COLUMNS.id)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Retrieve the [`ExpenseTotal`]s (via `connection`) of the [`Expense`]s that match the
+	/// `match_condition`, after they have been grouped according to `group_by`.
+	///
+	/// The [`Expense`]s are grouped and summed by the database, so none of them have to be
+	/// retrieved.
+	pub async fn retrieve_totals(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+		group_by: ExpenseGroupBy,
+	) -> Result<Vec<ExpenseTotal>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+		let job_columns = JobColumns::default().default_scope();
+		let timesheet_columns = TimesheetColumns::default().default_scope();
+
+		// The expression and alias of each column which the `Expense`s are grouped by.
+		let keys: Vec<_> = [
+			(
+				group_by.category,
+				columns.category.to_string(),
+				COLUMNS.category,
+			),
+			(
+				group_by.client,
+				job_columns.client_id.to_string(),
+				CLIENT_ID,
+			),
+			(group_by.job, timesheet_columns.job_id.to_string(), JOB_ID),
+			(
+				group_by.month,
+				format!(
+					"date_trunc('month', {} AT TIME ZONE 'UTC')::date",
+					timesheet_columns.time_begin
+				),
+				MONTH,
+			),
+		]
+		.into_iter()
+		.filter_map(|(group, expression, alias)| group.then(|| (expression, alias)))
+		.collect();
+
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				keys.iter().for_each(|(expression, alias)| {
+					q.push(expression).push(sql::AS).push(alias).push(',');
+				});
+
+				q.push(util::currency_column(columns.cost))
+					.push(sql::AS)
+					.push(util::currency_column(TOTAL))
+					.push(',')
+					.push(util::COUNT)
+					.push(sql::AS)
+					.push(COUNT)
+					.push(",sum(")
+					.push(columns.cost)
+					.push(')')
+					.push(sql::AS)
+					.push(TOTAL);
+			},
+			|q| {
+				q.push_default_equijoin::<TimesheetColumns<char>, _, _>(
+					timesheet_columns.id,
+					columns.timesheet_id,
+				)
+				.push_default_equijoin::<JobColumns<char>, _, _>(
+					job_columns.id,
+					timesheet_columns.job_id,
+				);
+			},
+		);
+
+		{
+			let mut separated = query.push(sql::GROUP_BY).separated(',');
+			separated.push(util::currency_column(columns.cost));
+			keys.iter().for_each(|(expression, _)| {
+				separated.push(expression);
+			});
+		}
+
+		query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(Self::row_to_total(group_by, &row)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Create an [`ExpenseTotal`] from some `row` which [`PgExpenses::retrieve_totals`] selected
+	/// when the [`Expense`]s were grouped according to `group_by`.
+	fn row_to_total(group_by: ExpenseGroupBy, row: &PgRow) -> Result<ExpenseTotal>
+	{
+		Ok(ExpenseTotal {
+			category: group_by
+				.category
+				.then(|| row.try_get(COLUMNS.category))
+				.transpose()?,
+			client_id: group_by
+				.client
+				.then(|| row.try_get(CLIENT_ID))
+				.transpose()?,
+			count: row.try_get(COUNT)?,
+			job_id: group_by.job.then(|| row.try_get(JOB_ID)).transpose()?,
+			month: group_by.month.then(|| row.try_get(MONTH)).transpose()?,
+			total: util::money_from_row(row, TOTAL)?,
+		})
+	}
+
+	/// Same as [`PgExpenses::retrieve_with_options`], except that the [`Expense`]s are yielded as
+	/// they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchExpense,
+		options: &'a RetrieveOptions<ExpenseColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Expense, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			|rows| {
+				future::ready(
+					rows
+						.iter()
+						.map(|row| PgExpenses::row_to_view(COLUMNS, row))
+						.collect(),
+				)
+			},
+		)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Expense`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchExpense,
+		options: &RetrieveOptions<ExpenseColumns<&'static str>>,
+	) -> Result<Vec<Expense>, Error>
+	{
+		Self::retrieve_query(match_condition, options)?
 			.prepare()
 			.fetch(connection)
 			.and_then(|row| future::ready(PgExpenses::row_to_view(COLUMNS, &row)))
 			.try_collect()
 			.await
+			.map_err(Error::from)
+	}
+
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Expense`] that
+	/// matches the `match_condition`, after any extra tables have been `join`ed.
+	///
+	/// [Soft deleted](crate::schema::SoftDeletable) [`Expense`]s are excluded, unless
+	/// `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is returned so that
+	/// more conditions can be added.
+	///
+	/// When `as_of` is given, the [`AuditedTable`](crate::schema::AuditedTable)s are selected from
+	/// as they were at that time.
+	fn select_matching<'a, TJoin, TSelect>(
+		match_condition: &'a MatchExpense,
+		include_deleted: bool,
+		as_of: Option<DateTime<Utc>>,
+		select: TSelect,
+		join: TJoin,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TJoin: FnOnce(&mut QueryBuilder<'a, Postgres>),
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
+		let mut query = match as_of
+		{
+			Some(as_of) =>
+			{
+				let mut q = QueryBuilder::new("WITH ");
+				PgAudit::push_as_of(&mut q, as_of);
+				q.push(' ');
+				q
+			},
+			None => QueryBuilder::new(""),
+		};
+
+		query.push(sql::SELECT);
+		select(&mut query);
+		query.push_default_from::<ExpenseColumns<char>>();
+		join(&mut query);
+
+		let context = PgSchema::write_where_clause(
+			Default::default(),
+			ExpenseColumns::<char>::DEFAULT_ALIAS,
+			match_condition,
+			&mut query,
+		);
+
+		let context = util::write_not_deleted(
+			context,
+			ExpenseColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
+
+		(query, context)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+	use std::collections::HashSet;
+
+	use clinvoice_adapter::{
+		schema::{JobAdapter, LocationAdapter, OrganizationAdapter, TimesheetAdapter},
+		Retrievable,
+	};
+	use clinvoice_finance::Decimal;
+	use clinvoice_match::{Match, MatchExpense};
+	use clinvoice_schema::{
+		chrono::{NaiveDate, TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{
+		util,
+		ExpenseGroupBy,
+		ExpenseTotal,
+		PgExpenses,
+		PgJob,
+		PgLocation,
+		PgOrganization,
+		PgTaxRate,
+		PgTimesheet,
+		TaxedAmount,
+	};
+
+	#[tokio::test]
+	async fn retrieve_totals()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let (organization, organization2) = futures::try_join!(
+			PgOrganization::create(&connection, earth.clone(), "Some Organization".into()),
+			PgOrganization::create(&connection, earth, "Some Other Organization".into()),
+		)
+		.unwrap();
+
+		let invoice = Invoice {
+			date: None,
+			hourly_rate: Money::new(20_00, 2, Currency::Usd),
+		};
+
+		let employee = util::create_employee(&connection).await;
+		let job = PgJob::create(
+			&connection,
+			organization.clone(),
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			invoice.clone(),
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+		let job2 = PgJob::create(
+			&connection,
+			organization2.clone(),
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			invoice,
+			String::new(),
+			"Do something else".into(),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			vec![
+				(
+					"Flight".into(),
+					Money::new(300_56, 2, Currency::Usd),
+					"Trip to Hawaii for research".into(),
+				),
+				(
+					"Food".into(),
+					Money::new(10_00, 2, Currency::Usd),
+					"Lunch in Hawaii".into(),
+				),
+			],
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let timesheet2 = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			vec![(
+				"Food".into(),
+				Money::new(5_00, 2, Currency::Usd),
+				"Breakfast".into(),
+			)],
+			job,
+			Utc.ymd(1990, 08, 01).and_hms(09, 00, 00),
+			Some(Utc.ymd(1990, 08, 01).and_hms(10, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let timesheet3 = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![(
+				"Food".into(),
+				Money::new(20_00, 2, Currency::Eur),
+				"Dinner".into(),
+			)],
+			job2,
+			Utc.ymd(1990, 07, 20).and_hms(18, 00, 00),
+			Some(Utc.ymd(1990, 07, 20).and_hms(19, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let match_condition = MatchExpense {
+			timesheet_id: Match::Or(vec![
+				timesheet.id.into(),
+				timesheet2.id.into(),
+				timesheet3.id.into(),
+			]),
+			..Default::default()
+		};
+
+		let total = |count, total, category, client_id, month| ExpenseTotal {
+			category,
+			client_id,
+			count,
+			job_id: None,
+			month,
+			total,
+		};
+
+		assert_eq!(
+			PgExpenses::retrieve_totals(&connection, &match_condition, Default::default())
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[
+				total(3, Money::new(315_56, 2, Currency::Usd), None, None, None),
+				total(1, Money::new(20_00, 2, Currency::Eur), None, None, None),
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+
+		assert_eq!(
+			PgExpenses::retrieve_totals(&connection, &match_condition, ExpenseGroupBy {
+				category: true,
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
+			[
+				total(
+					1,
+					Money::new(300_56, 2, Currency::Usd),
+					Some("Flight".into()),
+					None,
+					None
+				),
+				total(
+					2,
+					Money::new(15_00, 2, Currency::Usd),
+					Some("Food".into()),
+					None,
+					None
+				),
+				total(
+					1,
+					Money::new(20_00, 2, Currency::Eur),
+					Some("Food".into()),
+					None,
+					None
+				),
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+
+		assert_eq!(
+			PgExpenses::retrieve_totals(&connection, &match_condition, ExpenseGroupBy {
+				client: true,
+				month: true,
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
+			[
+				total(
+					2,
+					Money::new(310_56, 2, Currency::Usd),
+					None,
+					Some(organization.id),
+					Some(NaiveDate::from_ymd(1990, 07, 01))
+				),
+				total(
+					1,
+					Money::new(5_00, 2, Currency::Usd),
+					None,
+					Some(organization.id),
+					Some(NaiveDate::from_ymd(1990, 08, 01))
+				),
+				total(
+					1,
+					Money::new(20_00, 2, Currency::Eur),
+					None,
+					Some(organization2.id),
+					Some(NaiveDate::from_ymd(1990, 07, 01))
+				),
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_taxed_amounts()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let earth = organization.location.clone();
+
+		let employee = util::create_employee(&connection).await;
+		let job = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+		let vat = PgTaxRate::create(&connection, "VAT".into(), Decimal::new(20, 0), Some(&earth))
+			.await
+			.unwrap();
+		let levy = PgTaxRate::create(&connection, "Levy".into(), Decimal::new(2_5, 1), None)
+			.await
+			.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![
+				(
+					"Flight".into(),
+					Money::new(300_56, 2, Currency::Usd),
+					"Trip to Hawaii for research".into(),
+				),
+				(
+					"Food".into(),
+					Money::new(10_00, 2, Currency::Usd),
+					"Lunch in Hawaii".into(),
+				),
+			],
+			job,
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		PgTaxRate::attach_to_expense(&mut transaction, &timesheet.expenses[0], &[
+			vat.clone(),
+			levy.clone(),
+		])
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let match_expense = MatchExpense {
+			timesheet_id: timesheet.id.into(),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgExpenses::retrieve_taxed_amounts(&connection, &match_expense)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[
+				TaxedAmount {
+					// 22.5% of $300.56 is $67.626
+					gross: Money::new(368_19, 2, Currency::Usd),
+					id: timesheet.expenses[0].id,
+					net: Money::new(300_56, 2, Currency::Usd),
+					tax: Money::new(67_63, 2, Currency::Usd),
+				},
+				TaxedAmount {
+					gross: Money::new(10_00, 2, Currency::Usd),
+					id: timesheet.expenses[1].id,
+					net: Money::new(10_00, 2, Currency::Usd),
+					tax: Money::new(0, 2, Currency::Usd),
+				},
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+
+		PgTaxRate::detach_from_expense(&connection, &timesheet.expenses[0], &[vat])
+			.await
+			.unwrap();
+
+		assert_eq!(
+			PgExpenses::retrieve_taxed_amounts(&connection, &MatchExpense {
+				id: timesheet.expenses[0].id.into(),
+				..Default::default()
+			})
+			.await
+			.unwrap(),
+			[TaxedAmount {
+				// 2.5% of $300.56 is $7.514
+				gross: Money::new(308_07, 2, Currency::Usd),
+				id: timesheet.expenses[0].id,
+				net: Money::new(300_56, 2, Currency::Usd),
+				tax: Money::new(7_51, 2, Currency::Usd),
+			}],
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_negative()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![
+				(
+					"Taxi".into(),
+					Money::new(563_30, 2, Currency::Nok),
+					"Took a taxi cab".into(),
+				),
+				(
+					"Taxi".into(),
+					Money::new(-563_30, 2, Currency::Nok),
+					"Refunded taxi cab".into(),
+				),
+			],
+			job,
+			Utc.ymd(2022, 06, 08).and_hms(15, 27, 00),
+			Some(Utc.ymd(2022, 06, 09).and_hms(07, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgExpenses::retrieve(&connection, &MatchExpense {
+				cost: Match::LessThan(Money::new(0, 2, Currency::Nok)),
+				timesheet_id: timesheet.id.into(),
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.as_slice(),
+			&[timesheet.expenses[1].clone()],
+		);
 	}
 }
@@ -3,7 +3,7 @@ use clinvoice_schema::Expense;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgExpenses;
-use crate::PgSchema;
+use crate::{schema::util, PgSchema};
 
 #[async_trait::async_trait]
 impl Updatable for PgExpenses
@@ -20,7 +20,7 @@ impl Updatable for PgExpenses
 		Self::Entity: 'e,
 		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
 	{
-		let mut peekable_entities = entities.peekable();
+		let mut peekable_entities = entities.clone().peekable();
 
 		// There is nothing to do.
 		if peekable_entities.peek().is_none()
@@ -28,15 +28,33 @@ impl Updatable for PgExpenses
 			return Ok(());
 		}
 
-		PgSchema::update(connection, ExpenseColumns::default(), |query| {
-			query.push_values(peekable_entities, |mut q, e| {
-				q.push_bind(&e.category)
-					.push_bind(e.cost.amount.to_string())
-					.push_bind(&e.description)
-					.push_bind(e.id)
-					.push_bind(e.timesheet_id);
-			});
-		})
+		PgSchema::update_with(
+			connection,
+			ExpenseColumns::default(),
+			&[util::currency_column(
+				ExpenseColumns::<&str>::default().cost,
+			)],
+			|query| {
+				query.push_values(peekable_entities, |mut q, e| {
+					q.push_bind(&e.category)
+						.push_bind(e.cost.amount)
+						.push_bind(&e.description)
+						.push_bind(e.id)
+						.push_bind(e.timesheet_id)
+						.push_bind(e.cost.currency.to_string());
+				});
+			},
+		)
 		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| {
+				util::sole_value_of(entities, |x| match field
+				{
+					"currency" => Some(x.cost.currency.to_string()),
+					"timesheet_id" => Some(x.timesheet_id.to_string()),
+					_ => None,
+				})
+			})
+		})
 	}
 }
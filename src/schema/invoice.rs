@@ -0,0 +1,419 @@
+mod deletable;
+mod retrievable;
+mod updatable;
+
+use clinvoice_finance::Decimal;
+use clinvoice_schema::{
+	chrono::{DateTime, Datelike, Utc},
+	Expense,
+	Id,
+	Job,
+	Timesheet,
+};
+use sqlx::{postgres::PgRow, Postgres, Result, Row, Transaction};
+
+use super::{util, Adjustment, Error, InvoiceItem, InvoiceLine, IssuedInvoice};
+use crate::fmt::DateTimeExt;
+
+/// Implementor of the [`Deletable`](clinvoice_adapter::Deletable),
+/// [`Retrievable`](clinvoice_adapter::Retrievable), and [`Updatable`](clinvoice_adapter::Updatable)
+/// traits for [`IssuedInvoice`]s stored in the [`Postgres`] database.
+pub struct PgInvoice;
+
+impl PgInvoice
+{
+	/// Issue a new [`IssuedInvoice`] (via `connection`) for the `job`, which bills the `timesheets`
+	/// and `expenses`, and applies the `adjustments`.
+	///
+	/// The [`IssuedInvoice`] is given the next `number` of the year it was issued in. Since that
+	/// number is not given to anyone else until the `connection` is committed or rolled back, no
+	/// numbers are skipped.
+	///
+	/// Each [`Timesheet`] is billed for the time between its `time_begin` and `time_end`, rounded to
+	/// the nearest `increment` of the `job`, at its
+	/// [`RatedTimesheet::hourly_rate`](crate::schema::RatedTimesheet::hourly_rate). Each [`Expense`]
+	/// is billed for its `cost`. Each [`Adjustment`] is billed for its fixed amount, or its
+	/// percentage of the `timesheets` (rounded to two decimal places).
+	///
+	/// # Errors
+	///
+	/// * [`Error::UniqueViolation`] if any of the `timesheets` or `expenses` were already billed by
+	///   another [`IssuedInvoice`].
+	/// * [`Error::NotFound`] if any of the `timesheets`, `expenses`, or `adjustments` are not
+	///   part of the `job`, any of the `timesheets` do not have a `time_end`, or any of the
+	///   `timesheets` or `expenses` were [soft deleted](crate::schema::SoftDeletable).
+	///
+	/// Either way, the `connection` should be rolled back.
+	pub async fn create(
+		connection: &mut Transaction<'_, Postgres>,
+		job: &Job,
+		date_issued: DateTime<Utc>,
+		timesheets: &[Timesheet],
+		expenses: &[Expense],
+		adjustments: &[Adjustment],
+	) -> Result<IssuedInvoice, Error>
+	{
+		let date_issued = date_issued.pg_sanitize();
+		let year = date_issued.year();
+
+		let number = sqlx::query!(
+			"INSERT INTO invoice_numbers (year, number) VALUES ($1, 1)
+				ON CONFLICT (year) DO UPDATE SET number = invoice_numbers.number + 1
+				RETURNING number;",
+			year,
+		)
+		.fetch_one(&mut *connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?
+		.number;
+
+		let id = sqlx::query!(
+			"INSERT INTO invoices
+				(job_id, year, number, date_issued)
+			VALUES
+				($1,     $2,   $3,     $4)
+			RETURNING id;",
+			job.id,
+			year,
+			number,
+			date_issued,
+		)
+		.fetch_one(&mut *connection)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"job_id" => Some(job.id.to_string()),
+				"number" => Some(number.to_string()),
+				"year" => Some(year.to_string()),
+				_ => None,
+			})
+		})?
+		.id;
+
+		let timesheet_ids: Vec<_> = timesheets.iter().map(|t| t.id).collect();
+		let timesheet_lines = sqlx::query!(
+			r#"INSERT INTO invoice_lines (invoice_id, timesheet_id, amount, amount_currency)
+				SELECT
+					$1,
+					T.id,
+					extract(EPOCH FROM round_to_increment(T.time_end - T.time_begin, J.increment))::numeric
+						/ 3600 * TR.hourly_rate,
+					TR.hourly_rate_currency
+				FROM timesheets T
+				JOIN jobs J ON (J.id = T.job_id)
+				JOIN timesheet_rates TR ON (TR.timesheet_id = T.id)
+				WHERE T.id = ANY($2) AND T.job_id = $3 AND T.time_end IS NOT NULL AND T.deleted_at IS NULL
+			RETURNING id, timesheet_id AS "timesheet_id!", amount, amount_currency AS "amount_currency!";"#,
+			id,
+			&timesheet_ids,
+			job.id,
+		)
+		.fetch_all(&mut *connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		let expense_ids: Vec<_> = expenses.iter().map(|x| x.id).collect();
+		let expense_lines = sqlx::query!(
+			r#"INSERT INTO invoice_lines (invoice_id, expense_id, amount, amount_currency)
+				SELECT $1, X.id, X.cost, X.cost_currency
+				FROM expenses X
+				JOIN timesheets T ON (T.id = X.timesheet_id)
+				WHERE X.id = ANY($2) AND T.job_id = $3 AND X.deleted_at IS NULL
+			RETURNING id, expense_id AS "expense_id!", amount, amount_currency AS "amount_currency!";"#,
+			id,
+			&expense_ids,
+			job.id,
+		)
+		.fetch_all(&mut *connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		// NOTE: must come after the `timesheet_lines`, since percentages are of their total.
+		let adjustment_ids: Vec<_> = adjustments.iter().map(|a| a.id).collect();
+		let adjustment_lines = sqlx::query!(
+			r#"INSERT INTO invoice_lines (invoice_id, adjustment_id, amount, amount_currency)
+				SELECT
+					$1,
+					A.id,
+					COALESCE(
+						A.amount,
+						round(A.percentage / 100 * (
+							SELECT COALESCE(sum(L.amount), 0)
+							FROM invoice_lines L
+							WHERE L.invoice_id = $1 AND L.timesheet_id IS NOT NULL
+						), 2)
+					),
+					J.invoice_hourly_rate_currency
+				FROM job_adjustments A
+				JOIN jobs J ON (J.id = A.job_id)
+				WHERE A.id = ANY($2) AND A.job_id = $3
+			RETURNING
+				id,
+				adjustment_id AS "adjustment_id!",
+				amount,
+				amount_currency AS "amount_currency!";"#,
+			id,
+			&adjustment_ids,
+			job.id,
+		)
+		.fetch_all(&mut *connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		if timesheet_lines.len() != timesheet_ids.len() ||
+			expense_lines.len() != expense_ids.len() ||
+			adjustment_lines.len() != adjustment_ids.len()
+		{
+			return Err(Error::NotFound);
+		}
+
+		let mut lines = timesheet_lines
+			.into_iter()
+			.map(|l| {
+				Self::line(
+					l.id,
+					InvoiceItem::Timesheet(l.timesheet_id),
+					l.amount,
+					&l.amount_currency,
+				)
+			})
+			.chain(expense_lines.into_iter().map(|l| {
+				Self::line(
+					l.id,
+					InvoiceItem::Expense(l.expense_id),
+					l.amount,
+					&l.amount_currency,
+				)
+			}))
+			.chain(adjustment_lines.into_iter().map(|l| {
+				Self::line(
+					l.id,
+					InvoiceItem::Adjustment(l.adjustment_id),
+					l.amount,
+					&l.amount_currency,
+				)
+			}))
+			.collect::<Result<Vec<_>>>()?;
+
+		lines.sort_by_key(|l| l.id);
+
+		Ok(IssuedInvoice {
+			date_issued,
+			date_paid: None,
+			id,
+			job_id: job.id,
+			lines,
+			number,
+			year,
+		})
+	}
+
+	/// Create an [`InvoiceLine`] from the values which were stored in the database.
+	fn line(id: Id, item: InvoiceItem, amount: Decimal, currency: &str) -> Result<InvoiceLine>
+	{
+		Ok(InvoiceLine {
+			amount: util::money_from(amount, currency)?,
+			id,
+			item,
+		})
+	}
+
+	/// Create an [`IssuedInvoice`] from some `row` which [`Retrievable::retrieve`] selected.
+	///
+	/// [`Retrievable::retrieve`]: clinvoice_adapter::Retrievable::retrieve
+	pub(super) fn row_to_view(row: &PgRow) -> Result<IssuedInvoice>
+	{
+		let lines = row
+			.try_get::<Option<Vec<(Id, Option<Id>, Option<Id>, Option<Id>, Decimal, String)>>, _>(
+				"lines",
+			)?
+			.unwrap_or_default()
+			.into_iter()
+			.map(
+				|(id, adjustment_id, expense_id, timesheet_id, amount, currency)| {
+					let item = match (adjustment_id, expense_id, timesheet_id)
+					{
+						(Some(a), None, None) => InvoiceItem::Adjustment(a),
+						(None, Some(x), None) => InvoiceItem::Expense(x),
+						(None, None, Some(t)) => InvoiceItem::Timesheet(t),
+						_ =>
+						{
+							return Err(sqlx::Error::Decode(
+								"an invoice line must bill exactly one adjustment, expense, or timesheet"
+									.into(),
+							))
+						},
+					};
+
+					Self::line(id, item, amount, &currency)
+				},
+			)
+			.collect::<Result<_>>()?;
+
+		Ok(IssuedInvoice {
+			date_issued: row.try_get("date_issued")?,
+			date_paid: row.try_get("date_paid")?,
+			id: row.try_get("id")?,
+			job_id: row.try_get("job_id")?,
+			lines,
+			number: row.try_get("number")?,
+			year: row.try_get("year")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::schema::TimesheetAdapter;
+	use clinvoice_finance::Decimal;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::PgInvoice;
+	use crate::schema::{util, AdjustmentKind, InvoiceItem, PgAdjustment, PgTimesheet};
+
+	#[tokio::test]
+	async fn create()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			vec![(
+				"Flight".into(),
+				Money::new(300_56, 2, Currency::Usd),
+				"Trip to Hawaii for research".into(),
+			)],
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 05, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let timesheet2 = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			Vec::new(),
+			job.clone(),
+			Utc.ymd(1990, 07, 13).and_hms(09, 00, 00),
+			Some(Utc.ymd(1990, 07, 13).and_hms(09, 20, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let discount = PgAdjustment::create(
+			&mut transaction,
+			&job,
+			AdjustmentKind::Percentage(Decimal::new(-10, 0)),
+			"Loyalty discount".into(),
+		)
+		.await
+		.unwrap();
+
+		let invoice = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2091, 08, 01).and_hms(09, 00, 00),
+			&[timesheet.clone()],
+			&timesheet.expenses,
+			&[discount.clone()],
+		)
+		.await
+		.unwrap();
+
+		let invoice2 = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2091, 09, 01).and_hms(09, 00, 00),
+			&[timesheet2.clone()],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(invoice.year, 2091);
+		assert_eq!(invoice2.number, invoice.number + 1);
+		assert_eq!(
+			invoice
+				.lines
+				.iter()
+				.map(|l| (l.item, l.amount))
+				.collect::<Vec<_>>(),
+			[
+				// 1h05m, rounded to the nearest 15m, at $20/h
+				(
+					InvoiceItem::Timesheet(timesheet.id),
+					Money::new(20_00, 2, Currency::Usd)
+				),
+				(
+					InvoiceItem::Expense(timesheet.expenses[0].id),
+					Money::new(300_56, 2, Currency::Usd)
+				),
+				// 10% of the time, but not the expenses
+				(
+					InvoiceItem::Adjustment(discount.id),
+					Money::new(-2_00, 2, Currency::Usd)
+				),
+			],
+		);
+
+		// A timesheet cannot be billed twice.
+		let mut transaction = connection.begin().await.unwrap();
+		assert!(PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2091, 10, 01).and_hms(09, 00, 00),
+			&[timesheet],
+			&[],
+			&[],
+		)
+		.await
+		.is_err());
+		transaction.rollback().await.unwrap();
+
+		// The rolled back invoice did not use up a number.
+		let mut transaction = connection.begin().await.unwrap();
+		let invoice3 = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2091, 10, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+		transaction.commit().await.unwrap();
+
+		assert_eq!(invoice3.number, invoice2.number + 1);
+	}
+}
@@ -0,0 +1,161 @@
+use clinvoice_adapter::Deletable;
+use sqlx::{Executor, Postgres, Result};
+
+use super::PgInvoice;
+use crate::schema::{util, IssuedInvoice};
+
+#[async_trait::async_trait]
+impl Deletable for PgInvoice
+{
+	type Db = Postgres;
+	type Entity = IssuedInvoice;
+
+	/// Delete the `entities` (via `connection`), along with their
+	/// [`lines`](IssuedInvoice::lines).
+	///
+	/// # Errors
+	///
+	/// * [`Error::Database`](sqlx::Error::Database) (containing a
+	///   [`ValidationError`](crate::schema::ValidationError)) if any of the `entities` is not one of
+	///   the most recent [`IssuedInvoice`]s of its `year`, since deleting it would leave a gap in the
+	///   `number`s. Nothing is deleted in this case.
+	async fn delete<'c, 'e, 'i, TConn, TIter>(connection: TConn, entities: TIter) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Self::Db>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let entities: Vec<_> = entities.collect();
+		let ids: Vec<_> = entities.iter().map(|e| e.id).collect();
+
+		// There is nothing to do
+		if ids.is_empty()
+		{
+			return Ok(());
+		}
+
+		sqlx::query!("DELETE FROM invoices WHERE id = ANY($1);", &ids)
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities.iter().copied(), |i| match field
+					{
+						"number" => Some(i.number.to_string()),
+						_ => None,
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{schema::JobAdapter, Deletable, Retrievable};
+	use clinvoice_match::Match;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, MatchIssuedInvoice, PgInvoice, PgJob};
+
+	#[tokio::test]
+	async fn delete()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let invoice = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2092, 01, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+
+		let invoice2 = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2092, 02, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let match_both = MatchIssuedInvoice {
+			id: Match::Or(vec![invoice.id.into(), invoice2.id.into()]),
+			..Default::default()
+		};
+
+		// Deleting `invoice` would leave a gap before `invoice2`.
+		assert!(PgInvoice::delete(&connection, [&invoice].into_iter())
+			.await
+			.is_err());
+		assert_eq!(
+			PgInvoice::retrieve(&connection, &match_both).await.unwrap(),
+			[invoice.clone(), invoice2.clone()],
+		);
+
+		PgInvoice::delete(&connection, [&invoice, &invoice2].into_iter())
+			.await
+			.unwrap();
+		assert!(PgInvoice::retrieve(&connection, &match_both)
+			.await
+			.unwrap()
+			.is_empty());
+
+		// The numbers of the deleted invoices are given out again.
+		let mut transaction = connection.begin().await.unwrap();
+		let invoice3 = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2092, 03, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+		transaction.commit().await.unwrap();
+
+		assert_eq!(invoice3.number, invoice.number);
+	}
+}
@@ -0,0 +1,51 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Retrievable, WriteWhereClause};
+use futures::{future, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result};
+
+use super::PgInvoice;
+use crate::{
+	schema::{IssuedInvoice, MatchIssuedInvoice},
+	PgSchema,
+};
+
+/// Implementors of this trait are capable of being retrieved from a [`Database`].
+#[async_trait::async_trait]
+impl Retrievable for PgInvoice
+{
+	/// The [`Database`] where data of type [`Updatable::Entity`] is being stored.
+	type Db = Postgres;
+	/// The type of data that is to be [`update`](Deletable::update)d.
+	type Entity = IssuedInvoice;
+	/// The type used for [match](clinvoice_match)ing.
+	type Match = MatchIssuedInvoice;
+
+	/// Retrieve all [`IssuedInvoice`]s (via `connection`) that match the `match_condition`.
+	///
+	/// The [`IssuedInvoice`]s are sorted by their `year` and `number`.
+	async fn retrieve(
+		connection: &Pool<Postgres>,
+		match_condition: &Self::Match,
+	) -> Result<Vec<Self::Entity>>
+	{
+		let mut query = QueryBuilder::new(
+			"SELECT I.id, I.job_id, I.year, I.number, I.date_issued, I.date_paid,
+				array_agg(
+					(L.id, L.adjustment_id, L.expense_id, L.timesheet_id, L.amount, L.amount_currency)
+					ORDER BY L.id
+				)
+					FILTER (WHERE L.id IS NOT NULL) AS lines
+			FROM invoices I
+			LEFT JOIN invoice_lines L ON (L.invoice_id = I.id)",
+		);
+
+		PgSchema::write_where_clause(Default::default(), 'I', match_condition, &mut query);
+
+		query
+			.push(" GROUP BY I.id ORDER BY I.year, I.number;")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgInvoice::row_to_view(&row)))
+			.try_collect()
+			.await
+	}
+}
@@ -0,0 +1,152 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Updatable};
+use sqlx::{Postgres, QueryBuilder, Result, Transaction};
+
+use super::PgInvoice;
+use crate::{
+	fmt::DateTimeExt,
+	schema::{util, IssuedInvoice},
+};
+
+#[async_trait::async_trait]
+impl Updatable for PgInvoice
+{
+	type Db = Postgres;
+	type Entity = IssuedInvoice;
+
+	/// Update the `date_issued` and `date_paid` of the `entities` (via `connection`).
+	///
+	/// The `number`, `year`, and `lines` of an [`IssuedInvoice`] are fixed once it has been issued,
+	/// so the `date_issued` may not be moved to another `year`.
+	async fn update<'e, 'i, TIter>(
+		connection: &mut Transaction<Self::Db>,
+		entities: TIter,
+	) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.clone().peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(
+			"UPDATE invoices AS I SET date_issued = V.date_issued, date_paid = V.date_paid FROM (",
+		);
+
+		query
+			.push_values(peekable_entities, |mut q, e| {
+				q.push_bind(e.id)
+					.push_bind(e.date_issued.pg_sanitize())
+					.push_bind(e.date_paid.pg_sanitize());
+			})
+			.push(") AS V (id, date_issued, date_paid) WHERE I.id = V.id;")
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities, |i| match field
+					{
+						"date_paid" => i.date_paid.map(|d| d.to_string()),
+						"year" => Some(i.year.to_string()),
+						_ => None,
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{schema::JobAdapter, Retrievable, Updatable};
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, IssuedInvoice, PgInvoice, PgJob};
+
+	#[tokio::test]
+	async fn update()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		let invoice = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2093, 01, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let paid = IssuedInvoice {
+			date_issued: Utc.ymd(2093, 01, 02).and_hms(09, 00, 00),
+			date_paid: Some(Utc.ymd(2093, 01, 16).and_hms(12, 30, 00)),
+			..invoice.clone()
+		};
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgInvoice::update(&mut transaction, [&paid].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		assert_eq!(
+			PgInvoice::retrieve(&connection, &invoice.id.into())
+				.await
+				.unwrap(),
+			[paid.clone()],
+		);
+
+		// The invoice cannot be moved into another year, since it was numbered in this one.
+		let moved = IssuedInvoice {
+			date_issued: Utc.ymd(2094, 01, 02).and_hms(09, 00, 00),
+			..paid
+		};
+
+		let mut transaction = connection.begin().await.unwrap();
+		assert!(PgInvoice::update(&mut transaction, [&moved].into_iter())
+			.await
+			.is_err());
+	}
+}
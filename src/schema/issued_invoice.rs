@@ -0,0 +1,102 @@
+use clinvoice_finance::Money;
+use clinvoice_match::{Match, MatchOption};
+use clinvoice_schema::{
+	chrono::{DateTime, NaiveDateTime, Utc},
+	Id,
+};
+
+/// Something which was billed on an [`IssuedInvoice`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum InvoiceItem
+{
+	/// The [`Adjustment`](crate::schema::Adjustment) with this [`Id`].
+	Adjustment(Id),
+
+	/// The [`Expense`](clinvoice_schema::Expense) with this [`Id`].
+	Expense(Id),
+
+	/// The time worked on the [`Timesheet`](clinvoice_schema::Timesheet) with this [`Id`].
+	Timesheet(Id),
+}
+
+/// A line of an [`IssuedInvoice`], which records exactly what was billed and for how much.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct InvoiceLine
+{
+	/// The amount which was billed for the `item`.
+	pub amount: Money,
+
+	/// The reference number of this line, which is unique among all [`InvoiceLine`]s.
+	pub id: Id,
+
+	/// What was billed.
+	pub item: InvoiceItem,
+}
+
+/// An invoice which has been issued for (part of) a [`Job`](clinvoice_schema::Job).
+///
+/// Unlike the [`Invoice`](clinvoice_schema::Invoice) of a [`Job`](clinvoice_schema::Job), each
+/// [`IssuedInvoice`] has a `number`, and records which [`Timesheet`](clinvoice_schema::Timesheet)s
+/// and [`Expense`](clinvoice_schema::Expense)s it billed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct IssuedInvoice
+{
+	/// The date upon which the [`IssuedInvoice`] was sent to the client.
+	pub date_issued: DateTime<Utc>,
+
+	/// The date upon which the client paid the [`IssuedInvoice`].
+	pub date_paid: Option<DateTime<Utc>>,
+
+	/// The reference number of this [`IssuedInvoice`], which is unique among all
+	/// [`IssuedInvoice`]s.
+	pub id: Id,
+
+	/// The [`Id`] of the [`Job`](clinvoice_schema::Job) which was billed.
+	pub job_id: Id,
+
+	/// What was billed, in the order it was billed.
+	pub lines: Vec<InvoiceLine>,
+
+	/// The number of this [`IssuedInvoice`] within its `year`. The [`IssuedInvoice`]s of each
+	/// `year` are numbered `1`, `2`, `3`, … without any gaps.
+	pub number: i32,
+
+	/// The year (in UTC) of the `date_issued`.
+	pub year: i32,
+}
+
+/// An [`IssuedInvoice`] with [matchable](clinvoice_match) fields.
+///
+/// The `lines` of an [`IssuedInvoice`] cannot be matched.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchIssuedInvoice
+{
+	#[allow(missing_docs)]
+	pub date_issued: Match<NaiveDateTime>,
+
+	#[allow(missing_docs)]
+	pub date_paid: MatchOption<NaiveDateTime>,
+
+	#[allow(missing_docs)]
+	pub id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub job_id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub number: Match<i32>,
+
+	#[allow(missing_docs)]
+	pub year: Match<i32>,
+}
+
+impl From<Id> for MatchIssuedInvoice
+{
+	fn from(id: Id) -> Self
+	{
+		Self {
+			id: id.into(),
+			..Default::default()
+		}
+	}
+}
@@ -3,10 +3,11 @@ mod job_adapter;
 mod retrievable;
 mod updatable;
 
+use std::collections::HashMap;
+
 use clinvoice_adapter::schema::columns::{JobColumns, OrganizationColumns};
-use clinvoice_finance::{Decimal, Money};
-use clinvoice_schema::{Invoice, InvoiceDate, Job};
-use sqlx::{postgres::PgRow, Executor, Postgres, Result, Row};
+use clinvoice_schema::{Id, Invoice, InvoiceDate, Job, Location};
+use sqlx::{postgres::PgRow, Result, Row};
 
 use super::{util, PgOrganization};
 
@@ -16,27 +17,18 @@ pub struct PgJob;
 
 impl PgJob
 {
-	pub(super) async fn row_to_view<'c, TConn, TJobColumns, TOrgColumns>(
-		connection: TConn,
+	/// Create a [`Job`] from some `row`, whose client's [`Location`] must be one of the
+	/// `locations`.
+	pub(super) fn row_to_view<TJobColumns, TOrgColumns>(
 		columns: JobColumns<TJobColumns>,
 		organization_columns: OrganizationColumns<TOrgColumns>,
+		locations: &HashMap<Id, Location>,
 		row: &PgRow,
 	) -> Result<Job>
 	where
-		TConn: Executor<'c, Database = Postgres>,
 		TJobColumns: AsRef<str>,
 		TOrgColumns: AsRef<str>,
 	{
-		let client_fut = PgOrganization::row_to_view(connection, organization_columns, row);
-
-		let amount = row
-			.try_get::<String, _>(columns.invoice_hourly_rate.as_ref())
-			.and_then(|raw_hourly_rate| {
-				raw_hourly_rate
-					.parse::<Decimal>()
-					.map_err(|e| util::finance_err_to_sqlx(e.into()))
-			})?;
-
 		let increment = row
 			.try_get(columns.increment.as_ref())
 			.and_then(util::duration_from)?;
@@ -56,14 +48,11 @@ impl PgJob
 							paid: invoice_date_paid,
 						})
 					})?,
-				hourly_rate: Money {
-					amount,
-					..Default::default()
-				},
+				hourly_rate: util::money_from_row(row, columns.invoice_hourly_rate.as_ref())?,
 			},
 			notes: row.try_get(columns.notes.as_ref())?,
 			objectives: row.try_get(columns.objectives.as_ref())?,
-			client: client_fut.await?,
+			client: PgOrganization::row_to_view(organization_columns, locations, row)?,
 		})
 	}
 }
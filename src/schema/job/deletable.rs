@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::JobColumns, Deletable};
 use clinvoice_schema::{Id, Job};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Executor, Postgres, Result, Transaction};
 
 use super::PgJob;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, PgTimesheet, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgJob
@@ -28,18 +31,86 @@ impl Deletable for PgJob
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgJob
+{
+	type Entity = Job;
+
+	/// Soft delete the `entities` (via `connection`), along with their
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s (and their children) which were not already soft
+	/// deleted.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(j: &Job) -> Id
+		{
+			j.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Job| e.id`
+		PgSchema::set_deleted::<_, _, JobColumns<char>>(connection, entities.map(mapper), true)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Restore the `entities` (via `connection`), along with the
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s (and their children) which were soft deleted at
+	/// the same time as them.
+	///
+	/// # Errors
+	///
+	/// * A [`TimesheetOverlap`](crate::schema::TimesheetOverlap) if any of the
+	///   [`Timesheet`](clinvoice_schema::Timesheet)s which are restored would overlap another
+	///   [`Timesheet`](clinvoice_schema::Timesheet) of the same
+	///   [`Employee`](clinvoice_schema::Employee).
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(j: &Job) -> Id
+		{
+			j.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Job| e.id`
+		let ids: Vec<_> = entities.map(mapper).collect();
+		PgTimesheet::restore_cascading::<JobColumns<char>>(
+			connection,
+			&ids,
+			"SELECT T.id, T.employee_id, T.time_begin, T.time_end
+			FROM timesheets T JOIN jobs J ON (J.id = T.job_id)
+			WHERE J.id = ANY($1) AND T.deleted_at = J.deleted_at;",
+		)
+		.await
+		.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
 	use core::time::Duration;
+	use std::collections::HashSet;
 
 	use clinvoice_adapter::{
-		schema::{JobAdapter, LocationAdapter, OrganizationAdapter},
+		schema::{JobAdapter, LocationAdapter, OrganizationAdapter, TimesheetAdapter},
 		Deletable,
 		Retrievable,
 	};
-	use clinvoice_finance::{Currency, ExchangeRates, Exchangeable, Money};
-	use clinvoice_match::Match;
+	use clinvoice_finance::{Currency, Money};
+	use clinvoice_match::{Match, MatchExpense, MatchJob, MatchTimesheet};
 	use clinvoice_schema::{
 		chrono::{TimeZone, Utc},
 		Invoice,
@@ -47,7 +118,17 @@ mod tests
 	};
 	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgJob, PgLocation, PgOrganization};
+	use crate::schema::{
+		util,
+		PgExpenses,
+		PgJob,
+		PgLocation,
+		PgOrganization,
+		PgTimesheet,
+		RetrieveOptions,
+		SoftDeletable,
+		TimesheetOverlap,
+	};
 
 	#[tokio::test]
 	async fn delete()
@@ -121,20 +202,213 @@ mod tests
 			.await
 			.unwrap();
 
-		let exchange_rates = ExchangeRates::new().await.unwrap();
 		assert_eq!(
 			PgJob::retrieve(
 				&connection,
-				&Match::Or(vec![
-					job.id.into(),
-					job2.id.into(),
-					job3.id.into(),
-				]).into(),
+				&Match::Or(vec![job.id.into(), job2.id.into(), job3.id.into(),]).into(),
 			)
 			.await
 			.unwrap()
 			.as_slice(),
-			&[job3.exchange(Default::default(), &exchange_rates)],
+			&[job3],
+		);
+	}
+
+	#[tokio::test]
+	async fn soft_delete()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization.clone(),
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(300),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+		let job2 = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(2011, 03, 17).and_hms(12, 07, 07),
+			Money::new(20_00, 2, Currency::Eur),
+		)
+		.await;
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			util::create_employee(&connection).await,
+			vec![(
+				"Food".into(),
+				Money::new(10_17, 2, Currency::Usd),
+				"Takeout".into(),
+			)],
+			job.clone(),
+			Utc.ymd(2022, 06, 07).and_hms(09, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(17, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let match_timesheet: MatchTimesheet = Match::from(timesheet.id).into();
+		let match_expense = MatchExpense {
+			timesheet_id: timesheet.id.into(),
+			..Default::default()
+		};
+
+		let match_condition: MatchJob = Match::Or(vec![job.id.into(), job2.id.into()]).into();
+		let retrieve = |include_deleted| {
+			PgJob::retrieve_with_options(&connection, &match_condition, &RetrieveOptions {
+				include_deleted,
+				..Default::default()
+			})
+		};
+
+		PgJob::soft_delete(&connection, [&job].into_iter())
+			.await
+			.unwrap();
+
+		assert_eq!(retrieve(false).await.unwrap().as_slice(), &[job2.clone()]);
+		assert_eq!(
+			PgJob::count(&connection, &match_condition).await.unwrap(),
+			1
+		);
+		assert_eq!(
+			retrieve(true)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[job.clone(), job2.clone()].into_iter().collect(),
+		);
+
+		// The timesheets of a soft deleted job, and their expenses, are soft deleted with it.
+		assert!(PgTimesheet::retrieve(&connection, &match_timesheet)
+			.await
+			.unwrap()
+			.is_empty());
+		assert!(PgExpenses::retrieve(&connection, &match_expense)
+			.await
+			.unwrap()
+			.is_empty());
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		PgJob::restore(&mut transaction, [&job].into_iter())
+			.await
+			.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgTimesheet::retrieve(&connection, &match_timesheet)
+				.await
+				.unwrap(),
+			[timesheet.clone()],
+		);
+		assert_eq!(
+			PgExpenses::retrieve(&connection, &match_expense)
+				.await
+				.unwrap(),
+			timesheet.expenses,
+		);
+
+		assert_eq!(
+			retrieve(false)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[job, job2].into_iter().collect(),
+		);
+	}
+
+	#[tokio::test]
+	async fn restore_overlap()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let employee = util::create_employee(&connection).await;
+		let (job, job2) = futures::join!(
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			Utc.ymd(2022, 06, 07).and_hms(09, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(17, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		PgJob::soft_delete(&mut transaction, [&job].into_iter())
+			.await
+			.unwrap();
+
+		// The timesheet which was soft deleted along with the `job` does not conflict with this one.
+		let timesheet2 = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job2,
+			Utc.ymd(2022, 06, 07).and_hms(12, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(13, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let mut transaction = connection.begin().await.unwrap();
+		let error = PgJob::restore(&mut transaction, [&job].into_iter())
+			.await
+			.unwrap_err();
+
+		assert_eq!(
+			error
+				.as_database_error()
+				.unwrap()
+				.downcast_ref::<TimesheetOverlap>(),
+			&TimesheetOverlap {
+				conflicting_id: timesheet2.id,
+				employee_id: employee.id,
+				timesheet_id: Some(timesheet.id),
+			},
 		);
 	}
 }
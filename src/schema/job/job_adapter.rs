@@ -1,7 +1,6 @@
 use core::time::Duration;
 
 use clinvoice_adapter::schema::JobAdapter;
-use clinvoice_finance::{ExchangeRates, Exchangeable};
 use clinvoice_schema::{
 	chrono::{DateTime, Utc},
 	Invoice,
@@ -29,16 +28,11 @@ impl JobAdapter for PgJob
 	where
 		TConn: Executor<'c, Database = Postgres>,
 	{
-		let standardized_rate = ExchangeRates::new()
-			.await
-			.map(|r| invoice.hourly_rate.exchange(Default::default(), &r))
-			.map_err(util::finance_err_to_sqlx)?;
-
 		let row = sqlx::query!(
 			"INSERT INTO jobs
-				(client_id, date_close, date_open, increment, invoice_date_issued, invoice_date_paid, invoice_hourly_rate, notes, objectives)
+				(client_id, date_close, date_open, increment, invoice_date_issued, invoice_date_paid, invoice_hourly_rate, invoice_hourly_rate_currency, notes, objectives)
 			VALUES
-				($1,        $2,         $3,        $4,        $5,                  $6,                $7,                  $8,    $9)
+				($1,        $2,         $3,        $4,        $5,                  $6,                $7,                  $8,                           $9,    $10)
 			RETURNING id;",
 			client.id,
 			date_close,
@@ -46,12 +40,23 @@ impl JobAdapter for PgJob
 			increment as _,
 			invoice.date.as_ref().map(|d| d.issued),
 			invoice.date.as_ref().and_then(|d| d.paid),
-			standardized_rate.amount.to_string() as _,
+			invoice.hourly_rate.amount,
+			invoice.hourly_rate.currency.to_string() as _,
 			notes,
 			objectives,
 		)
 		.fetch_one(connection)
-		.await?;
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"client_id" => Some(client.id.to_string()),
+				"date_close" => date_close.map(|d| d.to_string()),
+				"date_open" => Some(date_open.to_string()),
+				"invoice_date_paid" => invoice.date.as_ref().and_then(|d| d.paid).map(|d| d.to_string()),
+				_ => None,
+			})
+		})?;
 
 		Ok(Job {
 			client,
@@ -73,7 +78,6 @@ mod tests
 	use core::time::Duration;
 
 	use clinvoice_adapter::schema::{LocationAdapter, OrganizationAdapter};
-	use clinvoice_finance::{ExchangeRates, Exchangeable};
 	use clinvoice_schema::{chrono::Utc, Currency, Invoice, Money};
 	use pretty_assertions::assert_eq;
 
@@ -101,7 +105,7 @@ mod tests
 			Duration::new(7640, 0),
 			Invoice {
 				date: None,
-				hourly_rate: Money::new(13_27, 2, Currency::Usd),
+				hourly_rate: Money::new(13_27, 2, Currency::Jpy),
 			},
 			String::new(),
 			"Write the test".into(),
@@ -119,6 +123,7 @@ mod tests
 					invoice_date_issued,
 					invoice_date_paid,
 					invoice_hourly_rate,
+					invoice_hourly_rate_currency,
 					notes,
 					objectives
 				FROM jobs
@@ -139,13 +144,8 @@ mod tests
 		assert_eq!(None, row.invoice_date_issued);
 		assert_eq!(None, row.invoice_date_paid);
 		assert_eq!(
-			job.invoice
-				.hourly_rate
-				.exchange(Default::default(), &ExchangeRates::new().await.unwrap()),
-			Money {
-				amount: row.invoice_hourly_rate.parse().unwrap(),
-				..Default::default()
-			},
+			job.invoice.hourly_rate,
+			util::money_from(row.invoice_hourly_rate, &row.invoice_hourly_rate_currency).unwrap(),
 		);
 		assert_eq!(job.notes, row.notes);
 		assert_eq!(job.objectives, row.objectives);
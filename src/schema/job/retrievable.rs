@@ -1,22 +1,109 @@
 use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
-	schema::columns::{JobColumns, LocationColumns, OrganizationColumns},
+	schema::columns::{
+		ExpenseColumns,
+		JobColumns,
+		LocationColumns,
+		OrganizationColumns,
+		TimesheetColumns,
+	},
 	Retrievable,
+	WriteContext,
 	WriteWhereClause,
 };
-use clinvoice_finance::{ExchangeRates, Exchangeable};
+use clinvoice_finance::Currency;
 use clinvoice_match::MatchJob;
-use clinvoice_schema::Job;
-use futures::{TryFutureExt, TryStreamExt};
-use sqlx::{Pool, Postgres, Result};
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Job,
+};
+use futures::{future, stream::BoxStream, TryStreamExt};
+use sqlx::{postgres::PgRow, Executor, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgJob;
 use crate::{
 	fmt::PgLocationRecursiveCte,
-	schema::{util, PgLocation},
+	schema::{
+		util,
+		BillableTime,
+		Error,
+		ExchangeRatesSource,
+		OutstandingBalance,
+		PgAudit,
+		PgExchangeRates,
+		PgLocation,
+		PgOrganization,
+		PgTaxRate,
+		ReceivablesAging,
+		RetrieveOptions,
+		TaxedAmount,
+	},
 	PgSchema,
 };
 
+const COLUMNS: JobColumns<&str> = JobColumns::default();
+
+const ORGANIZATION_COLUMNS_UNIQUE: OrganizationColumns<&str> = OrganizationColumns::unique();
+
+/// The alias of the [`BillableTime::amount`] column.
+const AMOUNT: &str = "amount";
+
+/// The alias of the [`BillableTime::duration`] column.
+const DURATION: &str = "duration";
+
+/// The alias of the [`BillableTime::duration_rounded`] column.
+const DURATION_ROUNDED: &str = "duration_rounded";
+
+/// The alias of the subquery which sums the [`DURATION`].
+const DURATION_ALIAS: &str = "D";
+
+/// The alias of the subquery which computes the [`DURATION_ROUNDED`], and the [`AMOUNT`] that it
+/// is worth at the rate of each [`Timesheet`](clinvoice_schema::Timesheet).
+const RATED_ALIAS: &str = "RT";
+
+/// The alias of the subquery which sums the [`ADJUSTMENT_FIXED`] and [`ADJUSTMENT_PERCENTAGE`].
+const ADJUSTMENT_ALIAS: &str = "ADJ";
+
+/// The alias of the sum of a [`Job`]'s [`AdjustmentKind::Fixed`](crate::schema::AdjustmentKind)
+/// amounts.
+const ADJUSTMENT_FIXED: &str = "fixed";
+
+/// The alias of the sum of a [`Job`]'s
+/// [`AdjustmentKind::Percentage`](crate::schema::AdjustmentKind)s.
+const ADJUSTMENT_PERCENTAGE: &str = "percentage";
+
+/// The alias of the [`OutstandingBalance::billed`] column.
+const BILLED: &str = "billed";
+
+/// The alias of the subquery which sums the [`BILLED`] and [`PAID`] amounts.
+const BALANCE_ALIAS: &str = "B";
+
+/// The alias of the currency of the [`BILLED`], [`OUTSTANDING`], and [`PAID`] columns.
+const CURRENCY: &str = "currency";
+
+/// The alias of the [`OutstandingBalance::outstanding`] column.
+const OUTSTANDING: &str = "outstanding";
+
+/// The alias of the subquery which computes the [`AGE`] of a [`Job`]'s invoice.
+const AGE_ALIAS: &str = "G";
+
+/// The alias of the number of days since a [`Job`]'s invoice was issued.
+const AGE: &str = "days";
+
+/// The alias of the subquery which lists the amounts that a [`Job`] bills for.
+const RECEIVABLE_ALIAS: &str = "A";
+
+/// The alias of the columns of [`ReceivablesAging`], along with the [`AGE`]s which they are for.
+const AGING_BUCKETS: [(&str, &str); 4] = [
+	("days_since_issue_0_to_30", "<= 30"),
+	("days_since_issue_31_to_60", "BETWEEN 31 AND 60"),
+	("days_since_issue_61_to_90", "BETWEEN 61 AND 90"),
+	("days_since_issue_over_90", "> 90"),
+];
+
+/// The alias of the [`OutstandingBalance::paid`] column.
+const PAID: &str = "paid";
+
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
 impl Retrievable for PgJob
@@ -34,19 +121,732 @@ impl Retrievable for PgJob
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: JobColumns<&str> = JobColumns::default();
+		Self::retrieve_via(connection, match_condition, &Default::default()).await
+	}
+}
+
+impl PgJob
+{
+	/// Count the [`Job`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(connection: &Pool<Postgres>, match_condition: &MatchJob)
+		-> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(util::COUNT);
+			},
+			|_| (),
+		);
+
+		query
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Check whether any [`Job`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(util::EXISTS);
+			},
+			|_| (),
+		);
+
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// [Retrieve](Retrievable::retrieve) all [`Job`]s (via `connection`) that match the
+	/// `match_condition`, and then exchange them into the `currency` using the rates which were
+	/// effective on each [`Job`]'s `date_open`.
+	///
+	/// # See also
+	///
+	/// * [`PgExchangeRates`], where the rates that were used are stored.
+	pub async fn retrieve_exchanged<TSource>(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+		currency: Currency,
+		source: &TSource,
+	) -> Result<Vec<Job>, Error>
+	where
+		TSource: ExchangeRatesSource + ?Sized,
+	{
+		let jobs = Self::retrieve(connection, match_condition).await?;
+		PgExchangeRates::exchange(
+			connection,
+			jobs,
+			currency,
+			source,
+			|j| j.date_open.naive_utc().date(),
+			|j| vec![j.invoice.hourly_rate.currency],
+		)
+		.await
+	}
+
+	/// Retrieve the [`BillableTime`] of every [`Job`] (via `connection`) that matches the
+	/// `match_condition`.
+	///
+	/// The durations of the [`Job`]s' [`Timesheet`](clinvoice_schema::Timesheet)s are summed,
+	/// rounded, and multiplied by the hourly rate by the database, so that none of the
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s have to be retrieved. [`Job`]s which have no
+	/// time worked are included, with a [`BillableTime`] of zero.
+	pub async fn retrieve_billable_time(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+	) -> Result<Vec<BillableTime>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(columns.id)
+					.push(',')
+					.push(columns.invoice_hourly_rate)
+					.push(',')
+					.push(util::currency_column(columns.invoice_hourly_rate))
+					.push(',')
+					.push(DURATION_ALIAS)
+					.push('.')
+					.push(DURATION)
+					.push(',')
+					.push(RATED_ALIAS)
+					.push('.')
+					.push(DURATION_ROUNDED)
+					.push(',')
+					.push(Self::billable_amount())
+					.push(sql::AS)
+					.push(AMOUNT);
+			},
+			Self::push_billable_time_join,
+		);
+
+		query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(Self::row_to_billable_time(&row)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Retrieve the [`TaxedAmount`] of every [`Job`] (via `connection`) that matches the
+	/// `match_condition`.
+	///
+	/// The `net` amount is the [`BillableTime::amount`], which is taxed by every
+	/// [`TaxRate`](crate::schema::TaxRate) attached to the [`Job`]. The
+	/// [`Expense`](clinvoice_schema::Expense)s of the [`Job`] are not included; see
+	/// [`PgExpenses::retrieve_taxed_amounts`](crate::schema::PgExpenses::retrieve_taxed_amounts).
+	pub async fn retrieve_taxed_amounts(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+	) -> Result<Vec<TaxedAmount>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(columns.id).push(',');
+				PgTaxRate::push_taxed_amount_columns(
+					q,
+					Self::billable_amount(),
+					util::currency_column(columns.invoice_hourly_rate),
+				);
+			},
+			|q| {
+				Self::push_billable_time_join(q);
+				PgTaxRate::push_percentage_join(q, "job_tax_rates", "job_id", columns.id);
+			},
+		);
+
+		query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgTaxRate::row_to_taxed_amount(&row, COLUMNS.id)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Retrieve the [`OutstandingBalance`]s of every [`Job`] (via `connection`) that matches the
+	/// `match_condition`.
+	///
+	/// The lines of each [`Job`]'s [`IssuedInvoice`](crate::schema::IssuedInvoice)s and its
+	/// [`Payment`](crate::schema::Payment)s are summed by the database. [`Job`]s which were neither
+	/// billed nor paid for have no [`OutstandingBalance`]s.
+	pub async fn retrieve_outstanding_balances(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+	) -> Result<Vec<OutstandingBalance>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+
+		let (mut query, _) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				let mut separated = q.separated(',');
+				separated.push(columns.id);
+				[CURRENCY, BILLED, PAID].into_iter().for_each(|c| {
+					separated
+						.push(BALANCE_ALIAS)
+						.push_unseparated('.')
+						.push_unseparated(c);
+				});
+
+				q.push(',')
+					.push(BALANCE_ALIAS)
+					.push('.')
+					.push(BILLED)
+					.push('-')
+					.push(BALANCE_ALIAS)
+					.push('.')
+					.push(PAID)
+					.push(sql::AS)
+					.push(OUTSTANDING);
+			},
+			|q| {
+				q.push(" CROSS JOIN LATERAL (SELECT A.")
+					.push(CURRENCY)
+					.push(",sum(A.")
+					.push(BILLED)
+					.push(')')
+					.push(sql::AS)
+					.push(BILLED)
+					.push(",sum(A.")
+					.push(PAID)
+					.push(')')
+					.push(sql::AS)
+					.push(PAID)
+					.push(
+						" FROM (SELECT IL.amount_currency, IL.amount, 0 FROM invoice_lines IL JOIN \
+						 invoices I ON (I.id = IL.invoice_id) WHERE I.job_id = ",
+					)
+					.push(columns.id)
+					.push(
+						" UNION ALL SELECT P.amount_currency, 0, P.amount FROM payments P WHERE \
+						 P.job_id = ",
+					)
+					.push(columns.id)
+					.push(") AS A (")
+					.push(CURRENCY)
+					.push(',')
+					.push(BILLED)
+					.push(',')
+					.push(PAID)
+					.push(')')
+					.push(sql::GROUP_BY)
+					.push("A.")
+					.push(CURRENCY)
+					.push(')')
+					.push(sql::AS)
+					.push(BALANCE_ALIAS);
+			},
+		);
+
+		query
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(Self::row_to_outstanding_balance(&row)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Retrieve the [`ReceivablesAging`] of every client whose [`Job`]s (via `connection`) match the
+	/// `match_condition`, `as_of` the given date.
+	///
+	/// A [`Job`] is owed for when its invoice was issued on or before `as_of`, and it was not paid
+	/// by then. The amount owed is its [`BillableTime`] plus the cost of its
+	/// [`Expense`](clinvoice_schema::Expense)s, less the [`Payment`](crate::schema::Payment)s which
+	/// were received by `as_of`. It is placed into a bucket according to the number of whole days
+	/// between the invoice being issued and `as_of`.
+	pub async fn retrieve_receivables_aging(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+		as_of: DateTime<Utc>,
+	) -> Result<Vec<ReceivablesAging>, Error>
+	{
+		let columns = COLUMNS.default_scope();
+		let expense_columns = ExpenseColumns::default().default_scope();
+		let timesheet_columns = TimesheetColumns::default().default_scope();
+
+		let (mut query, context) = Self::select_matching(
+			match_condition,
+			false,
+			None,
+			|q| {
+				q.push(columns.client_id)
+					.push(',')
+					.push(RECEIVABLE_ALIAS)
+					.push('.')
+					.push(CURRENCY);
+
+				AGING_BUCKETS.into_iter().for_each(|(alias, days)| {
+					q.push(",COALESCE(sum(")
+						.push(RECEIVABLE_ALIAS)
+						.push('.')
+						.push(AMOUNT)
+						.push(") FILTER (WHERE ")
+						.push(AGE_ALIAS)
+						.push('.')
+						.push(AGE)
+						.push(' ')
+						.push(days)
+						.push("), 0)")
+						.push(sql::AS)
+						.push(alias);
+				});
+			},
+			|q| {
+				q.push(" CROSS JOIN LATERAL (SELECT extract(DAY FROM ")
+					.push_bind(as_of)
+					.push('-')
+					.push(columns.invoice_date_issued)
+					.push(')')
+					.push(sql::AS)
+					.push(AGE)
+					.push(')')
+					.push(sql::AS)
+					.push(AGE_ALIAS);
+
+				Self::push_billable_time_join(q);
+
+				// NOTE: this is the same amount as `retrieve_billable_time`, plus the expenses, less
+				//       the payments which were received by `as_of`.
+				q.push(" CROSS JOIN LATERAL (SELECT ")
+					.push(util::currency_column(columns.invoice_hourly_rate))
+					.push(',')
+					.push(Self::billable_amount())
+					.push(" UNION ALL SELECT ")
+					.push(util::currency_column(expense_columns.cost))
+					.push(',')
+					.push(expense_columns.cost)
+					.push_default_from::<ExpenseColumns<char>>()
+					.push_default_equijoin::<TimesheetColumns<char>, _, _>(
+						timesheet_columns.id,
+						expense_columns.timesheet_id,
+					)
+					.push(sql::WHERE)
+					.push(timesheet_columns.job_id)
+					.push('=')
+					.push(columns.id)
+					.push(sql::AND)
+					.push(ExpenseColumns::<char>::DEFAULT_ALIAS)
+					.push('.')
+					.push(util::DELETED_AT)
+					.push(sql::IS)
+					.push(sql::NULL)
+					.push(" UNION ALL SELECT P.amount_currency, -P.amount FROM payments P")
+					.push(" WHERE P.job_id = ")
+					.push(columns.id)
+					.push(" AND P.date <= ")
+					.push_bind(as_of)
+					.push(") AS ")
+					.push(RECEIVABLE_ALIAS)
+					.push(" (")
+					.push(CURRENCY)
+					.push(',')
+					.push(AMOUNT)
+					.push(')');
+			},
+		);
+
+		query
+			.push(context)
+			.push(columns.invoice_date_issued)
+			.push(" <= ")
+			.push_bind(as_of)
+			.push(sql::AND)
+			.push('(')
+			.push(columns.invoice_date_paid)
+			.push(sql::IS)
+			.push(sql::NULL)
+			.push(sql::OR)
+			.push(columns.invoice_date_paid)
+			.push(" > ")
+			.push_bind(as_of)
+			.push(')')
+			.push(sql::GROUP_BY)
+			.push(columns.client_id)
+			.push(',')
+			.push(RECEIVABLE_ALIAS)
+			.push('.')
+			.push(CURRENCY)
+			.push(" HAVING sum(")
+			.push(RECEIVABLE_ALIAS)
+			.push('.')
+			.push(AMOUNT)
+			.push(") <> 0")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(Self::row_to_receivables_aging(&row)))
+			.try_collect()
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Create a [`ReceivablesAging`] from some `row` which [`PgJob::retrieve_receivables_aging`]
+	/// selected.
+	fn row_to_receivables_aging(row: &PgRow) -> Result<ReceivablesAging>
+	{
+		let currency = row.try_get::<String, _>(CURRENCY)?;
+		let money = |column| {
+			row.try_get(column)
+				.and_then(|a| util::money_from(a, &currency))
+		};
+
+		Ok(ReceivablesAging {
+			client_id: row.try_get(COLUMNS.client_id)?,
+			days_since_issue_0_to_30: money(AGING_BUCKETS[0].0)?,
+			days_since_issue_31_to_60: money(AGING_BUCKETS[1].0)?,
+			days_since_issue_61_to_90: money(AGING_BUCKETS[2].0)?,
+			days_since_issue_over_90: money(AGING_BUCKETS[3].0)?,
+		})
+	}
+
+	/// Create an [`OutstandingBalance`] from some `row` which
+	/// [`PgJob::retrieve_outstanding_balances`] selected.
+	fn row_to_outstanding_balance(row: &PgRow) -> Result<OutstandingBalance>
+	{
+		let currency = row.try_get::<String, _>(CURRENCY)?;
+		let money = |column| {
+			row.try_get(column)
+				.and_then(|a| util::money_from(a, &currency))
+		};
+
+		Ok(OutstandingBalance {
+			billed: money(BILLED)?,
+			job_id: row.try_get(COLUMNS.id)?,
+			outstanding: money(OUTSTANDING)?,
+			paid: money(PAID)?,
+		})
+	}
+
+	/// Append joins to the `query` which sum the [`DURATION`] of each [`Job`]'s
+	/// [`Timesheet`](clinvoice_schema::Timesheet)s, round it to the [`DURATION_ROUNDED`], find what
+	/// it is worth at the [`RATED_ALIAS`], and sum its [`Adjustment`](crate::schema::Adjustment)s.
+	///
+	/// The time billed at each [`BillingRate`](crate::schema::BillingRate) is rounded separately,
+	/// and the [`DURATION_ROUNDED`] is the sum of those rounded times, so that it is always the
+	/// time which the [`AMOUNT`] is billed for.
+	///
+	/// # See also
+	///
+	/// * [`PgJob::billable_amount`], which is the amount of [`Money`](clinvoice_finance::Money) that
+	///   the [`DURATION_ROUNDED`] is worth.
+	fn push_billable_time_join(query: &mut QueryBuilder<Postgres>)
+	{
+		let columns = COLUMNS.default_scope();
+		let timesheet_columns = TimesheetColumns::default().default_scope();
+
+		// NOTE: `Timesheet`s without a `time_end` are still being worked on, and so they are not
+		//       billable yet. Soft deleted `Timesheet`s are never billable.
+		query
+			.push(" CROSS JOIN LATERAL (SELECT COALESCE(sum(")
+			.push(timesheet_columns.time_end)
+			.push('-')
+			.push(timesheet_columns.time_begin)
+			.push("), interval '0')")
+			.push(sql::AS)
+			.push(DURATION)
+			.push_default_from::<TimesheetColumns<char>>()
+			.push(sql::WHERE)
+			.push(timesheet_columns.job_id)
+			.push('=')
+			.push(columns.id)
+			.push(sql::AND)
+			.push(timesheet_columns.time_end)
+			.push(sql::IS)
+			.push(sql::NOT)
+			.push(sql::NULL)
+			.push(sql::AND)
+			.push(TimesheetColumns::<char>::DEFAULT_ALIAS)
+			.push('.')
+			.push(util::DELETED_AT)
+			.push(sql::IS)
+			.push(sql::NULL)
+			.push(')')
+			.push(sql::AS)
+			.push(DURATION_ALIAS);
+
+		query
+			.push(" CROSS JOIN LATERAL (SELECT COALESCE(sum(G.")
+			.push(DURATION_ROUNDED)
+			.push("), interval '0')")
+			.push(sql::AS)
+			.push(DURATION_ROUNDED)
+			.push(",COALESCE(sum(extract(EPOCH FROM G.")
+			.push(DURATION_ROUNDED)
+			.push(")::numeric / 3600 * G.hourly_rate), 0)")
+			.push(sql::AS)
+			.push(AMOUNT)
+			.push(" FROM (SELECT TR.hourly_rate, round_to_increment(sum(")
+			.push(timesheet_columns.time_end)
+			.push('-')
+			.push(timesheet_columns.time_begin)
+			.push("),")
+			.push(columns.increment)
+			.push(')')
+			.push(sql::AS)
+			.push(DURATION_ROUNDED)
+			.push_default_from::<TimesheetColumns<char>>()
+			.push(" JOIN timesheet_rates TR ON (TR.timesheet_id = ")
+			.push(timesheet_columns.id)
+			.push(')')
+			.push(sql::WHERE)
+			.push(timesheet_columns.job_id)
+			.push('=')
+			.push(columns.id)
+			.push(sql::AND)
+			.push(timesheet_columns.time_end)
+			.push(sql::IS)
+			.push(sql::NOT)
+			.push(sql::NULL)
+			.push(sql::AND)
+			.push(TimesheetColumns::<char>::DEFAULT_ALIAS)
+			.push('.')
+			.push(util::DELETED_AT)
+			.push(sql::IS)
+			.push(sql::NULL)
+			.push(sql::GROUP_BY)
+			.push("TR.hourly_rate) G)")
+			.push(sql::AS)
+			.push(RATED_ALIAS);
+
+		query
+			.push(" CROSS JOIN LATERAL (SELECT COALESCE(sum(JA.amount), 0)")
+			.push(sql::AS)
+			.push(ADJUSTMENT_FIXED)
+			.push(",COALESCE(sum(JA.percentage), 0)")
+			.push(sql::AS)
+			.push(ADJUSTMENT_PERCENTAGE)
+			.push(" FROM job_adjustments JA WHERE JA.job_id = ")
+			.push(columns.id)
+			.push(')')
+			.push(sql::AS)
+			.push(ADJUSTMENT_ALIAS);
+	}
+
+	/// The amount of [`Money`](clinvoice_finance::Money) which the time worked on a [`Job`] is
+	/// worth after its [`Adjustment`](crate::schema::Adjustment)s, following the
+	/// [`PgJob::push_billable_time_join`].
+	///
+	/// The percentage [`Adjustment`](crate::schema::Adjustment)s are rounded to two decimal
+	/// places, the same as they are on an [`IssuedInvoice`](crate::schema::IssuedInvoice).
+	fn billable_amount() -> String
+	{
+		let time = format!("{RATED_ALIAS}.{AMOUNT}");
+
+		format!(
+			"{time} + round({time} * {ADJUSTMENT_ALIAS}.{ADJUSTMENT_PERCENTAGE} / 100, 2) + \
+			 {ADJUSTMENT_ALIAS}.{ADJUSTMENT_FIXED}"
+		)
+	}
+
+	/// Create a [`BillableTime`] from some `row` which [`PgJob::retrieve_billable_time`] selected.
+	fn row_to_billable_time(row: &PgRow) -> Result<BillableTime>
+	{
+		let currency = row.try_get::<String, _>(
+			util::currency_column(COLUMNS.invoice_hourly_rate)
+				.to_string()
+				.as_str(),
+		)?;
+
+		Ok(BillableTime {
+			amount: row
+				.try_get(AMOUNT)
+				.and_then(|a| util::money_from(a, &currency))?,
+			duration: row.try_get(DURATION).and_then(util::duration_from)?,
+			duration_rounded: row
+				.try_get(DURATION_ROUNDED)
+				.and_then(util::duration_from)?,
+			job_id: row.try_get(COLUMNS.id)?,
+		})
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Job`]s are retrieved as they
+	/// were `as_of` some time (via `connection`), rather than as they are now.
+	///
+	/// # See also
+	///
+	/// * [`RetrieveOptions::as_of`], to sort and paginate them as well.
+	pub async fn retrieve_as_of(
+		connection: &Pool<Postgres>,
+		as_of: DateTime<Utc>,
+		match_condition: &MatchJob,
+	) -> Result<Vec<Job>, Error>
+	{
+		Self::retrieve_with_options(connection, match_condition, &RetrieveOptions {
+			as_of: Some(as_of),
+			..Default::default()
+		})
+		.await
+	}
+
+	/// Generate the query which selects every [`Job`] that matches the `match_condition`,
+	/// according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchJob,
+		options: &RetrieveOptions<JobColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) = Self::select_matching(
+			match_condition,
+			options.include_deleted,
+			options.as_of,
+			|q| {
+				let columns = COLUMNS.default_scope();
+				q.push_columns(&columns)
+					.push(',')
+					.push(util::currency_column(columns.invoice_hourly_rate))
+					.push_more_columns(
+						&OrganizationColumns::default()
+							.default_scope()
+							.r#as(ORGANIZATION_COLUMNS_UNIQUE),
+					);
+			},
+			|_| (),
+		);
+
+		options.write_where(
+			context,
+			COLUMNS,
+			JobColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			JobColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		);
+
+		Ok(query)
+	}
+
+	/// Same as [`PgJob::retrieve_with_options`], except that the [`Job`]s are yielded as they are
+	/// retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchJob,
+		options: &'a RetrieveOptions<JobColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Job, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			move |rows| async move { Self::rows_to_views(connection, &rows).await },
+		)
+	}
+
+	/// Same as [`PgJob::retrieve_with_options`], except that any `connection` which can be used
+	/// more than once will do.
+	///
+	/// No matter how many [`Job`]s match, they are retrieved in a fixed number of queries.
+	async fn retrieve_via<'c, TConn>(
+		connection: TConn,
+		match_condition: &MatchJob,
+		options: &RetrieveOptions<JobColumns<&'static str>>,
+	) -> Result<Vec<Job>>
+	where
+		TConn: Copy + Executor<'c, Database = Postgres>,
+	{
+		let rows = Self::retrieve_query(match_condition, options)?
+			.prepare()
+			.fetch_all(connection)
+			.await?;
+
+		Self::rows_to_views(connection, &rows).await
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Job`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchJob,
+		options: &RetrieveOptions<JobColumns<&'static str>>,
+	) -> Result<Vec<Job>, Error>
+	{
+		Self::retrieve_via(connection, match_condition, options)
+			.await
+			.map_err(Error::from)
+	}
 
-		const ORGANIZATION_COLUMNS_UNIQUE: OrganizationColumns<&str> = OrganizationColumns::unique();
+	/// Convert the `rows` which the [`PgJob::retrieve_query`] selected into [`Job`]s, retrieving
+	/// their client's [`Location`](clinvoice_schema::Location)s via `connection`.
+	async fn rows_to_views<'c, TConn>(connection: TConn, rows: &[PgRow]) -> Result<Vec<Job>>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		PgLocation::rows_to_views(
+			connection,
+			rows,
+			|row| PgOrganization::location_id_of(&ORGANIZATION_COLUMNS_UNIQUE, row).map(Some),
+			|locations, row| Self::row_to_view(COLUMNS, ORGANIZATION_COLUMNS_UNIQUE, locations, row),
+		)
+		.await
+	}
 
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Job`] that matches
+	/// the `match_condition`, after any extra tables have been `join`ed.
+	///
+	/// [Soft deleted](crate::schema::SoftDeletable) [`Job`]s are excluded, unless `include_deleted`
+	/// is `true`. The [`WriteContext`] of the `WHERE` clause is returned so that more conditions can
+	/// be added.
+	///
+	/// When `as_of` is given, the [`AuditedTable`](crate::schema::AuditedTable)s are selected from
+	/// as they were at that time.
+	fn select_matching<'a, TJoin, TSelect>(
+		match_condition: &'a MatchJob,
+		include_deleted: bool,
+		as_of: Option<DateTime<Utc>>,
+		select: TSelect,
+		join: TJoin,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TJoin: FnOnce(&mut QueryBuilder<'a, Postgres>),
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
 		let columns = COLUMNS.default_scope();
-		let exchange_rates_fut = ExchangeRates::new().map_err(util::finance_err_to_sqlx);
 		let mut query = PgLocation::query_with_recursive(&match_condition.client.location);
+		if let Some(as_of) = as_of
+		{
+			query.push(',');
+			PgAudit::push_as_of(&mut query, as_of);
+		}
+
 		let organization_columns = OrganizationColumns::default().default_scope();
 
+		query.push(sql::SELECT);
+		select(&mut query);
 		query
-			.push(sql::SELECT)
-			.push_columns(&columns)
-			.push_more_columns(&organization_columns.r#as(ORGANIZATION_COLUMNS_UNIQUE))
 			.push_default_from::<JobColumns<char>>()
 			.push_default_equijoin::<OrganizationColumns<char>, _, _>(
 				organization_columns.id,
@@ -59,11 +859,13 @@ impl Retrievable for PgJob
 				organization_columns.location_id,
 			);
 
-		PgSchema::write_where_clause(
+		join(&mut query);
+
+		let context = PgSchema::write_where_clause(
 			PgSchema::write_where_clause(
 				Default::default(),
 				JobColumns::<char>::DEFAULT_ALIAS,
-				&match_condition.exchange_ref(Default::default(), &exchange_rates_fut.await?),
+				match_condition,
 				&mut query,
 			),
 			OrganizationColumns::<char>::DEFAULT_ALIAS,
@@ -71,14 +873,14 @@ impl Retrievable for PgJob
 			&mut query,
 		);
 
-		query
-			.prepare()
-			.fetch(connection)
-			.and_then(|row| async move {
-				PgJob::row_to_view(connection, COLUMNS, ORGANIZATION_COLUMNS_UNIQUE, &row).await
-			})
-			.try_collect()
-			.await
+		let context = util::write_not_deleted(
+			context,
+			JobColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
+
+		(query, context)
 	}
 }
 
@@ -89,21 +891,56 @@ mod tests
 	use std::collections::HashSet;
 
 	use clinvoice_adapter::{
-		schema::{JobAdapter, LocationAdapter, OrganizationAdapter},
+		schema::{
+			EmployeeAdapter,
+			JobAdapter,
+			LocationAdapter,
+			OrganizationAdapter,
+			TimesheetAdapter,
+		},
+		Deletable,
 		Retrievable,
+		Updatable,
 	};
-	use clinvoice_finance::{ExchangeRates, Exchangeable};
-	use clinvoice_match::{Match, MatchInvoice, MatchJob, MatchOption};
+	use clinvoice_finance::{Decimal, ExchangeRates, Exchangeable};
+	use clinvoice_match::{Match, MatchInvoice, MatchJob, MatchOption, MatchOrganization};
 	use clinvoice_schema::{
-		chrono::{TimeZone, Utc},
+		chrono::{NaiveDate, TimeZone, Utc},
 		Currency,
 		Invoice,
 		InvoiceDate,
 		Money,
 	};
+	use futures::TryStreamExt;
 	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgJob, PgLocation, PgOrganization};
+	use crate::schema::{
+		util,
+		AdjustmentKind,
+		AuditedTable,
+		BillableTime,
+		BillingRateTarget,
+		Order,
+		OrderBy,
+		OutstandingBalance,
+		Page,
+		PgAdjustment,
+		PgAudit,
+		PgBillingRate,
+		PgEmployee,
+		PgExchangeRates,
+		PgInvoice,
+		PgJob,
+		PgLocation,
+		PgOrganization,
+		PgPayment,
+		PgTaxRate,
+		PgTimesheet,
+		ReceivablesAging,
+		RetrieveOptions,
+		SortValue,
+		TaxedAmount,
+	};
 
 	#[tokio::test]
 	async fn retrieve()
@@ -192,16 +1029,50 @@ mod tests
 		)
 		.unwrap();
 
-		let exchange_rates = ExchangeRates::new().await.unwrap();
-
 		assert_eq!(
 			PgJob::retrieve(&connection, &job.id.into())
 				.await
 				.unwrap()
 				.as_slice(),
-			&[job.exchange_ref(Default::default(), &exchange_rates)],
+			&[job.clone()],
+		);
+
+		let match_all = MatchJob {
+			id: Match::Or(vec![
+				job.id.into(),
+				job2.id.into(),
+				job3.id.into(),
+				job4.id.into(),
+			]),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgJob::retrieve_stream(&connection, &match_all, &Default::default())
+				.try_collect::<HashSet<_>>()
+				.await
+				.unwrap(),
+			PgJob::retrieve(&connection, &match_all)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
 		);
 
+		assert_eq!(PgJob::count(&connection, &match_all).await.unwrap(), 4);
+		assert!(PgJob::exists(&connection, &match_all).await.unwrap());
+
+		let match_none = MatchJob {
+			invoice: MatchInvoice {
+				date_issued: MatchOption::some(),
+				..Default::default()
+			},
+			..MatchJob::from(job.id)
+		};
+
+		assert_eq!(PgJob::count(&connection, &match_none).await.unwrap(), 0);
+		assert!(!PgJob::exists(&connection, &match_none).await.unwrap());
+
 		assert_eq!(
 			PgJob::retrieve(&connection, &MatchJob {
 				id: Match::Or(vec![job2.id.into(), job3.id.into()]),
@@ -215,12 +1086,7 @@ mod tests
 			.unwrap()
 			.into_iter()
 			.collect::<HashSet<_>>(),
-			[
-				job2.exchange(Default::default(), &exchange_rates),
-				job3.exchange(Default::default(), &exchange_rates),
-			]
-			.into_iter()
-			.collect::<HashSet<_>>(),
+			[job2, job3].into_iter().collect::<HashSet<_>>(),
 		);
 
 		assert_eq!(
@@ -236,12 +1102,889 @@ mod tests
 			.unwrap()
 			.into_iter()
 			.collect::<HashSet<_>>(),
+			[job, job4].into_iter().collect::<HashSet<_>>(),
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_billable_time()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let employee = util::create_employee(&connection).await;
+		let job = util::create_job(
+			&connection,
+			organization.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+		let job2 = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(0),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(15_00, 2, Currency::Eur),
+			},
+			String::new(),
+			"Do something else".into(),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		for (time_begin, time_end) in [
+			(
+				Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+				Some(Utc.ymd(1990, 07, 12).and_hms(16, 05, 00)),
+			),
+			(
+				Utc.ymd(1990, 07, 13).and_hms(09, 00, 00),
+				Some(Utc.ymd(1990, 07, 13).and_hms(09, 20, 00)),
+			),
+			// still being worked on, so it is not billable
+			(Utc.ymd(1990, 07, 14).and_hms(09, 00, 00), None),
+		]
+		{
+			PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				job.clone(),
+				time_begin,
+				time_end,
+				String::new(),
+			)
+			.await
+			.unwrap();
+		}
+
+		for (kind, reason) in [
+			(
+				AdjustmentKind::Percentage(Decimal::new(-10, 0)),
+				"Loyalty discount",
+			),
+			(
+				AdjustmentKind::Fixed(Money::new(-5_00, 2, Currency::Usd)),
+				"Goodwill credit",
+			),
+		]
+		{
+			PgAdjustment::create(&mut transaction, &job, kind, reason.into())
+				.await
+				.unwrap();
+		}
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgJob::retrieve_billable_time(&connection, &MatchJob {
+				id: Match::Or(vec![job.id.into(), job2.id.into()]),
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
 			[
-				job.exchange(Default::default(), &exchange_rates),
-				job4.exchange(Default::default(), &exchange_rates),
+				BillableTime {
+					// 1h25m, rounded to the nearest 15m, at $20/h, less 10% and $5
+					amount: Money::new(22_00, 2, Currency::Usd),
+					duration: Duration::from_secs(5100),
+					duration_rounded: Duration::from_secs(5400),
+					job_id: job.id,
+				},
+				BillableTime {
+					amount: Money::new(0, 2, Currency::Eur),
+					duration: Duration::ZERO,
+					duration_rounded: Duration::ZERO,
+					job_id: job2.id,
+				},
 			]
 			.into_iter()
 			.collect::<HashSet<_>>(),
 		);
 	}
+
+	#[tokio::test]
+	async fn retrieve_billable_time_rated()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		let senior = PgEmployee::create(
+			&connection,
+			"My Name".into(),
+			"Employed".into(),
+			"Senior Engineer".into(),
+		)
+		.await
+		.unwrap();
+
+		PgBillingRate::create(
+			&connection,
+			&job,
+			BillingRateTarget::Employee(senior.id),
+			Money::new(30_00, 2, Currency::Usd),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		for e in [employee, senior]
+		{
+			PgTimesheet::create(
+				&mut transaction,
+				e,
+				Vec::new(),
+				job.clone(),
+				Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+				Some(Utc.ymd(1990, 07, 12).and_hms(15, 10, 00)),
+				String::new(),
+			)
+			.await
+			.unwrap();
+		}
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgJob::retrieve_billable_time(&connection, &job.id.into())
+				.await
+				.unwrap(),
+			[BillableTime {
+				// 10m at $20/h and 10m at $30/h, each rounded to the nearest 15m
+				amount: Money::new(12_50, 2, Currency::Usd),
+				duration: Duration::from_secs(1200),
+				duration_rounded: Duration::from_secs(1800),
+				job_id: job.id,
+			}],
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_outstanding_balances()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job, job2) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![(
+				"Flight".into(),
+				Money::new(300_56, 2, Currency::Usd),
+				"Trip to Hawaii for research".into(),
+			)],
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let invoice = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(1990, 08, 01).and_hms(09, 00, 00),
+			&[timesheet.clone()],
+			&timesheet.expenses,
+			&[],
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		futures::try_join!(
+			PgPayment::create(
+				&connection,
+				&job,
+				Some(&invoice),
+				Money::new(100_00, 2, Currency::Usd),
+				Utc.ymd(1990, 08, 15).and_hms(09, 00, 00),
+				"Check".into(),
+				"#1234".into(),
+			),
+			PgPayment::create(
+				&connection,
+				&job,
+				None,
+				Money::new(50_00, 2, Currency::Usd),
+				Utc.ymd(1990, 09, 15).and_hms(09, 00, 00),
+				"Cash".into(),
+				String::new(),
+			),
+			PgPayment::create(
+				&connection,
+				&job,
+				None,
+				Money::new(10_00, 2, Currency::Eur),
+				Utc.ymd(1990, 09, 16).and_hms(09, 00, 00),
+				"Cash".into(),
+				String::new(),
+			),
+		)
+		.unwrap();
+
+		assert_eq!(
+			PgJob::retrieve_outstanding_balances(&connection, &MatchJob {
+				id: Match::Or(vec![job.id.into(), job2.id.into()]),
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
+			[
+				OutstandingBalance {
+					// $20 for the hour worked, and the flight
+					billed: Money::new(320_56, 2, Currency::Usd),
+					job_id: job.id,
+					outstanding: Money::new(170_56, 2, Currency::Usd),
+					paid: Money::new(150_00, 2, Currency::Usd),
+				},
+				OutstandingBalance {
+					billed: Money::new(0, 2, Currency::Eur),
+					job_id: job.id,
+					outstanding: Money::new(-10_00, 2, Currency::Eur),
+					paid: Money::new(10_00, 2, Currency::Eur),
+				},
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_receivables_aging()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let employee = util::create_employee(&connection).await;
+		let organization = PgOrganization::create(&connection, earth, "Some Organization".into())
+			.await
+			.unwrap();
+
+		let as_of = Utc.ymd(1990, 12, 31).and_hms(12, 00, 00);
+		let job_invoiced = |issued, paid| Invoice {
+			date: Some(InvoiceDate { issued, paid }),
+			hourly_rate: Money::new(20_00, 2, Currency::Usd),
+		};
+
+		let (job, job2, job3, job4) = futures::try_join!(
+			PgJob::create(
+				&connection,
+				organization.clone(),
+				None,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Duration::from_secs(900),
+				job_invoiced(Utc.ymd(1990, 11, 15).and_hms(12, 00, 00), None),
+				String::new(),
+				"Invoiced 46 days ago".into()
+			),
+			PgJob::create(
+				&connection,
+				organization.clone(),
+				None,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Duration::from_secs(900),
+				job_invoiced(Utc.ymd(1990, 12, 21).and_hms(12, 00, 00), None),
+				String::new(),
+				"Invoiced 10 days ago".into()
+			),
+			PgJob::create(
+				&connection,
+				organization.clone(),
+				None,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Duration::from_secs(900),
+				job_invoiced(
+					Utc.ymd(1990, 08, 01).and_hms(12, 00, 00),
+					Some(Utc.ymd(1990, 08, 15).and_hms(12, 00, 00))
+				),
+				String::new(),
+				"Paid before the report".into()
+			),
+			PgJob::create(
+				&connection,
+				organization.clone(),
+				None,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Duration::from_secs(900),
+				job_invoiced(
+					Utc.ymd(1990, 08, 01).and_hms(12, 00, 00),
+					Some(Utc.ymd(1991, 01, 15).and_hms(12, 00, 00))
+				),
+				String::new(),
+				"Paid after the report".into()
+			),
+		)
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		for (j, day) in [(&job, 10), (&job3, 11), (&job4, 12)]
+		{
+			PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				j.clone(),
+				Utc.ymd(1990, 07, day).and_hms(15, 00, 00),
+				Some(Utc.ymd(1990, 07, day).and_hms(16, 05, 00)),
+				String::new(),
+			)
+			.await
+			.unwrap();
+		}
+
+		// NOTE: not finished, so only the expense is owed.
+		PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![(
+				"Flight".into(),
+				Money::new(300_56, 2, Currency::Usd),
+				"Trip to Hawaii for research".into(),
+			)],
+			job2,
+			Utc.ymd(1990, 07, 13).and_hms(09, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		// NOTE: only the payment which was received by `as_of` is deducted.
+		futures::try_join!(
+			PgPayment::create(
+				&connection,
+				&job4,
+				None,
+				Money::new(5_00, 2, Currency::Usd),
+				Utc.ymd(1990, 12, 01).and_hms(09, 00, 00),
+				"Check".into(),
+				"#1234".into(),
+			),
+			PgPayment::create(
+				&connection,
+				&job4,
+				None,
+				Money::new(10_00, 2, Currency::Usd),
+				Utc.ymd(1991, 01, 02).and_hms(09, 00, 00),
+				"Check".into(),
+				"#1235".into(),
+			),
+		)
+		.unwrap();
+
+		assert_eq!(
+			PgJob::retrieve_receivables_aging(
+				&connection,
+				&MatchJob {
+					client: MatchOrganization {
+						id: organization.id.into(),
+						..Default::default()
+					},
+					..Default::default()
+				},
+				as_of,
+			)
+			.await
+			.unwrap(),
+			[ReceivablesAging {
+				client_id: organization.id,
+				days_since_issue_0_to_30: Money::new(300_56, 2, Currency::Usd),
+				// 1h05m, rounded to the nearest 15m, at $20/h
+				days_since_issue_31_to_60: Money::new(20_00, 2, Currency::Usd),
+				days_since_issue_61_to_90: Money::new(0, 2, Currency::Usd),
+				// less a partial payment of $5
+				days_since_issue_over_90: Money::new(15_00, 2, Currency::Usd),
+			}],
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_round_trips()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let mut ids = Vec::new();
+		let mut round_trips = Vec::new();
+
+		for i in 0..5
+		{
+			let location = PgLocation::create(&connection, format!("City {i}"), Some(earth.clone()))
+				.await
+				.unwrap();
+
+			let organization =
+				PgOrganization::create(&connection, location, format!("Organization {i}"))
+					.await
+					.unwrap();
+
+			let job = util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			)
+			.await;
+
+			ids.push(job.id.into());
+
+			let counter = util::RoundTrips::new(&connection);
+			let retrieved = PgJob::retrieve_via(
+				&counter,
+				&MatchJob {
+					id: Match::Or(ids.clone()),
+					..Default::default()
+				},
+				&Default::default(),
+			)
+			.await
+			.unwrap();
+
+			assert_eq!(retrieved.len(), ids.len());
+			round_trips.push(counter.count());
+		}
+
+		// The number of round-trips must not grow with the number of rows retrieved.
+		assert!(
+			round_trips.windows(2).all(|w| w[0] == w[1]),
+			"{round_trips:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_taxed_amounts()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let earth = organization.location.clone();
+
+		let invoice = Invoice {
+			date: None,
+			hourly_rate: Money::new(20_00, 2, Currency::Usd),
+		};
+
+		let employee = util::create_employee(&connection).await;
+		let job = PgJob::create(
+			&connection,
+			organization.clone(),
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			invoice.clone(),
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+		let job2 = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			invoice,
+			String::new(),
+			"Do something tax-free".into(),
+		)
+		.await
+		.unwrap();
+		let vat = PgTaxRate::create(&connection, "VAT".into(), Decimal::new(20, 0), Some(&earth))
+			.await
+			.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		for (j, day) in [(&job, 11), (&job2, 12)]
+		{
+			PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				j.clone(),
+				Utc.ymd(1990, 07, day).and_hms(15, 00, 00),
+				Some(Utc.ymd(1990, 07, day).and_hms(16, 05, 00)),
+				String::new(),
+			)
+			.await
+			.unwrap();
+		}
+
+		PgTaxRate::attach_to_job(&mut transaction, &job, &[vat.clone()])
+			.await
+			.unwrap();
+
+		// Attaching the same tax rate again should be harmless.
+		PgTaxRate::attach_to_job(&mut transaction, &job, &[vat])
+			.await
+			.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgJob::retrieve_taxed_amounts(&connection, &MatchJob {
+				id: Match::Or(vec![job.id.into(), job2.id.into()]),
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
+			[
+				TaxedAmount {
+					// 1h05m, rounded to the nearest 15m, at $20/h, plus 20%
+					gross: Money::new(24_00, 2, Currency::Usd),
+					id: job.id,
+					net: Money::new(20_00, 2, Currency::Usd),
+					tax: Money::new(4_00, 2, Currency::Usd),
+				},
+				TaxedAmount {
+					gross: Money::new(20_00, 2, Currency::Usd),
+					id: job2.id,
+					net: Money::new(20_00, 2, Currency::Usd),
+					tax: Money::new(0, 2, Currency::Usd),
+				},
+			]
+			.into_iter()
+			.collect::<HashSet<_>>(),
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_exchanged()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (stored, unstored) = (
+			NaiveDate::from_ymd(1881, 01, 03),
+			NaiveDate::from_ymd(1881, 01, 04),
+		);
+
+		let (job, job2) = futures::join!(
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.from_utc_date(&stored).and_hms(09, 00, 00),
+				Money::new(20_00, 2, Currency::Eur),
+			),
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.from_utc_date(&unstored).and_hms(09, 00, 00),
+				Money::new(20_00, 2, Currency::Eur),
+			),
+		);
+
+		PgExchangeRates::store(
+			&connection,
+			stored,
+			[(Currency::Eur, Decimal::ONE), (Currency::Usd, Decimal::TWO)].into_iter(),
+		)
+		.await
+		.unwrap();
+
+		let stored_rates = "Date, EUR, USD, \n03 January 1881, 1, 2, \n"
+			.parse::<ExchangeRates>()
+			.unwrap();
+		let source = "Date, EUR, USD, \n04 January 1881, 1, 3, \n"
+			.parse::<ExchangeRates>()
+			.unwrap();
+
+		// The rates in the database take precedence over the `source`.
+		assert_eq!(
+			PgJob::retrieve_exchanged(&connection, &job.id.into(), Currency::Usd, &source)
+				.await
+				.unwrap()
+				.as_slice(),
+			&[job.exchange(Currency::Usd, &stored_rates)],
+		);
+
+		// Rates which are not in the database are taken from the `source`, and then stored.
+		assert_eq!(
+			PgJob::retrieve_exchanged(&connection, &job2.id.into(), Currency::Usd, &source)
+				.await
+				.unwrap()
+				.as_slice(),
+			&[job2.exchange(Currency::Usd, &source)],
+		);
+
+		let row = sqlx::query!(
+			"SELECT rate FROM exchange_rates WHERE date = $1 AND currency = 'USD';",
+			unstored,
+		)
+		.fetch_one(&connection)
+		.await
+		.unwrap();
+
+		assert_eq!(row.rate, Decimal::from(3));
+	}
+
+	#[tokio::test]
+	async fn retrieve_with_options()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let closed_early = PgJob::create(
+			&connection,
+			organization.clone(),
+			Some(Utc.ymd(1882, 01, 02).and_hms(09, 00, 00)),
+			Utc.ymd(1882, 01, 01).and_hms(09, 00, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+		let closed_late = PgJob::create(
+			&connection,
+			organization.clone(),
+			Some(Utc.ymd(1882, 01, 03).and_hms(09, 00, 00)),
+			Utc.ymd(1882, 01, 01).and_hms(09, 00, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+		let open = util::create_job(
+			&connection,
+			organization.clone(),
+			Utc.ymd(1882, 01, 01).and_hms(09, 00, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		let match_condition = MatchJob {
+			id: Match::Or(vec![
+				closed_early.id.into(),
+				closed_late.id.into(),
+				open.id.into(),
+			]),
+			..Default::default()
+		};
+
+		// `NULL`s are last when ascending…
+		let mut options = RetrieveOptions {
+			order_by: vec![OrderBy::ascending(|c| c.date_close)],
+			page: None,
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgJob::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[closed_early.clone(), closed_late.clone(), open.clone()],
+		);
+
+		options.page = Some(Page::Keyset {
+			after: vec![
+				SortValue::Timestamp(closed_early.date_close.unwrap()),
+				SortValue::Id(closed_early.id),
+			],
+			limit: 5,
+		});
+
+		assert_eq!(
+			PgJob::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[closed_late.clone(), open.clone()],
+		);
+
+		options.page = Some(Page::Keyset {
+			after: vec![SortValue::Null, SortValue::Id(open.id)],
+			limit: 5,
+		});
+
+		assert!(
+			PgJob::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap()
+				.is_empty()
+		);
+
+		// …and first when descending.
+		options.order_by[0].order = Order::Descending;
+
+		assert_eq!(
+			PgJob::retrieve_with_options(&connection, &match_condition, &options)
+				.await
+				.unwrap(),
+			[closed_late, closed_early],
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_as_of()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let employee = util::create_employee(&connection).await;
+		let organization = PgOrganization::create(&connection, earth, "Some Organization".into())
+			.await
+			.unwrap();
+
+		let mut job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(2022, 03, 01).and_hms(09, 00, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee,
+			vec![(
+				"Flight".into(),
+				Money::new(300_56, 2, Currency::Usd),
+				"Trip to the client".into(),
+			)],
+			job.clone(),
+			Utc.ymd(2022, 03, 02).and_hms(09, 00, 00),
+			Some(Utc.ymd(2022, 03, 02).and_hms(17, 00, 00)),
+			"Did something".into(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		// NOTE: the time is taken from the database, so that its clock is the only one which matters.
+		let as_of = PgAudit::retrieve_history(&connection, AuditedTable::Timesheets, timesheet.id)
+			.await
+			.unwrap()
+			.pop()
+			.unwrap()
+			.date;
+
+		let old_job = job.clone();
+		job.invoice.hourly_rate = Money::new(25_00, 2, Currency::Usd);
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgJob::update(&mut transaction, [&job].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		PgTimesheet::delete(&connection, [&timesheet].into_iter())
+			.await
+			.unwrap();
+
+		assert_eq!(
+			PgJob::retrieve(&connection, &job.id.into()).await.unwrap(),
+			[job.clone()],
+		);
+		assert_eq!(
+			PgJob::retrieve_as_of(&connection, as_of, &job.id.into())
+				.await
+				.unwrap(),
+			[old_job],
+		);
+
+		assert!(PgTimesheet::retrieve(&connection, &timesheet.id.into())
+			.await
+			.unwrap()
+			.is_empty());
+		assert_eq!(
+			PgTimesheet::retrieve_as_of(&connection, as_of, &timesheet.id.into())
+				.await
+				.unwrap(),
+			[timesheet],
+		);
+	}
 }
@@ -1,10 +1,8 @@
 use clinvoice_adapter::{schema::columns::JobColumns, Updatable};
-use clinvoice_finance::{ExchangeRates, Exchangeable};
 use clinvoice_schema::{
 	chrono::{DateTime, Utc},
 	Job,
 };
-use futures::TryFutureExt;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgJob;
@@ -37,37 +35,54 @@ impl Updatable for PgJob
 			return Ok(());
 		}
 
-		let exchange_rates = ExchangeRates::new()
-			.map_err(util::finance_err_to_sqlx)
-			.await?;
-		PgSchema::update(connection, JobColumns::default(), |query| {
-			query.push_values(peekable_entities, |mut q, e| {
-				q.push_bind(e.client.id)
-					.push_bind(e.date_open.pg_sanitize())
-					.push_bind(e.date_close.pg_sanitize())
-					.push_bind(e.id)
-					.push_bind(e.increment);
-
-				match e.invoice.date.pg_sanitize()
+		PgSchema::update_with(
+			connection,
+			JobColumns::default(),
+			&[util::currency_column(
+				JobColumns::<&str>::default().invoice_hourly_rate,
+			)],
+			|query| {
+				query.push_values(peekable_entities, |mut q, e| {
+					q.push_bind(e.client.id)
+						.push_bind(e.date_open.pg_sanitize())
+						.push_bind(e.date_close.pg_sanitize())
+						.push_bind(e.id)
+						.push_bind(e.increment);
+
+					match e.invoice.date.pg_sanitize()
+					{
+						Some(ref date) => q.push_bind(date.issued).push_bind(date.paid),
+						_ => q
+							.push_bind(None::<DateTime<Utc>>)
+							.push_bind(None::<DateTime<Utc>>),
+					};
+
+					q.push_bind(e.invoice.hourly_rate.amount)
+						.push_bind(&e.notes)
+						.push_bind(&e.objectives)
+						.push_bind(e.invoice.hourly_rate.currency.to_string());
+				});
+			},
+		)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| {
+				util::sole_value_of(entities.clone(), |j| match field
 				{
-					Some(ref date) => q.push_bind(date.issued).push_bind(date.paid),
-					_ => q
-						.push_bind(None::<DateTime<Utc>>)
-						.push_bind(None::<DateTime<Utc>>),
-				};
-
-				q.push_bind(
-					e.invoice
-						.hourly_rate
-						.exchange(Default::default(), &exchange_rates)
-						.amount
-						.to_string(),
-				)
-				.push_bind(&e.notes)
-				.push_bind(&e.objectives);
-			});
-		})
-		.await?;
+					"client_id" => Some(j.client.id.to_string()),
+					"currency" => Some(j.invoice.hourly_rate.currency.to_string()),
+					"date_close" => j.date_close.map(|d| d.to_string()),
+					"date_open" => Some(j.date_open.to_string()),
+					"invoice_date_paid" => j
+						.invoice
+						.date
+						.as_ref()
+						.and_then(|d| d.paid)
+						.map(|d| d.to_string()),
+					_ => None,
+				})
+			})
+		})?;
 
 		PgOrganization::update(connection, entities.map(|e| &e.client)).await
 	}
@@ -84,7 +99,7 @@ mod tests
 		Updatable,
 	};
 	use clinvoice_finance::Money;
-	use clinvoice_schema::{chrono, Invoice, InvoiceDate};
+	use clinvoice_schema::{chrono, Currency, Invoice, InvoiceDate};
 	use futures::TryFutureExt;
 	use pretty_assertions::assert_eq;
 
@@ -129,7 +144,7 @@ mod tests
 				issued: chrono::Utc::now(),
 				paid: Some(chrono::Utc::now() + chrono::Duration::seconds(300)),
 			}),
-			hourly_rate: Money::new(200_00, 2, Default::default()),
+			hourly_rate: Money::new(200_00, 2, Currency::Eur),
 		};
 		job.notes = format!("Finished {}", job.notes);
 		job.objectives = format!("Test {}", job.notes);
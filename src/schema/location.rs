@@ -4,6 +4,7 @@ mod retrievable;
 mod updatable;
 
 use core::fmt::Display;
+use std::collections::HashMap;
 
 use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, SnakeCase, TableToSql},
@@ -12,8 +13,8 @@ use clinvoice_adapter::{
 };
 use clinvoice_match::{Match, MatchLocation, MatchOption, MatchOuterLocation};
 use clinvoice_schema::{Id, Location};
-use futures::{future, TryFutureExt, TryStreamExt};
-use sqlx::{Error, Executor, Postgres, QueryBuilder, Result, Row};
+use futures::{TryFutureExt, TryStreamExt};
+use sqlx::{postgres::PgRow, Error, Executor, Postgres, QueryBuilder, Result, Row};
 
 use crate::{fmt::PgLocationRecursiveCte, PgSchema};
 
@@ -141,51 +142,71 @@ impl PgLocation
 		query
 	}
 
-	/// Construct a [`Location`], also constructing all outer [`Location`]s, and return it.
-	pub(super) async fn retrieve_by_id<'c, TConn>(connection: TConn, id: Id) -> Result<Location>
+	/// Construct each [`Location`] whose [`Id`] is in `ids`, also constructing all of their outer
+	/// [`Location`]s, using a single query.
+	///
+	/// The returned map contains the requested [`Location`]s as well as their outer [`Location`]s.
+	pub(super) async fn retrieve_by_ids<'c, TConn, TIter>(
+		connection: TConn,
+		ids: TIter,
+	) -> Result<HashMap<Id, Location>>
 	where
 		TConn: Executor<'c, Database = Postgres>,
+		TIter: IntoIterator<Item = Id>,
 	{
-		const SOURCE: &str = "this column in `locations` must be non-null";
-		sqlx::query!(
-			"WITH RECURSIVE location_view AS
+		/// Construct the [`Location`] with the `id` (and all of its outer [`Location`]s) from the
+		/// `views`, remembering every [`Location`] which was constructed in `locations`.
+		fn construct(
+			id: Id,
+			views: &HashMap<Id, (String, Option<Id>)>,
+			locations: &mut HashMap<Id, Location>,
+		) -> Result<Location>
+		{
+			if let Some(location) = locations.get(&id)
+			{
+				return Ok(location.clone());
+			}
+
+			let (name, outer_id) = views.get(&id).ok_or(Error::RowNotFound)?;
+			let location = Location {
+				id,
+				name: name.clone(),
+				outer: outer_id
+					.map(|outer| construct(outer, views, locations).map(Box::new))
+					.transpose()?,
+			};
+
+			locations.insert(id, location.clone());
+			Ok(location)
+		}
+
+		let ids: Vec<_> = ids.into_iter().collect();
+
+		// There is nothing to do
+		if ids.is_empty()
+		{
+			return Ok(HashMap::new());
+		}
+
+		let views = sqlx::query!(
+			r#"WITH RECURSIVE location_view AS
 			(
-				SELECT id, name, outer_id FROM locations WHERE id = $1
+				SELECT id, name, outer_id FROM locations WHERE id = ANY($1)
 				UNION
 				SELECT L.id, L.name, L.outer_id FROM locations L JOIN location_view V ON (L.id = V.outer_id)
-			) SELECT * FROM location_view ORDER BY id;",
-			id,
+			) SELECT id AS "id!", name AS "name!", outer_id FROM location_view;"#,
+			&ids,
 		)
 		.fetch(connection)
-		.try_fold(None, |previous: Option<Location>, view| {
-			future::ok(Some(Location {
-				id: match view.id
-				{
-					Some(id) => id,
-					_ =>
-					{
-						return future::err(Error::ColumnDecode {
-							index: "name".into(),
-							source: SOURCE.into(),
-						})
-					},
-				},
-				name: match view.name
-				{
-					Some(n) => n,
-					_ =>
-					{
-						return future::err(Error::ColumnDecode {
-							index: "name".into(),
-							source: SOURCE.into(),
-						})
-					},
-				},
-				outer: previous.map(Box::new),
-			}))
-		})
-		.await
-		.and_then(|v| v.ok_or(Error::RowNotFound))
+		.map_ok(|view| (view.id, (view.name, view.outer_id)))
+		.try_collect()
+		.await?;
+
+		let mut locations = HashMap::with_capacity(ids.len());
+		ids.into_iter()
+			.try_for_each(|id| construct(id, &views, &mut locations).map(drop))?;
+
+		Ok(locations)
 	}
 
 	/// Retrieve a [`Match`] which will match all of the [`Id`]s of the [`Location`]s which match the
@@ -213,4 +234,32 @@ impl PgLocation
 			.map_ok(Match::Or)
 			.await
 	}
+
+	/// Convert each of the `rows` using `row_to_view`, after [retrieving](Self::retrieve_by_ids)
+	/// the [`Location`] which `location_id_of` each row in a single query.
+	pub(super) async fn rows_to_views<'c, T, TConn, TLocationIdOf, TRowToView>(
+		connection: TConn,
+		rows: &[PgRow],
+		location_id_of: TLocationIdOf,
+		row_to_view: TRowToView,
+	) -> Result<Vec<T>>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+		TLocationIdOf: Fn(&PgRow) -> Result<Option<Id>>,
+		TRowToView: Fn(&HashMap<Id, Location>, &PgRow) -> Result<T>,
+	{
+		let locations = Self::retrieve_by_ids(
+			connection,
+			rows
+				.iter()
+				.filter_map(|row| location_id_of(row).transpose())
+				.collect::<Result<Vec<_>>>()?,
+		)
+		.await?;
+
+		rows
+			.iter()
+			.map(|row| row_to_view(&locations, row))
+			.collect()
+	}
 }
@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::LocationColumns, Deletable};
 use clinvoice_schema::{Id, Location};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Executor, Postgres, Result, Transaction};
 
 use super::PgLocation;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgLocation
@@ -28,14 +31,73 @@ impl Deletable for PgLocation
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgLocation
+{
+	type Entity = Location;
+
+	/// Soft delete the `entities` (via `connection`), along with the [`Location`]s inside of them
+	/// which were not already soft deleted.
+	///
+	/// The [`Organization`](clinvoice_schema::Organization)s at the `entities` are unaffected.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(l: &Location) -> Id
+		{
+			l.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Location| e.id`
+		PgSchema::set_deleted::<_, _, LocationColumns<char>>(connection, entities.map(mapper), true)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Restore the `entities` (via `connection`), along with the [`Location`]s inside of them which
+	/// were soft deleted at the same time as them.
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(l: &Location) -> Id
+		{
+			l.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Location| e.id`
+		PgSchema::set_deleted::<_, _, LocationColumns<char>>(connection, entities.map(mapper), false)
+			.await
+			.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
-	use clinvoice_adapter::{schema::LocationAdapter, Deletable, Retrievable};
-	use clinvoice_match::Match;
+	use std::collections::HashSet;
+
+	use clinvoice_adapter::{
+		schema::{LocationAdapter, OrganizationAdapter},
+		Deletable,
+		Retrievable,
+	};
+	use clinvoice_match::{Match, MatchLocation};
 	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgLocation};
+	use crate::schema::{util, PgLocation, PgOrganization, RetrieveOptions, SoftDeletable};
 
 	#[tokio::test]
 	async fn delete()
@@ -74,4 +136,77 @@ mod tests
 			&[earth]
 		);
 	}
+
+	#[tokio::test]
+	async fn soft_delete()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let usa = PgLocation::create(&connection, "USA".into(), Some(earth.clone()))
+			.await
+			.unwrap();
+
+		let organization =
+			PgOrganization::create(&connection, usa.clone(), "Some Organization".into())
+				.await
+				.unwrap();
+
+		let match_condition: MatchLocation = Match::Or(vec![earth.id.into(), usa.id.into()]).into();
+		let retrieve = |include_deleted| {
+			PgLocation::retrieve_with_options(&connection, &match_condition, &RetrieveOptions {
+				include_deleted,
+				..Default::default()
+			})
+		};
+
+		PgLocation::soft_delete(&connection, [&earth].into_iter())
+			.await
+			.unwrap();
+
+		// The locations inside of a soft deleted location are soft deleted with it.
+		assert!(retrieve(false).await.unwrap().is_empty());
+		assert_eq!(
+			PgLocation::count(&connection, &match_condition)
+				.await
+				.unwrap(),
+			0
+		);
+		assert_eq!(
+			retrieve(true)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[earth.clone(), usa.clone()].into_iter().collect(),
+		);
+
+		// The organizations at a soft deleted location are not.
+		assert_eq!(
+			PgOrganization::retrieve(&connection, &Match::from(organization.id).into())
+				.await
+				.unwrap(),
+			[organization],
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		PgLocation::restore(&mut transaction, [&earth].into_iter())
+			.await
+			.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			retrieve(false)
+				.await
+				.unwrap()
+				.into_iter()
+				.collect::<HashSet<_>>(),
+			[earth, usa].into_iter().collect(),
+		);
+	}
 }
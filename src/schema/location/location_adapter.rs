@@ -3,6 +3,7 @@ use clinvoice_schema::Location;
 use sqlx::{Executor, Postgres, Result};
 
 use super::PgLocation;
+use crate::schema::util;
 
 #[async_trait::async_trait]
 impl LocationAdapter for PgLocation
@@ -21,7 +22,14 @@ impl LocationAdapter for PgLocation
 			outer.as_ref().map(|o| o.id)
 		)
 		.fetch_one(connection)
-		.await?;
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"outer_id" => outer.as_ref().map(|o| o.id.to_string()),
+				_ => None,
+			})
+		})?;
 
 		Ok(Location {
 			id: row.id,
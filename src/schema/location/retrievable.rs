@@ -1,15 +1,23 @@
+use std::collections::HashMap;
+
 use clinvoice_adapter::{
-	fmt::{sql, QueryBuilderExt, TableToSql},
+	fmt::{sql, QueryBuilderExt, SnakeCase, TableToSql},
 	schema::columns::LocationColumns,
 	Retrievable,
+	WriteContext,
 };
 use clinvoice_match::MatchLocation;
-use clinvoice_schema::Location;
-use futures::TryStreamExt;
-use sqlx::{Pool, Postgres, Result, Row};
+use clinvoice_schema::{Id, Location};
+use futures::{future, stream::BoxStream};
+use sqlx::{postgres::PgRow, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgLocation;
-use crate::fmt::PgLocationRecursiveCte;
+use crate::{
+	fmt::PgLocationRecursiveCte,
+	schema::{util, Error, RetrieveOptions},
+};
+
+const COLUMNS: LocationColumns<&'static str> = LocationColumns::default();
 
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
@@ -28,22 +36,167 @@ impl Retrievable for PgLocation
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: LocationColumns<&'static str> = LocationColumns::default();
+		Self::retrieve_with_options(connection, match_condition, &Default::default())
+			.await
+			.map_err(sqlx::Error::from)
+	}
+}
+
+impl PgLocation
+{
+	/// Count the [`Location`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchLocation,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, |q| {
+			q.push(util::COUNT);
+		});
+
+		query
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Check whether any [`Location`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchLocation,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, |q| {
+			q.push(util::EXISTS);
+		});
+
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Get the [`Id`] of the [`Location`] which some `row` of the [`PgLocation::retrieve_query`]
+	/// selected.
+	fn id_of(row: &PgRow) -> Result<Option<Id>>
+	{
+		row.try_get(COLUMNS.id).map(Some)
+	}
+
+	/// Generate the query which selects the [`Id`] of every [`Location`] that matches the
+	/// `match_condition`, according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchLocation,
+		options: &RetrieveOptions<LocationColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) =
+			Self::select_matching(match_condition, options.include_deleted, |q| {
+				q.push(COLUMNS.default_scope().id);
+			});
+
+		options.write_where(
+			context,
+			COLUMNS,
+			LocationColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			LocationColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		);
+
+		Ok(query)
+	}
+
+	/// Same as [`PgLocation::retrieve_with_options`], except that the [`Location`]s are yielded as
+	/// they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchLocation,
+		options: &'a RetrieveOptions<LocationColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Location, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			move |rows| async move {
+				Self::rows_to_views(connection, &rows, Self::id_of, Self::row_to_view).await
+			},
+		)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Location`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchLocation,
+		options: &RetrieveOptions<LocationColumns<&'static str>>,
+	) -> Result<Vec<Location>, Error>
+	{
+		let rows = Self::retrieve_query(match_condition, options)?
+			.prepare()
+			.fetch_all(connection)
+			.await?;
 
+		Self::rows_to_views(connection, &rows, Self::id_of, Self::row_to_view)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Get the [`Location`] which some `row` of the [`PgLocation::retrieve_query`] selected from
+	/// the `locations` which were [retrieved](PgLocation::retrieve_by_ids) for it.
+	fn row_to_view(locations: &HashMap<Id, Location>, row: &PgRow) -> Result<Location>
+	{
+		row.try_get(COLUMNS.id).map(|id: Id| locations[&id].clone())
+	}
+
+	/// Generate a query which `select`s something (e.g. the [`Id`]) of every [`Location`] that
+	/// matches the `match_condition`.
+	///
+	/// [Soft deleted](crate::schema::SoftDeletable) [`Location`]s are excluded, unless
+	/// `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is returned so that
+	/// more conditions can be added.
+	fn select_matching<'a, TSelect>(
+		match_condition: &'a MatchLocation,
+		include_deleted: bool,
+		select: TSelect,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
+		// NOTE: the `WITH RECURSIVE` statement only selects the `COLUMNS`, so the `locations` are
+		//       joined again to tell which were soft deleted.
+		let alias_table = SnakeCase::from((LocationColumns::<char>::DEFAULT_ALIAS, 'T'));
 		let mut query = Self::query_with_recursive(match_condition);
 
+		query.push(sql::SELECT);
+		select(&mut query);
 		query
-			.push(sql::SELECT)
-			.push(COLUMNS.default_scope().id)
 			.push_from(
 				PgLocationRecursiveCte::from(match_condition),
 				LocationColumns::<char>::DEFAULT_ALIAS,
 			)
-			.prepare()
-			.fetch(connection)
-			.and_then(|row| PgLocation::retrieve_by_id(connection, row.get(COLUMNS.id)))
-			.try_collect()
-			.await
+			.push_equijoin(
+				LocationColumns::<&str>::TABLE_NAME,
+				alias_table,
+				COLUMNS.scope(alias_table).id,
+				COLUMNS.default_scope().id,
+			);
+
+		// NOTE: the `match_condition` was already applied by the `WITH RECURSIVE` statement.
+		let context =
+			util::write_not_deleted(Default::default(), alias_table, include_deleted, &mut query);
+
+		(query, context)
 	}
 }
 
@@ -54,6 +207,7 @@ mod tests
 
 	use clinvoice_adapter::{schema::LocationAdapter, Retrievable};
 	use clinvoice_match::{MatchLocation, MatchOuterLocation};
+	use futures::TryStreamExt;
 	use pretty_assertions::assert_eq;
 
 	use crate::schema::{util, PgLocation};
@@ -102,4 +256,33 @@ mod tests
 			.collect::<HashSet<_>>()
 		);
 	}
+
+	#[tokio::test]
+	async fn retrieve_stream()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let (arizona, utah) = futures::try_join!(
+			PgLocation::create(&connection, "Arizona".into(), Some(earth.clone())),
+			PgLocation::create(&connection, "Utah".into(), Some(earth.clone())),
+		)
+		.unwrap();
+
+		let match_condition = MatchLocation {
+			outer: MatchOuterLocation::Some(Box::new(earth.id.into())),
+			..Default::default()
+		};
+
+		assert_eq!(
+			[arizona, utah].into_iter().collect::<HashSet<_>>(),
+			PgLocation::retrieve_stream(&connection, &match_condition, &Default::default())
+				.try_collect::<HashSet<_>>()
+				.await
+				.unwrap()
+		);
+	}
 }
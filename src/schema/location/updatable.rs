@@ -5,7 +5,7 @@ use clinvoice_schema::Location;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgLocation;
-use crate::PgSchema;
+use crate::{schema::util, PgSchema};
 
 #[async_trait::async_trait]
 impl Updatable for PgLocation
@@ -84,6 +84,15 @@ impl Updatable for PgLocation
 			});
 		})
 		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| {
+				util::sole_value_of(entities_collected.iter().copied(), |l| match field
+				{
+					"outer_id" => l.outer.as_ref().map(|o| o.id.to_string()),
+					_ => None,
+				})
+			})
+		})
 	}
 }
 
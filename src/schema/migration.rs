@@ -0,0 +1,439 @@
+mod drift;
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use std::error::Error as StdError;
+
+pub use drift::{SchemaDifference, SchemaDrift};
+use sqlx::{migrate::MigrateError, Acquire, Executor, PgConnection, PgPool, Postgres};
+
+use super::{Error, PgSchema};
+
+/// Declare the [`Migration`]s which are embedded in this crate.
+///
+/// Each `$file` must have a corresponding `migrations/{$file}.up.sql` and
+/// `migrations/{$file}.down.sql` in the root of the crate.
+macro_rules! migrations {
+	($($version:literal => $file:literal),+ $(,)?) => {
+		&[$(Migration {
+			version: $version,
+			name: $file,
+			up: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/migrations/", $file, ".up.sql")),
+			down: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/migrations/", $file, ".down.sql")),
+		}),+]
+	};
+}
+
+/// Every [`Migration`] which this crate knows how to apply, in ascending order of `version`.
+///
+/// Once a [`Migration`] has been released, it must not be edited. Instead, add a new one.
+const MIGRATIONS: &[Migration] = migrations![
+	1 => "0001_init",
+	2 => "0002_currency",
+	3 => "0003_numeric_money",
+	4 => "0004_exchange_rates",
+	5 => "0005_historical_exchange_rates",
+	6 => "0006_round_to_increment",
+	7 => "0007_invoices",
+	8 => "0008_payments",
+	9 => "0009_tax_rates",
+	10 => "0010_job_adjustments",
+	11 => "0011_billing_rates",
+	12 => "0012_timesheet_overlap",
+	13 => "0013_running_timesheets",
+	14 => "0014_audit_log",
+	15 => "0015_soft_delete",
+	16 => "0016_history",
+	17 => "0017_named_constraints",
+];
+
+/// The database has been migrated to a version newer than [`PgSchema::LATEST_VERSION`], which
+/// means that it was migrated by a newer version of this crate.
+///
+/// Returned as an [`Error::SchemaTooNew`], or inside of a [`MigrateError::Source`] by the adapter
+/// traits (such as [`Initializable`](clinvoice_adapter::Initializable)) which must return an
+/// [`sqlx::Error`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SchemaTooNew
+{
+	/// The version of the schema in the database.
+	pub found: i32,
+
+	/// The newest version which this crate knows about.
+	pub latest: i32,
+}
+
+impl Display for SchemaTooNew
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult
+	{
+		let Self { found, latest } = self;
+		write!(
+			f,
+			"the database schema is at version {found}, but only {latest} is supported"
+		)
+	}
+}
+
+impl StdError for SchemaTooNew {}
+
+/// A numbered change to the schema, along with the instructions to revert it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Migration
+{
+	/// The SQL which reverts the changes made by `up`.
+	down: &'static str,
+
+	/// The name of the files which `up` and `down` were read from.
+	name: &'static str,
+
+	/// The SQL which applies this migration.
+	up: &'static str,
+
+	/// The version that the schema will be at after `up` is applied.
+	version: i32,
+}
+
+impl PgSchema
+{
+	/// The newest schema version which this crate is able to work with.
+	pub const LATEST_VERSION: i32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+	/// Connect to the database at the `url`, after checking that it has not been migrated by a newer
+	/// version of this crate.
+	///
+	/// The adapters of this crate do not check the version of the schema themselves, so the
+	/// `connection` they are given should come from here, or the database should have been
+	/// [initialized](clinvoice_adapter::Initializable::init) first.
+	///
+	/// # Errors
+	///
+	/// * An [`Error::SchemaTooNew`] if the database was migrated by a newer version of this crate.
+	pub async fn connect(url: &str) -> Result<PgPool, Error>
+	{
+		let connection = PgPool::connect(url).await?;
+		Self::current_version(&connection).await?;
+		Ok(connection)
+	}
+
+	/// Get the version of the schema which has been applied to the database (via `connection`), or
+	/// [`None`] if it has never been [migrated](PgSchema::migrate).
+	///
+	/// # Errors
+	///
+	/// * An [`Error::SchemaTooNew`] if the database was migrated by a newer version of this crate.
+	pub async fn current_version<'c, TConn>(connection: TConn) -> Result<Option<i32>, Error>
+	where
+		TConn: Acquire<'c, Database = Postgres> + Send,
+	{
+		let mut connection = connection.acquire().await?;
+		Self::current_version_of(&mut connection).await
+	}
+
+	/// Same as [`PgSchema::current_version`], but on a specific `connection`.
+	async fn current_version_of(connection: &mut PgConnection) -> Result<Option<i32>, Error>
+	{
+		let version_table =
+			sqlx::query!(r#"SELECT to_regclass('schema_version') IS NOT NULL AS "exists!";"#)
+				.fetch_one(&mut *connection)
+				.await?;
+
+		if !version_table.exists
+		{
+			return Ok(None);
+		}
+
+		let version = sqlx::query!("SELECT max(version) AS version FROM schema_version;")
+			.fetch_one(connection)
+			.await?
+			.version;
+
+		match version
+		{
+			Some(found) if found > Self::LATEST_VERSION => Err(
+				SchemaTooNew {
+					found,
+					latest: Self::LATEST_VERSION,
+				}
+				.into(),
+			),
+			_ => Ok(version),
+		}
+	}
+
+	/// Apply every [`Migration`] which has not been applied to the database (via `connection`),
+	/// bringing it up to the [`LATEST_VERSION`](PgSchema::LATEST_VERSION).
+	///
+	/// # See also
+	///
+	/// * [`PgSchema::migrate_to`]
+	pub async fn migrate<'c, TConn>(connection: TConn) -> Result<(), Error>
+	where
+		TConn: Acquire<'c, Database = Postgres> + Send,
+	{
+		Self::migrate_to(connection, Self::LATEST_VERSION).await
+	}
+
+	/// Apply (or revert) [`Migration`]s to the database (via `connection`) until its schema is at
+	/// the `target` version. A `target` of `0` reverts every [`Migration`].
+	///
+	/// All of the work is done in a single transaction, so if any [`Migration`] fails then the
+	/// database is left as it was.
+	///
+	/// # Errors
+	///
+	/// If any of the following:
+	///
+	/// * the database is at a version newer than [`PgSchema::LATEST_VERSION`] (see
+	///   [`Error::SchemaTooNew`]).
+	/// * the database was initialized before schema versioning was introduced, and its schema
+	///   differs from what `0001_init` creates (see [`SchemaDrift`]).
+	/// * `target` is not the version of any known [`Migration`].
+	/// * a [`Migration`] could not be applied.
+	pub async fn migrate_to<'c, TConn>(connection: TConn, target: i32) -> Result<(), Error>
+	where
+		TConn: Acquire<'c, Database = Postgres> + Send,
+	{
+		if target != 0 && MIGRATIONS.iter().all(|m| m.version != target)
+		{
+			return Err(
+				sqlx::Error::from(MigrateError::Source(
+					format!("there is no migration with version {target}").into(),
+				))
+				.into(),
+			);
+		}
+
+		let mut transaction = connection.begin().await?;
+
+		sqlx::query!(
+			"CREATE TABLE IF NOT EXISTS schema_version
+			(
+				version integer PRIMARY KEY,
+				name text NOT NULL,
+				applied timestamptz NOT NULL DEFAULT now()
+			);"
+		)
+		.execute(&mut transaction)
+		.await?;
+
+		// NOTE: prevents two clients from migrating the same database at once.
+		sqlx::query!("LOCK TABLE schema_version IN EXCLUSIVE MODE;")
+			.execute(&mut transaction)
+			.await?;
+
+		let mut current = Self::current_version_of(&mut transaction)
+			.await?
+			.unwrap_or(0);
+
+		// NOTE: databases which were initialized before schema versioning was introduced should
+		//       already have everything that `0001_init` creates, so it is recorded as applied rather
+		//       than run. Whatever they lack is reported before any other migration is applied to
+		//       them.
+		if current == 0 &&
+			target > 0 &&
+			sqlx::query!(r#"SELECT to_regclass('jobs') IS NOT NULL AS "exists!";"#)
+				.fetch_one(&mut transaction)
+				.await?
+				.exists
+		{
+			let init = &MIGRATIONS[0];
+			sqlx::query!(
+				"INSERT INTO schema_version (version, name) VALUES ($1, $2);",
+				init.version,
+				init.name,
+			)
+			.execute(&mut transaction)
+			.await?;
+
+			current = init.version;
+
+			let differences = Self::drift_from(&mut transaction, current).await?;
+			if !differences.is_empty()
+			{
+				return Err(
+					sqlx::Error::from(MigrateError::Source(SchemaDrift(differences).into())).into(),
+				);
+			}
+		}
+
+		if target > current
+		{
+			for migration in MIGRATIONS
+				.iter()
+				.filter(|m| current < m.version && m.version <= target)
+			{
+				transaction.execute(migration.up).await?;
+				sqlx::query!(
+					"INSERT INTO schema_version (version, name) VALUES ($1, $2);",
+					migration.version,
+					migration.name,
+				)
+				.execute(&mut transaction)
+				.await?;
+			}
+		}
+		else
+		{
+			for migration in MIGRATIONS
+				.iter()
+				.rev()
+				.filter(|m| target < m.version && m.version <= current)
+			{
+				transaction.execute(migration.down).await?;
+				sqlx::query!(
+					"DELETE FROM schema_version WHERE version = $1;",
+					migration.version
+				)
+				.execute(&mut transaction)
+				.await?;
+			}
+		}
+
+		transaction.commit().await.map_err(Error::from)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use pretty_assertions::assert_eq;
+	use sqlx::{migrate::MigrateError, Acquire, Executor};
+
+	use super::{SchemaDifference, SchemaDrift, SchemaTooNew, MIGRATIONS};
+	use crate::{
+		schema::{util, Error},
+		PgSchema,
+	};
+
+	#[test]
+	fn migrations_are_ordered()
+	{
+		assert_eq!(MIGRATIONS[0].version, 1);
+		MIGRATIONS.windows(2).for_each(|w| {
+			assert_eq!(
+				w[0].version + 1,
+				w[1].version,
+				"{} is out of order",
+				w[1].name
+			)
+		});
+	}
+
+	#[tokio::test]
+	async fn connect()
+	{
+		PgSchema::migrate(&util::connect().await).await.unwrap();
+
+		let connection = PgSchema::connect(&dotenv::var("DATABASE_URL").unwrap())
+			.await
+			.unwrap();
+		assert_eq!(
+			PgSchema::current_version(&connection).await.unwrap(),
+			Some(PgSchema::LATEST_VERSION),
+		);
+	}
+
+	#[tokio::test]
+	async fn migrate()
+	{
+		let connection = util::connect().await;
+
+		PgSchema::migrate(&connection).await.unwrap();
+		assert_eq!(
+			PgSchema::current_version(&connection).await.unwrap(),
+			Some(PgSchema::LATEST_VERSION)
+		);
+
+		// Migrating when there is nothing to do should be harmless.
+		PgSchema::migrate(&connection).await.unwrap();
+		assert_eq!(
+			PgSchema::current_version(&connection).await.unwrap(),
+			Some(PgSchema::LATEST_VERSION)
+		);
+	}
+
+	#[tokio::test]
+	async fn migrate_partial_baseline()
+	{
+		let connection = util::connect().await;
+
+		// NOTE: rolled back at the end of the test, so that the other tests are not affected.
+		let mut transaction = connection.begin().await.unwrap();
+		transaction
+			.execute("CREATE SCHEMA pre_versioning; SET LOCAL search_path TO pre_versioning;")
+			.await
+			.unwrap();
+
+		// A database which was initialized before schema versioning, but only in part.
+		transaction.execute(MIGRATIONS[0].up).await.unwrap();
+		transaction
+			.execute("DROP TABLE expenses; ALTER TABLE jobs ALTER COLUMN notes DROP NOT NULL;")
+			.await
+			.unwrap();
+
+		match PgSchema::migrate(&mut transaction).await.unwrap_err()
+		{
+			Error::Other(sqlx::Error::Migrate(e)) => match *e
+			{
+				MigrateError::Source(e) => assert_eq!(
+					e.downcast_ref::<SchemaDrift>(),
+					Some(&SchemaDrift(vec![
+						SchemaDifference::MissingTable("expenses".into()),
+						SchemaDifference::ColumnType {
+							table: "jobs".into(),
+							column: "notes".into(),
+							expected: "text NOT NULL".into(),
+							found: "text".into(),
+						},
+					]))
+				),
+				e => panic!("expected `SchemaDrift`, got {e:?}"),
+			},
+			e => panic!("expected `SchemaDrift`, got {e:?}"),
+		}
+
+		// Nothing was migrated.
+		assert_eq!(
+			PgSchema::current_version(&mut transaction).await.unwrap(),
+			None
+		);
+	}
+
+	#[tokio::test]
+	async fn schema_too_new()
+	{
+		/// Assert that the `error` is a [`SchemaTooNew`].
+		fn assert_too_new(error: Error)
+		{
+			match error
+			{
+				Error::SchemaTooNew(e) => assert_eq!(e, SchemaTooNew {
+					found: PgSchema::LATEST_VERSION + 1,
+					latest: PgSchema::LATEST_VERSION,
+				}),
+				e => panic!("expected `SchemaTooNew`, got {e:?}"),
+			}
+		}
+
+		let connection = util::connect().await;
+		PgSchema::migrate(&connection).await.unwrap();
+
+		// NOTE: rolled back at the end of the test, so that the other tests are not affected.
+		let mut transaction = connection.begin().await.unwrap();
+		sqlx::query!(
+			"INSERT INTO schema_version (version, name) VALUES ($1, 'from_the_future');",
+			PgSchema::LATEST_VERSION + 1,
+		)
+		.execute(&mut transaction)
+		.await
+		.unwrap();
+
+		assert_too_new(
+			PgSchema::current_version(&mut transaction)
+				.await
+				.unwrap_err(),
+		);
+		assert_too_new(PgSchema::drift(&mut transaction).await.unwrap_err());
+		assert_too_new(PgSchema::migrate_to(&mut transaction, 1).await.unwrap_err());
+	}
+}
@@ -0,0 +1,416 @@
+mod display;
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	error::Error as StdError,
+};
+
+use futures::TryStreamExt;
+use sqlx::{Acquire, Executor, PgConnection, Postgres, Result};
+
+use super::MIGRATIONS;
+use crate::{schema::Error, PgSchema};
+
+/// The prefix of the schema which [`PgSchema::drift`] builds the expected [`Catalog`] in. It never
+/// outlives the transaction it was created in.
+const EXPECTED_SCHEMA: &str = "clinvoice_drift_";
+
+/// A single way in which the schema of a database differs from the schema that its
+/// [`PgSchema::current_version`] says it should have.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum SchemaDifference
+{
+	/// A column has a different type (or nullability) than it should.
+	ColumnType
+	{
+		/// The name of the table which the column belongs to.
+		table: String,
+
+		/// The name of the column.
+		column: String,
+
+		/// The type that the column should have.
+		expected: String,
+
+		/// The type that the column has.
+		found: String,
+	},
+
+	/// A constraint has a different definition than it should.
+	ConstraintDefinition
+	{
+		/// The table (or domain) which the constraint belongs to.
+		owner: String,
+
+		/// The name of the constraint.
+		name: String,
+
+		/// The definition that the constraint should have.
+		expected: String,
+
+		/// The definition that the constraint has.
+		found: String,
+	},
+
+	/// A column which should exist does not.
+	MissingColumn
+	{
+		/// The name of the table which the column belongs to.
+		table: String,
+
+		/// The name of the column.
+		column: String,
+	},
+
+	/// A constraint which should exist does not.
+	MissingConstraint
+	{
+		/// The table (or domain) which the constraint belongs to.
+		owner: String,
+
+		/// The name of the constraint.
+		name: String,
+	},
+
+	/// A table which should exist does not.
+	MissingTable(String),
+
+	/// A column exists which should not.
+	UnexpectedColumn
+	{
+		/// The name of the table which the column belongs to.
+		table: String,
+
+		/// The name of the column.
+		column: String,
+	},
+
+	/// A constraint exists which should not.
+	UnexpectedConstraint
+	{
+		/// The table (or domain) which the constraint belongs to.
+		owner: String,
+
+		/// The name of the constraint.
+		name: String,
+	},
+}
+
+/// Every [`SchemaDifference`] found by [`PgSchema::drift`]. Returned as an [`Error`](StdError) (inside of a
+/// [`MigrateError::Source`](sqlx::migrate::MigrateError::Source)) when
+/// [`PgSchema::init`](clinvoice_adapter::Initializable::init) finds that the schema has drifted.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SchemaDrift(pub Vec<SchemaDifference>);
+
+impl StdError for SchemaDrift {}
+
+/// A snapshot of the tables, columns, and constraints in the `current_schema()`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Catalog
+{
+	/// The type of each `(table, column)`, followed by `NOT NULL` where applicable.
+	columns: BTreeMap<(String, String), String>,
+
+	/// The definition of each `(owner, constraint)`, where the owner is a table or a domain.
+	constraints: BTreeMap<(String, String), String>,
+}
+
+impl Catalog
+{
+	/// Take a snapshot of the `current_schema()` (via `connection`).
+	///
+	/// The `schema_version` table is not included, as it is managed by [`PgSchema::migrate_to`]
+	/// rather than by any [`Migration`](super::Migration).
+	async fn snapshot(connection: &mut PgConnection) -> Result<Self>
+	{
+		let columns = sqlx::query!(
+			r#"SELECT
+					C.relname::text AS "table!",
+					A.attname::text AS "column!",
+					format_type(A.atttypid, A.atttypmod) ||
+						CASE WHEN A.attnotnull THEN ' NOT NULL' ELSE '' END AS "data_type!"
+				FROM pg_attribute A
+				JOIN pg_class C ON (C.oid = A.attrelid)
+				WHERE C.relnamespace = current_schema()::regnamespace
+					AND C.relkind = 'r'
+					AND C.relname <> 'schema_version'
+					AND A.attnum > 0
+					AND NOT A.attisdropped;"#
+		)
+		.fetch(&mut *connection)
+		.map_ok(|row| ((row.table, row.column), row.data_type))
+		.try_collect()
+		.await?;
+
+		let constraints = sqlx::query!(
+			r#"SELECT
+					C.relname::text AS "owner!",
+					K.conname::text AS "name!",
+					pg_get_constraintdef(K.oid) AS "definition!"
+				FROM pg_constraint K
+				JOIN pg_class C ON (C.oid = K.conrelid)
+				WHERE C.relnamespace = current_schema()::regnamespace
+					AND C.relname <> 'schema_version'
+			UNION ALL
+				SELECT T.typname::text, K.conname::text, pg_get_constraintdef(K.oid)
+				FROM pg_constraint K
+				JOIN pg_type T ON (T.oid = K.contypid)
+				WHERE T.typnamespace = current_schema()::regnamespace;"#
+		)
+		.fetch(connection)
+		.map_ok(|row| ((row.owner, row.name), row.definition))
+		.try_collect()
+		.await?;
+
+		Ok(Self {
+			columns,
+			constraints,
+		})
+	}
+
+	/// Find every [`SchemaDifference`] between `self` (what was expected) and the `found`
+	/// [`Catalog`].
+	///
+	/// Tables which are in `found` but not in `self` are ignored, since they may belong to some
+	/// other application using the same database.
+	fn diff(&self, found: &Self) -> Vec<SchemaDifference>
+	{
+		/// Get the names of every table in the `columns` of a [`Catalog`].
+		fn tables(columns: &BTreeMap<(String, String), String>) -> BTreeSet<&str>
+		{
+			columns.keys().map(|(table, _)| table.as_str()).collect()
+		}
+
+		let expected_tables = tables(&self.columns);
+		let found_tables = tables(&found.columns);
+
+		let mut differences: Vec<_> = expected_tables
+			.difference(&found_tables)
+			.map(|table| SchemaDifference::MissingTable(table.to_string()))
+			.collect();
+
+		// NOTE: once a table is reported missing, its columns and constraints are not reported too.
+		let is_missing =
+			|owner: &str| !found_tables.contains(owner) && expected_tables.contains(owner);
+
+		self
+			.columns
+			.iter()
+			.filter(|((table, _), _)| !is_missing(table))
+			.for_each(|((table, column), expected)| {
+				match found.columns.get(&(table.clone(), column.clone()))
+				{
+					Some(f) if f != expected => differences.push(SchemaDifference::ColumnType {
+						table: table.clone(),
+						column: column.clone(),
+						expected: expected.clone(),
+						found: f.clone(),
+					}),
+					None => differences.push(SchemaDifference::MissingColumn {
+						table: table.clone(),
+						column: column.clone(),
+					}),
+					_ => (),
+				}
+			});
+
+		differences.extend(
+			found
+				.columns
+				.keys()
+				.filter(|&key| {
+					expected_tables.contains(key.0.as_str()) && !self.columns.contains_key(key)
+				})
+				.map(|(table, column)| SchemaDifference::UnexpectedColumn {
+					table: table.clone(),
+					column: column.clone(),
+				}),
+		);
+
+		let expected_owners: BTreeSet<_> = self
+			.constraints
+			.keys()
+			.map(|(owner, _)| owner.as_str())
+			.collect();
+
+		self
+			.constraints
+			.iter()
+			.filter(|((owner, _), _)| !is_missing(owner))
+			.for_each(|((owner, name), expected)| {
+				match found.constraints.get(&(owner.clone(), name.clone()))
+				{
+					Some(f) if f != expected =>
+					{
+						differences.push(SchemaDifference::ConstraintDefinition {
+							owner: owner.clone(),
+							name: name.clone(),
+							expected: expected.clone(),
+							found: f.clone(),
+						})
+					},
+					None => differences.push(SchemaDifference::MissingConstraint {
+						owner: owner.clone(),
+						name: name.clone(),
+					}),
+					_ => (),
+				}
+			});
+
+		differences.extend(
+			found
+				.constraints
+				.keys()
+				.filter(|&key| {
+					(expected_tables.contains(key.0.as_str()) ||
+						expected_owners.contains(key.0.as_str())) &&
+						!self.constraints.contains_key(key)
+				})
+				.map(|(owner, name)| SchemaDifference::UnexpectedConstraint {
+					owner: owner.clone(),
+					name: name.clone(),
+				}),
+		);
+
+		differences
+	}
+}
+
+impl PgSchema
+{
+	/// Compare the schema of the database (via `connection`) to the schema which its
+	/// [`current_version`](PgSchema::current_version) says that it should have, and report every
+	/// [`SchemaDifference`] between the two.
+	///
+	/// The expected schema is found by applying every [`Migration`](super::Migration) up to the
+	/// current version in a scratch schema, which is rolled back before this function returns.
+	pub async fn drift<'c, TConn>(connection: TConn) -> Result<Vec<SchemaDifference>, Error>
+	where
+		TConn: Acquire<'c, Database = Postgres> + Send,
+	{
+		let mut connection = connection.acquire().await?;
+
+		let version = Self::current_version_of(&mut connection)
+			.await?
+			.unwrap_or(0);
+
+		Self::drift_from(&mut connection, version)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Same as [`PgSchema::drift`], except that the schema of the database (via `connection`) is
+	/// compared to the one which it should have at the `version`, rather than at its
+	/// [`current_version`](PgSchema::current_version).
+	pub(super) async fn drift_from(
+		connection: &mut PgConnection,
+		version: i32,
+	) -> Result<Vec<SchemaDifference>>
+	{
+		let mut transaction = connection.begin().await?;
+
+		let found = Catalog::snapshot(&mut transaction).await?;
+
+		// NOTE: the transaction ID is part of the name, so that concurrent calls (or a schema which
+		//       was left behind somehow) do not conflict.
+		let schema = sqlx::query!(
+			r#"SELECT $1::text || txid_current() AS "name!";"#,
+			EXPECTED_SCHEMA
+		)
+		.fetch_one(&mut transaction)
+		.await?
+		.name;
+
+		transaction
+			.execute(format!("CREATE SCHEMA {schema}; SET LOCAL search_path TO {schema};").as_str())
+			.await?;
+
+		for migration in MIGRATIONS.iter().take_while(|m| m.version <= version)
+		{
+			transaction.execute(migration.up).await?;
+		}
+
+		let expected = Catalog::snapshot(&mut transaction).await?;
+		transaction.rollback().await?;
+
+		Ok(expected.diff(&found))
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use pretty_assertions::assert_eq;
+
+	use super::{Catalog, SchemaDifference};
+	use crate::{schema::util, PgSchema};
+
+	#[test]
+	fn diff()
+	{
+		let expected = Catalog {
+			columns: [
+				(("foo", "id"), "bigint NOT NULL"),
+				(("foo", "name"), "text NOT NULL"),
+				(("bar", "id"), "bigint NOT NULL"),
+			]
+			.into_iter()
+			.map(|((t, c), d)| ((t.into(), c.into()), d.into()))
+			.collect(),
+			constraints: [
+				(("foo", "foo_pkey"), "PRIMARY KEY (id)"),
+				(("bar", "bar_pkey"), "PRIMARY KEY (id)"),
+			]
+			.into_iter()
+			.map(|((o, n), d)| ((o.into(), n.into()), d.into()))
+			.collect(),
+		};
+
+		assert_eq!(expected.diff(&expected), Vec::new());
+
+		let found = Catalog {
+			columns: [
+				(("foo", "id"), "bigint NOT NULL"),
+				(("foo", "name"), "text"),
+				(("foo", "notes"), "text"),
+				(("unrelated", "id"), "bigint"),
+			]
+			.into_iter()
+			.map(|((t, c), d)| ((t.into(), c.into()), d.into()))
+			.collect(),
+			constraints: [(("foo", "foo_pkey"), "PRIMARY KEY (id, name)")]
+				.into_iter()
+				.map(|((o, n), d)| ((o.into(), n.into()), d.into()))
+				.collect(),
+		};
+
+		assert_eq!(expected.diff(&found), vec![
+			SchemaDifference::MissingTable("bar".into()),
+			SchemaDifference::ColumnType {
+				table: "foo".into(),
+				column: "name".into(),
+				expected: "text NOT NULL".into(),
+				found: "text".into(),
+			},
+			SchemaDifference::UnexpectedColumn {
+				table: "foo".into(),
+				column: "notes".into(),
+			},
+			SchemaDifference::ConstraintDefinition {
+				owner: "foo".into(),
+				name: "foo_pkey".into(),
+				expected: "PRIMARY KEY (id)".into(),
+				found: "PRIMARY KEY (id, name)".into(),
+			},
+		]);
+	}
+
+	#[tokio::test]
+	async fn drift()
+	{
+		let connection = util::connect().await;
+
+		PgSchema::migrate(&connection).await.unwrap();
+		assert_eq!(PgSchema::drift(&connection).await.unwrap(), Vec::new());
+	}
+}
@@ -0,0 +1,57 @@
+use core::fmt::{Display, Formatter, Result};
+
+use super::{SchemaDifference, SchemaDrift};
+
+impl Display for SchemaDifference
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result
+	{
+		match self
+		{
+			Self::ColumnType {
+				table,
+				column,
+				expected,
+				found,
+			} => write!(
+				f,
+				"column `{table}.{column}` should be `{expected}`, but is `{found}`"
+			),
+			Self::ConstraintDefinition {
+				owner,
+				name,
+				expected,
+				found,
+			} => write!(
+				f,
+				"constraint `{name}` on `{owner}` should be `{expected}`, but is `{found}`"
+			),
+			Self::MissingColumn { table, column } => write!(f, "column `{table}.{column}` is missing"),
+			Self::MissingConstraint { owner, name } =>
+			{
+				write!(f, "constraint `{name}` on `{owner}` is missing")
+			},
+			Self::MissingTable(table) => write!(f, "table `{table}` is missing"),
+			Self::UnexpectedColumn { table, column } =>
+			{
+				write!(f, "column `{table}.{column}` is not part of the schema")
+			},
+			Self::UnexpectedConstraint { owner, name } =>
+			{
+				write!(
+					f,
+					"constraint `{name}` on `{owner}` is not part of the schema"
+				)
+			},
+		}
+	}
+}
+
+impl Display for SchemaDrift
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result
+	{
+		write!(f, "the database schema has drifted from what was expected:")?;
+		self.0.iter().try_for_each(|d| write!(f, "\n* {d}"))
+	}
+}
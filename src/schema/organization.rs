@@ -3,11 +3,11 @@ mod organization_adapter;
 mod retrievable;
 mod updatable;
 
-use clinvoice_adapter::schema::columns::OrganizationColumns;
-use clinvoice_schema::Organization;
-use sqlx::{postgres::PgRow, Executor, Postgres, Result, Row};
+use std::collections::HashMap;
 
-use super::PgLocation;
+use clinvoice_adapter::schema::columns::OrganizationColumns;
+use clinvoice_schema::{Id, Location, Organization};
+use sqlx::{postgres::PgRow, Error, Result, Row};
 
 /// Implementor of the [`OrganizationAdapter`](clinvoice_adapter::schema::OrganizationAdapter) for the
 /// [`Postgres`](sqlx::Postgres) database.
@@ -15,20 +15,37 @@ pub struct PgOrganization;
 
 impl PgOrganization
 {
-	pub(super) async fn row_to_view<'c, TConn, TColumn>(
-		connection: TConn,
+	/// Get the `location_id` of the [`Organization`] in some `row`, so that its [`Location`] can be
+	/// [retrieved](super::PgLocation::retrieve_by_ids) before calling
+	/// [`PgOrganization::row_to_view`].
+	pub(super) fn location_id_of<TColumn>(
+		columns: &OrganizationColumns<TColumn>,
+		row: &PgRow,
+	) -> Result<Id>
+	where
+		TColumn: AsRef<str>,
+	{
+		row.try_get(columns.location_id.as_ref())
+	}
+
+	/// Create an [`Organization`] from some `row`, whose [`Location`] must be one of the
+	/// `locations`.
+	pub(super) fn row_to_view<TColumn>(
 		columns: OrganizationColumns<TColumn>,
+		locations: &HashMap<Id, Location>,
 		row: &PgRow,
 	) -> Result<Organization>
 	where
-		TConn: Executor<'c, Database = Postgres>,
 		TColumn: AsRef<str>,
 	{
-		let location_id = row.try_get(columns.location_id.as_ref())?;
+		let location_id = Self::location_id_of(&columns, row)?;
 		Ok(Organization {
 			id: row.try_get(columns.id.as_ref())?,
 			name: row.try_get(columns.name.as_ref())?,
-			location: PgLocation::retrieve_by_id(connection, location_id).await?,
+			location: locations
+				.get(&location_id)
+				.cloned()
+				.ok_or(Error::RowNotFound)?,
 		})
 	}
 }
@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::OrganizationColumns, Deletable};
 use clinvoice_schema::{Id, Organization};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Executor, Postgres, Result, Transaction};
 
 use super::PgOrganization;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, PgTimesheet, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgOrganization
@@ -28,6 +31,77 @@ impl Deletable for PgOrganization
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgOrganization
+{
+	type Entity = Organization;
+
+	/// Soft delete the `entities` (via `connection`), along with their
+	/// [`Job`](clinvoice_schema::Job)s (and their children) which were not already soft deleted.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(o: &Organization) -> Id
+		{
+			o.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Organization| e.id`
+		PgSchema::set_deleted::<_, _, OrganizationColumns<char>>(
+			connection,
+			entities.map(mapper),
+			true,
+		)
+		.await
+		.map_err(Error::from)
+	}
+
+	/// Restore the `entities` (via `connection`), along with the [`Job`](clinvoice_schema::Job)s
+	/// (and their children) which were soft deleted at the same time as them.
+	///
+	/// # Errors
+	///
+	/// * A [`TimesheetOverlap`](crate::schema::TimesheetOverlap) if any of the
+	///   [`Timesheet`](clinvoice_schema::Timesheet)s which are restored would overlap another
+	///   [`Timesheet`](clinvoice_schema::Timesheet) of the same
+	///   [`Employee`](clinvoice_schema::Employee).
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(o: &Organization) -> Id
+		{
+			o.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Organization| e.id`
+		let ids: Vec<_> = entities.map(mapper).collect();
+		PgTimesheet::restore_cascading::<OrganizationColumns<char>>(
+			connection,
+			&ids,
+			"SELECT T.id, T.employee_id, T.time_begin, T.time_end
+			FROM timesheets T
+			JOIN jobs J ON (J.id = T.job_id)
+			JOIN organizations O ON (O.id = J.client_id)
+			WHERE O.id = ANY($1) AND J.deleted_at = O.deleted_at AND T.deleted_at = J.deleted_at;",
+		)
+		.await
+		.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -36,10 +110,12 @@ mod tests
 		Deletable,
 		Retrievable,
 	};
-	use clinvoice_match::Match;
+	use clinvoice_finance::{Currency, Money};
+	use clinvoice_match::{Match, MatchJob, MatchOrganization};
+	use clinvoice_schema::chrono::{TimeZone, Utc};
 	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgLocation, PgOrganization};
+	use crate::schema::{util, PgJob, PgLocation, PgOrganization, SoftDeletable};
 
 	#[tokio::test]
 	async fn delete()
@@ -76,7 +152,8 @@ mod tests
 					organization.id.into(),
 					organization2.id.into(),
 					organization3.id.into()
-				]).into(),
+				])
+				.into(),
 			)
 			.await
 			.unwrap()
@@ -84,4 +161,54 @@ mod tests
 			&[organization3]
 		);
 	}
+
+	#[tokio::test]
+	async fn soft_delete()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let job = util::create_job(
+			&connection,
+			organization.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		let match_job: MatchJob = Match::from(job.id).into();
+		let match_organization: MatchOrganization = Match::from(organization.id).into();
+
+		PgOrganization::soft_delete(&connection, [&organization].into_iter())
+			.await
+			.unwrap();
+
+		// The jobs of a soft deleted organization are soft deleted with it.
+		assert!(PgOrganization::retrieve(&connection, &match_organization)
+			.await
+			.unwrap()
+			.is_empty());
+		assert!(PgJob::retrieve(&connection, &match_job)
+			.await
+			.unwrap()
+			.is_empty());
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		PgOrganization::restore(&mut transaction, [&organization].into_iter())
+			.await
+			.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgOrganization::retrieve(&connection, &match_organization)
+				.await
+				.unwrap(),
+			[organization],
+		);
+		assert_eq!(PgJob::retrieve(&connection, &match_job).await.unwrap(), [
+			job
+		]);
+	}
 }
@@ -3,6 +3,7 @@ use clinvoice_schema::{Location, Organization};
 use sqlx::{Executor, Postgres, Result};
 
 use super::PgOrganization;
+use crate::schema::util;
 
 #[async_trait::async_trait]
 impl OrganizationAdapter for PgOrganization
@@ -21,7 +22,14 @@ impl OrganizationAdapter for PgOrganization
 			name
 		)
 		.fetch_one(connection)
-		.await?;
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"location_id" => Some(location.id.to_string()),
+				_ => None,
+			})
+		})?;
 
 		Ok(Organization {
 			id: row.id,
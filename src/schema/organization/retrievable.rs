@@ -2,15 +2,25 @@ use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
 	schema::columns::{LocationColumns, OrganizationColumns},
 	Retrievable,
+	WriteContext,
 	WriteWhereClause,
 };
 use clinvoice_match::MatchOrganization;
-use clinvoice_schema::Organization;
-use futures::TryStreamExt;
-use sqlx::{Pool, Postgres, Result};
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Organization,
+};
+use futures::{future, stream::BoxStream};
+use sqlx::{postgres::PgRow, Executor, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgOrganization;
-use crate::{fmt::PgLocationRecursiveCte, schema::PgLocation, PgSchema};
+use crate::{
+	fmt::PgLocationRecursiveCte,
+	schema::{util, Error, PgAudit, PgLocation, RetrieveOptions},
+	PgSchema,
+};
+
+const COLUMNS: OrganizationColumns<&'static str> = OrganizationColumns::default();
 
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
@@ -29,15 +39,195 @@ impl Retrievable for PgOrganization
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: OrganizationColumns<&'static str> = OrganizationColumns::default();
+		Self::retrieve_via(connection, match_condition, &Default::default()).await
+	}
+}
+
+impl PgOrganization
+{
+	/// Count the [`Organization`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchOrganization,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, None, |q| {
+			q.push(util::COUNT);
+		});
+
+		query
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Check whether any [`Organization`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchOrganization,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, None, |q| {
+			q.push(util::EXISTS);
+		});
+
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Organization`]s are retrieved as they
+	/// were `as_of` some time (via `connection`), rather than as they are now.
+	///
+	/// # See also
+	///
+	/// * [`RetrieveOptions::as_of`], to sort and paginate them as well.
+	pub async fn retrieve_as_of(
+		connection: &Pool<Postgres>,
+		as_of: DateTime<Utc>,
+		match_condition: &MatchOrganization,
+	) -> Result<Vec<Organization>, Error>
+	{
+		Self::retrieve_with_options(connection, match_condition, &RetrieveOptions {
+			as_of: Some(as_of),
+			..Default::default()
+		})
+		.await
+	}
+
+	/// Generate the query which selects every [`Organization`] that matches the
+	/// `match_condition`, according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchOrganization,
+		options: &RetrieveOptions<OrganizationColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let (mut query, context) = Self::select_matching(
+			match_condition,
+			options.include_deleted,
+			options.as_of,
+			|q| {
+				q.push_columns(&COLUMNS.default_scope());
+			},
+		);
+
+		options.write_where(
+			context,
+			COLUMNS,
+			OrganizationColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		)?;
+		options.write_order(
+			COLUMNS,
+			OrganizationColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		);
+
+		Ok(query)
+	}
+
+	/// Same as [`PgOrganization::retrieve_with_options`], except that the [`Organization`]s are
+	/// yielded as they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchOrganization,
+		options: &'a RetrieveOptions<OrganizationColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Organization, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			move |rows| async move { Self::rows_to_views(connection, &rows).await },
+		)
+	}
+
+	/// Same as [`PgOrganization::retrieve_with_options`], except that any `connection` which can be
+	/// used more than once will do.
+	///
+	/// No matter how many [`Organization`]s match, they are retrieved in a fixed number of queries.
+	async fn retrieve_via<'c, TConn>(
+		connection: TConn,
+		match_condition: &MatchOrganization,
+		options: &RetrieveOptions<OrganizationColumns<&'static str>>,
+	) -> Result<Vec<Organization>>
+	where
+		TConn: Copy + Executor<'c, Database = Postgres>,
+	{
+		let rows = Self::retrieve_query(match_condition, options)?
+			.prepare()
+			.fetch_all(connection)
+			.await?;
+
+		Self::rows_to_views(connection, &rows).await
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Organization`]s are sorted and
+	/// paginated according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchOrganization,
+		options: &RetrieveOptions<OrganizationColumns<&'static str>>,
+	) -> Result<Vec<Organization>, Error>
+	{
+		Self::retrieve_via(connection, match_condition, options)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Convert the `rows` which the [`PgOrganization::retrieve_query`] selected into
+	/// [`Organization`]s, retrieving their [`Location`](clinvoice_schema::Location)s via
+	/// `connection`.
+	async fn rows_to_views<'c, TConn>(connection: TConn, rows: &[PgRow]) -> Result<Vec<Organization>>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		PgLocation::rows_to_views(
+			connection,
+			rows,
+			|row| Self::location_id_of(&COLUMNS, row).map(Some),
+			|locations, row| Self::row_to_view(COLUMNS, locations, row),
+		)
+		.await
+	}
 
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Organization`]
+	/// that matches the `match_condition`.
+	///
+	/// [Soft deleted](crate::schema::SoftDeletable) [`Organization`]s are excluded, unless
+	/// `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is returned so that
+	/// more conditions can be added.
+	///
+	/// When `as_of` is given, the [`AuditedTable`](crate::schema::AuditedTable)s are selected from
+	/// as they were at that time.
+	fn select_matching<'a, TSelect>(
+		match_condition: &'a MatchOrganization,
+		include_deleted: bool,
+		as_of: Option<DateTime<Utc>>,
+		select: TSelect,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
 		let columns = COLUMNS.default_scope();
 		let location_columns = LocationColumns::default().default_scope();
 		let mut query = PgLocation::query_with_recursive(&match_condition.location);
+		if let Some(as_of) = as_of
+		{
+			query.push(',');
+			PgAudit::push_as_of(&mut query, as_of);
+		}
 
+		query.push(sql::SELECT);
+		select(&mut query);
 		query
-			.push(sql::SELECT)
-			.push_columns(&columns)
 			.push_default_from::<OrganizationColumns<char>>()
 			.push_equijoin(
 				PgLocationRecursiveCte::from(&match_condition.location),
@@ -46,21 +236,21 @@ impl Retrievable for PgOrganization
 				columns.location_id,
 			);
 
-		PgSchema::write_where_clause(
+		let context = PgSchema::write_where_clause(
 			Default::default(),
 			OrganizationColumns::<char>::DEFAULT_ALIAS,
 			match_condition,
 			&mut query,
 		);
 
-		query
-			.prepare()
-			.fetch(connection)
-			.and_then(
-				|row| async move { PgOrganization::row_to_view(connection, COLUMNS, &row).await },
-			)
-			.try_collect()
-			.await
+		let context = util::write_not_deleted(
+			context,
+			OrganizationColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
+
+		(query, context)
 	}
 }
 
@@ -134,4 +324,52 @@ mod tests
 			[organization, organization2].into_iter().collect(),
 		);
 	}
+
+	#[tokio::test]
+	async fn retrieve_round_trips()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let mut ids = Vec::new();
+		let mut round_trips = Vec::new();
+
+		for i in 0..5
+		{
+			let location = PgLocation::create(&connection, format!("City {i}"), Some(earth.clone()))
+				.await
+				.unwrap();
+
+			let organization =
+				PgOrganization::create(&connection, location, format!("Organization {i}"))
+					.await
+					.unwrap();
+
+			ids.push(organization.id.into());
+
+			let counter = util::RoundTrips::new(&connection);
+			let retrieved = PgOrganization::retrieve_via(
+				&counter,
+				&MatchOrganization {
+					id: Match::Or(ids.clone()),
+					..Default::default()
+				},
+				&Default::default(),
+			)
+			.await
+			.unwrap();
+
+			assert_eq!(retrieved.len(), ids.len());
+			round_trips.push(counter.count());
+		}
+
+		// The number of round-trips must not grow with the number of rows retrieved.
+		assert!(
+			round_trips.windows(2).all(|w| w[0] == w[1]),
+			"{round_trips:?}"
+		);
+	}
 }
@@ -3,7 +3,10 @@ use clinvoice_schema::Organization;
 use sqlx::{Postgres, Result, Transaction};
 
 use super::PgOrganization;
-use crate::{schema::PgLocation, PgSchema};
+use crate::{
+	schema::{util, PgLocation},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Updatable for PgOrganization
@@ -35,7 +38,16 @@ impl Updatable for PgOrganization
 					.push_bind(&e.name);
 			});
 		})
-		.await?;
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| {
+				util::sole_value_of(entities.clone(), |o| match field
+				{
+					"location_id" => Some(o.location.id.to_string()),
+					_ => None,
+				})
+			})
+		})?;
 
 		PgLocation::update(connection, entities.map(|e| &e.location)).await
 	}
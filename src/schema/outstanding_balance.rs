@@ -0,0 +1,29 @@
+use clinvoice_finance::Money;
+use clinvoice_schema::Id;
+
+/// How much of what was billed for some [`Job`](clinvoice_schema::Job) has not been paid yet.
+///
+/// There is one [`OutstandingBalance`] for each currency that the
+/// [`Job`](clinvoice_schema::Job) was billed or paid in.
+///
+/// # See also
+///
+/// * [`PgJob::retrieve_outstanding_balances`](crate::schema::PgJob::retrieve_outstanding_balances),
+///   which computes these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OutstandingBalance
+{
+	/// The sum of the [`InvoiceLine`](crate::schema::InvoiceLine)s of every
+	/// [`IssuedInvoice`](crate::schema::IssuedInvoice) of the [`Job`](clinvoice_schema::Job).
+	pub billed: Money,
+
+	/// The [`Id`] of the [`Job`](clinvoice_schema::Job) which was billed.
+	pub job_id: Id,
+
+	/// The `billed` amount, less the `paid` amount. Negative when the client has overpaid.
+	pub outstanding: Money,
+
+	/// The sum of the [`Payment`](crate::schema::Payment)s made for the
+	/// [`Job`](clinvoice_schema::Job).
+	pub paid: Money,
+}
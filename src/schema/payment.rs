@@ -0,0 +1,260 @@
+mod deletable;
+mod retrievable;
+mod updatable;
+
+use clinvoice_finance::Money;
+use clinvoice_match::{Match, MatchOption, MatchStr};
+use clinvoice_schema::{
+	chrono::{DateTime, NaiveDateTime, Utc},
+	Id,
+	Job,
+};
+use sqlx::{postgres::PgRow, Executor, Postgres, Result, Row};
+
+use super::{util, Error, IssuedInvoice};
+use crate::fmt::DateTimeExt;
+
+/// [`Money`] which a client paid towards a [`Job`], and possibly a specific [`IssuedInvoice`] of
+/// that [`Job`].
+///
+/// A [`Job`] (or [`IssuedInvoice`]) may be paid for in any number of [`Payment`]s.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Payment
+{
+	/// The [`Money`] which was paid.
+	pub amount: Money,
+
+	/// The date upon which the [`Payment`] was received.
+	pub date: DateTime<Utc>,
+
+	/// The [`Id`] which the database assigned to this [`Payment`], which is unique among all
+	/// [`Payment`]s. See `reference` for what the client called it.
+	pub id: Id,
+
+	/// The [`Id`] of the [`IssuedInvoice`] which was paid, if any.
+	pub invoice_id: Option<Id>,
+
+	/// The [`Id`] of the [`Job`] which was paid for.
+	pub job_id: Id,
+
+	/// How the [`Payment`] was made (e.g. "Bank transfer").
+	pub method: String,
+
+	/// The reference which the client gave to the [`Payment`] (e.g. a check number).
+	pub reference: String,
+}
+
+/// A [`Payment`] with [matchable](clinvoice_match) fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchPayment
+{
+	#[allow(missing_docs)]
+	pub amount: Match<Money>,
+
+	#[allow(missing_docs)]
+	pub date: Match<NaiveDateTime>,
+
+	#[allow(missing_docs)]
+	pub id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub invoice_id: MatchOption<Id>,
+
+	#[allow(missing_docs)]
+	pub job_id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub method: MatchStr<String>,
+
+	#[allow(missing_docs)]
+	pub reference: MatchStr<String>,
+}
+
+impl From<Id> for MatchPayment
+{
+	fn from(id: Id) -> Self
+	{
+		Self {
+			id: id.into(),
+			..Default::default()
+		}
+	}
+}
+
+/// Implementor of the [`Deletable`](clinvoice_adapter::Deletable),
+/// [`Retrievable`](clinvoice_adapter::Retrievable), and [`Updatable`](clinvoice_adapter::Updatable)
+/// traits for [`Payment`]s stored in the [`Postgres`] database.
+pub struct PgPayment;
+
+impl PgPayment
+{
+	/// Record (via `connection`) that an `amount` was paid for the `job` on the `date`.
+	///
+	/// When the `invoice` is [`Some`], the [`Payment`] is towards that [`IssuedInvoice`]
+	/// specifically, and it must be one of the `job`'s.
+	pub async fn create<'c, TConn>(
+		connection: TConn,
+		job: &Job,
+		invoice: Option<&IssuedInvoice>,
+		amount: Money,
+		date: DateTime<Utc>,
+		method: String,
+		reference: String,
+	) -> Result<Payment, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let invoice_id = invoice.map(|i| i.id);
+		let row = sqlx::query!(
+			"INSERT INTO payments
+				(job_id, invoice_id, amount, amount_currency, date, method, reference)
+			VALUES
+				($1,     $2,         $3,     $4,              $5,   $6,     $7)
+			RETURNING id;",
+			job.id,
+			invoice_id,
+			amount.amount,
+			amount.currency.to_string() as _,
+			date,
+			method,
+			reference,
+		)
+		.fetch_one(connection)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"amount" => Some(amount.amount.to_string()),
+				"invoice_id" => invoice_id.map(|id| id.to_string()),
+				"job_id" => Some(job.id.to_string()),
+				_ => None,
+			})
+		})?;
+
+		Ok(Payment {
+			amount,
+			date: date.pg_sanitize(),
+			id: row.id,
+			invoice_id,
+			job_id: job.id,
+			method,
+			reference,
+		})
+	}
+
+	/// Create a [`Payment`] from some `row` which [`Retrievable::retrieve`] selected.
+	///
+	/// [`Retrievable::retrieve`]: clinvoice_adapter::Retrievable::retrieve
+	pub(super) fn row_to_view(row: &PgRow) -> Result<Payment>
+	{
+		Ok(Payment {
+			amount: util::money_from_row(row, "amount")?,
+			date: row.try_get("date")?,
+			id: row.try_get("id")?,
+			invoice_id: row.try_get("invoice_id")?,
+			job_id: row.try_get("job_id")?,
+			method: row.try_get("method")?,
+			reference: row.try_get("reference")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::PgPayment;
+	use crate::schema::{util, PgInvoice};
+
+	#[tokio::test]
+	async fn create()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (job, job2) = futures::join!(
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+		let invoice = PgInvoice::create(
+			&mut transaction,
+			&job,
+			Utc.ymd(2094, 01, 01).and_hms(09, 00, 00),
+			&[],
+			&[],
+			&[],
+		)
+		.await
+		.unwrap();
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let payment = PgPayment::create(
+			&connection,
+			&job,
+			Some(&invoice),
+			Money::new(10_00, 2, Currency::Usd),
+			Utc.ymd(2094, 01, 15).and_hms(09, 00, 00),
+			"Check".into(),
+			"#1234".into(),
+		)
+		.await
+		.unwrap();
+
+		let row = sqlx::query!(
+			r#"SELECT
+					amount,
+					amount_currency AS "amount_currency!",
+					invoice_id,
+					job_id,
+					method,
+					reference
+				FROM payments
+				WHERE id = $1;"#,
+			payment.id,
+		)
+		.fetch_one(&connection)
+		.await
+		.unwrap();
+
+		assert_eq!(payment.amount.amount, row.amount);
+		assert_eq!(payment.amount.currency.to_string(), row.amount_currency);
+		assert_eq!(payment.invoice_id, row.invoice_id);
+		assert_eq!(payment.job_id, row.job_id);
+		assert_eq!(payment.method, row.method);
+		assert_eq!(payment.reference, row.reference);
+
+		// An invoice can only be paid as part of its own job.
+		assert!(PgPayment::create(
+			&connection,
+			&job2,
+			Some(&invoice),
+			Money::new(10_00, 2, Currency::Usd),
+			Utc.ymd(2094, 01, 15).and_hms(09, 00, 00),
+			"Check".into(),
+			"#1235".into(),
+		)
+		.await
+		.is_err());
+	}
+}
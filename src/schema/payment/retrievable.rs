@@ -0,0 +1,43 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Retrievable, WriteWhereClause};
+use futures::{future, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result};
+
+use super::{MatchPayment, Payment, PgPayment};
+use crate::PgSchema;
+
+/// Implementors of this trait are capable of being retrieved from a [`Database`].
+#[async_trait::async_trait]
+impl Retrievable for PgPayment
+{
+	/// The [`Database`] where data of type [`Updatable::Entity`] is being stored.
+	type Db = Postgres;
+	/// The type of data that is to be [`update`](Deletable::update)d.
+	type Entity = Payment;
+	/// The type used for [match](clinvoice_match)ing.
+	type Match = MatchPayment;
+
+	/// Retrieve all [`Payment`]s (via `connection`) that match the `match_condition`.
+	///
+	/// The [`Payment`]s are sorted by their `date`.
+	async fn retrieve(
+		connection: &Pool<Postgres>,
+		match_condition: &Self::Match,
+	) -> Result<Vec<Self::Entity>>
+	{
+		let mut query = QueryBuilder::new(
+			"SELECT P.id, P.job_id, P.invoice_id, P.amount, P.amount_currency, P.date, P.method,
+				P.reference
+			FROM payments P",
+		);
+
+		PgSchema::write_where_clause(Default::default(), 'P', match_condition, &mut query);
+
+		query
+			.push(" ORDER BY P.date, P.id;")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgPayment::row_to_view(&row)))
+			.try_collect()
+			.await
+	}
+}
@@ -0,0 +1,181 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Updatable};
+use sqlx::{Postgres, QueryBuilder, Result, Transaction};
+
+use super::{Payment, PgPayment};
+use crate::{fmt::DateTimeExt, schema::util};
+
+#[async_trait::async_trait]
+impl Updatable for PgPayment
+{
+	type Db = Postgres;
+	type Entity = Payment;
+
+	async fn update<'e, 'i, TIter>(
+		connection: &mut Transaction<Self::Db>,
+		entities: TIter,
+	) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.clone().peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(
+			"UPDATE payments AS P SET
+				amount = V.amount,
+				amount_currency = V.amount_currency,
+				date = V.date,
+				invoice_id = V.invoice_id,
+				job_id = V.job_id,
+				method = V.method,
+				reference = V.reference
+			FROM (",
+		);
+
+		query
+			.push_values(peekable_entities, |mut q, e| {
+				q.push_bind(e.id)
+					.push_bind(e.amount.amount)
+					.push_bind(e.amount.currency.to_string())
+					.push_bind(e.date.pg_sanitize())
+					.push_bind(e.invoice_id)
+					.push_bind(e.job_id)
+					.push_bind(&e.method)
+					.push_bind(&e.reference);
+			})
+			.push(
+				") AS V (id, amount, amount_currency, date, invoice_id, job_id, method, reference)
+				WHERE P.id = V.id;",
+			)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities, |p| match field
+					{
+						"amount" => Some(p.amount.amount.to_string()),
+						"invoice_id" => p.invoice_id.map(|id| id.to_string()),
+						"job_id" => Some(p.job_id.to_string()),
+						_ => None,
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{schema::JobAdapter, Deletable, Retrievable, Updatable};
+	use clinvoice_match::Match;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Invoice,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, MatchPayment, Payment, PgJob, PgPayment};
+
+	#[tokio::test]
+	async fn update()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let job = PgJob::create(
+			&connection,
+			organization,
+			None,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap();
+
+		let (payment, payment2) = futures::try_join!(
+			PgPayment::create(
+				&connection,
+				&job,
+				None,
+				Money::new(10_00, 2, Currency::Usd),
+				Utc.ymd(1990, 08, 01).and_hms(09, 00, 00),
+				"Check".into(),
+				"#1234".into(),
+			),
+			PgPayment::create(
+				&connection,
+				&job,
+				None,
+				Money::new(25_00, 2, Currency::Usd),
+				Utc.ymd(1990, 09, 01).and_hms(09, 00, 00),
+				"Cash".into(),
+				String::new(),
+			),
+		)
+		.unwrap();
+
+		let match_job = MatchPayment {
+			job_id: job.id.into(),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgPayment::retrieve(&connection, &match_job).await.unwrap(),
+			[payment.clone(), payment2.clone()],
+		);
+
+		let updated = Payment {
+			amount: Money::new(12_50, 2, Currency::Eur),
+			method: "Bank transfer".into(),
+			..payment
+		};
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgPayment::update(&mut transaction, [&updated].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		assert_eq!(
+			PgPayment::retrieve(&connection, &MatchPayment {
+				amount: Match::GreaterThan(Money::new(10_00, 2, Currency::Eur)),
+				..match_job.clone()
+			})
+			.await
+			.unwrap(),
+			[updated],
+		);
+
+		PgPayment::delete(&connection, [&payment2].into_iter())
+			.await
+			.unwrap();
+
+		assert!(PgPayment::retrieve(&connection, &payment2.id.into())
+			.await
+			.unwrap()
+			.is_empty());
+	}
+}
@@ -0,0 +1,20 @@
+use clinvoice_finance::Money;
+use clinvoice_schema::Timesheet;
+
+/// A [`Timesheet`], along with the hourly rate which it is billed at.
+///
+/// # See also
+///
+/// * [`PgTimesheet::retrieve_rated`](crate::schema::PgTimesheet::retrieve_rated), which retrieves
+///   these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RatedTimesheet
+{
+	/// The [`BillingRate`](crate::schema::BillingRate) of the [`Timesheet`]'s
+	/// [`Employee`](clinvoice_schema::Employee) or their `title`, or else the `invoice.hourly_rate`
+	/// of its [`Job`](clinvoice_schema::Job).
+	pub hourly_rate: Money,
+
+	#[allow(missing_docs)]
+	pub timesheet: Timesheet,
+}
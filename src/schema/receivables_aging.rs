@@ -0,0 +1,34 @@
+use clinvoice_finance::Money;
+use clinvoice_schema::Id;
+
+/// How much some client [`Organization`](clinvoice_schema::Organization) owes for the
+/// [`Job`](clinvoice_schema::Job)s which were invoiced but not paid, grouped by how many days ago
+/// they were invoiced.
+///
+/// The days are counted from when each invoice was issued rather than from when it was due, since
+/// no payment terms are recorded.
+///
+/// There is one [`ReceivablesAging`] for each currency that the client owes money in.
+///
+/// # See also
+///
+/// * [`PgJob::retrieve_receivables_aging`](crate::schema::PgJob::retrieve_receivables_aging),
+///   which computes these.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ReceivablesAging
+{
+	/// The [`Id`] of the [`Organization`](clinvoice_schema::Organization) which owes the money.
+	pub client_id: Id,
+
+	/// The amount owed for [`Job`](clinvoice_schema::Job)s invoiced 0–30 days ago.
+	pub days_since_issue_0_to_30: Money,
+
+	/// The amount owed for [`Job`](clinvoice_schema::Job)s invoiced 31–60 days ago.
+	pub days_since_issue_31_to_60: Money,
+
+	/// The amount owed for [`Job`](clinvoice_schema::Job)s invoiced 61–90 days ago.
+	pub days_since_issue_61_to_90: Money,
+
+	/// The amount owed for [`Job`](clinvoice_schema::Job)s invoiced more than 90 days ago.
+	pub days_since_issue_over_90: Money,
+}
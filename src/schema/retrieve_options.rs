@@ -0,0 +1,364 @@
+use core::{fmt::Display, time::Duration};
+
+use clinvoice_adapter::{fmt::sql, WriteContext};
+use clinvoice_finance::Decimal;
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Id,
+};
+use sqlx::{Error, Postgres, QueryBuilder, Result};
+
+/// `LIMIT`, padded with spaces.
+const LIMIT: &str = " LIMIT ";
+
+/// `OFFSET`, padded with spaces.
+const OFFSET: &str = " OFFSET ";
+
+/// `ORDER BY`, padded with spaces.
+const ORDER_BY: &str = " ORDER BY ";
+
+/// The direction in which some column is sorted.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Order
+{
+	/// Smallest first, with `NULL` last.
+	#[default]
+	Ascending,
+
+	/// Largest first, with `NULL` first.
+	Descending,
+}
+
+/// A column to sort retrieved entities by.
+#[derive(Copy, Clone, Debug)]
+pub struct OrderBy<TColumns>
+{
+	/// Select the column to sort by from the columns of the entity (e.g. `|c| c.name`).
+	pub column: fn(TColumns) -> &'static str,
+
+	/// The direction to sort the `column` in.
+	pub order: Order,
+}
+
+impl<TColumns> OrderBy<TColumns>
+{
+	/// Sort by the `column` in [ascending](Order::Ascending) order.
+	pub fn ascending(column: fn(TColumns) -> &'static str) -> Self
+	{
+		Self {
+			column,
+			order: Order::Ascending,
+		}
+	}
+
+	/// Sort by the `column` in [descending](Order::Descending) order.
+	pub fn descending(column: fn(TColumns) -> &'static str) -> Self
+	{
+		Self {
+			column,
+			order: Order::Descending,
+		}
+	}
+}
+
+/// Which of the (sorted) entities should be retrieved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Page
+{
+	/// Retrieve at most `limit` entities, starting after the entity whose sort keys had the values
+	/// in `after`.
+	///
+	/// `after` must contain the value of each [`RetrieveOptions::order_by`] column of the last
+	/// entity which was retrieved, followed by the value of its unique column (i.e. the `label` of
+	/// a [`Contact`](clinvoice_schema::Contact), or the `id` of anything else).
+	Keyset
+	{
+		/// The values of the sort keys of the last entity which was retrieved.
+		after: Vec<SortValue>,
+
+		/// The maximum number of entities to retrieve.
+		limit: i64,
+	},
+
+	/// Retrieve at most `limit` entities, after skipping the first `offset` of them.
+	Offset
+	{
+		/// The maximum number of entities to retrieve.
+		limit: i64,
+
+		/// The number of entities to skip.
+		offset: i64,
+	},
+}
+
+/// The value of some column which an entity was sorted by.
+///
+/// # See also
+///
+/// * [`Page::Keyset`], for where these are used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SortValue
+{
+	/// A `bool` column.
+	Bool(bool),
+
+	/// A `numeric` column.
+	Decimal(Decimal),
+
+	/// An `interval` column.
+	Duration(Duration),
+
+	/// A column which references some entity.
+	Id(Id),
+
+	/// A nullable column, which had no value.
+	Null,
+
+	/// A `text` column.
+	Text(String),
+
+	/// A `timestamptz` column.
+	Timestamp(DateTime<Utc>),
+}
+
+impl SortValue
+{
+	/// Bind this value to the `query`.
+	fn push_bind(&self, query: &mut QueryBuilder<Postgres>)
+	{
+		match self
+		{
+			Self::Bool(b) => query.push_bind(*b),
+			Self::Decimal(d) => query.push_bind(*d),
+			Self::Duration(d) => query.push_bind(*d),
+			Self::Id(id) => query.push_bind(*id),
+			Self::Null => query.push(sql::NULL),
+			Self::Text(s) => query.push_bind(s.clone()),
+			Self::Timestamp(t) => query.push_bind(*t),
+		};
+	}
+}
+
+/// How to sort retrieved entities, and which of them to retrieve.
+///
+/// When the entities are sorted, ties are always broken by their unique column, so that the order
+/// is the same every time they are retrieved.
+#[derive(Clone, Debug)]
+pub struct RetrieveOptions<TColumns>
+{
+	/// Retrieve the entities as they were at this time, rather than as they are now. Only the
+	/// [`AuditedTable`](crate::schema::AuditedTable)s are affected; everything else is retrieved as
+	/// it is now.
+	pub as_of: Option<DateTime<Utc>>,
+
+	/// Whether to retrieve entities which were [soft deleted](crate::schema::SoftDeletable) as
+	/// well. Only the entities which are being retrieved are affected, not those they refer to.
+	pub include_deleted: bool,
+
+	/// The columns to sort by, from highest to lowest priority.
+	pub order_by: Vec<OrderBy<TColumns>>,
+
+	/// Which of the entities to retrieve. All of them are retrieved when this is [`None`].
+	pub page: Option<Page>,
+}
+
+impl<TColumns> Default for RetrieveOptions<TColumns>
+{
+	fn default() -> Self
+	{
+		Self {
+			as_of: None,
+			include_deleted: false,
+			order_by: Vec::new(),
+			page: None,
+		}
+	}
+}
+
+impl<TColumns> RetrieveOptions<TColumns>
+where
+	TColumns: Copy,
+{
+	/// Get each of the `columns` (scoped to the `alias`) which is sorted by, and the [`Order`] it is
+	/// sorted in. The `unique` column is always last.
+	fn sort_keys<TAlias>(
+		&self,
+		columns: TColumns,
+		alias: TAlias,
+		unique: fn(TColumns) -> &'static str,
+	) -> Vec<(String, Order)>
+	where
+		TAlias: Display,
+	{
+		self
+			.order_by
+			.iter()
+			.map(|o| (format!("{alias}.{}", (o.column)(columns)), o.order))
+			.chain([(format!("{alias}.{}", unique(columns)), Order::Ascending)])
+			.collect()
+	}
+
+	/// Append an `ORDER BY`, `LIMIT`, and `OFFSET` to the `query` (depending on the
+	/// [`RetrieveOptions`]), where the `columns` are those of the table with the `alias`.
+	///
+	/// # See also
+	///
+	/// * [`RetrieveOptions::write_where`], which must be called first.
+	pub(super) fn write_order<TAlias>(
+		&self,
+		columns: TColumns,
+		alias: TAlias,
+		unique: fn(TColumns) -> &'static str,
+		query: &mut QueryBuilder<Postgres>,
+	) where
+		TAlias: Display,
+	{
+		if self.order_by.is_empty() && self.page.is_none()
+		{
+			return;
+		}
+
+		{
+			let mut separated = query.push(ORDER_BY).separated(',');
+			self
+				.sort_keys(columns, alias, unique)
+				.into_iter()
+				.for_each(|(column, order)| {
+					separated.push(column).push_unseparated(match order
+					{
+						Order::Ascending => " ASC",
+						Order::Descending => " DESC",
+					});
+				});
+		}
+
+		match self.page
+		{
+			Some(Page::Keyset { limit, .. }) =>
+			{
+				query.push(LIMIT).push_bind(limit);
+			},
+			Some(Page::Offset { limit, offset }) =>
+			{
+				query
+					.push(LIMIT)
+					.push_bind(limit)
+					.push(OFFSET)
+					.push_bind(offset);
+			},
+			None => (),
+		}
+	}
+
+	/// Append a condition to the `query` (in the given `context`) which only matches the entities
+	/// that come after a [`Page::Keyset`], where the `columns` are those of the table with the
+	/// `alias`.
+	///
+	/// For each sort key, the condition is `(a > $1) OR (a = $1 AND b > $2) OR …`, with `<` in
+	/// place of `>` for [`Order::Descending`] columns.
+	///
+	/// # Errors
+	///
+	/// * [`Error::Configuration`] if the `after` of the [`Page::Keyset`] does not have exactly one
+	///   value per sort key.
+	pub(super) fn write_where<TAlias>(
+		&self,
+		context: WriteContext,
+		columns: TColumns,
+		alias: TAlias,
+		unique: fn(TColumns) -> &'static str,
+		query: &mut QueryBuilder<Postgres>,
+	) -> Result<()>
+	where
+		TAlias: Display,
+	{
+		let after = match self.page
+		{
+			Some(Page::Keyset { ref after, .. }) => after,
+			_ => return Ok(()),
+		};
+
+		let sort_keys = self.sort_keys(columns, alias, unique);
+		if after.len() != sort_keys.len()
+		{
+			return Err(Error::Configuration(
+				format!(
+					"the keyset page has {} value(s) after which to start, but the rows are sorted by \
+					 {} key(s)",
+					after.len(),
+					sort_keys.len(),
+				)
+				.into(),
+			));
+		}
+
+		query.push(context).push(" (");
+
+		for (i, ((column, order), value)) in sort_keys.iter().zip(after).enumerate()
+		{
+			if i > 0
+			{
+				query.push(sql::OR);
+			}
+
+			query.push('(');
+
+			// All of the higher priority sort keys are equal…
+			for ((c, _), v) in sort_keys.iter().zip(after).take(i)
+			{
+				query.push(c);
+				if let SortValue::Null = v
+				{
+					query.push(sql::IS).push(sql::NULL);
+				}
+				else
+				{
+					query.push('=');
+					v.push_bind(query);
+				}
+				query.push(sql::AND);
+			}
+
+			// …and this one comes after the `value`.
+			match (order, value)
+			{
+				// `NULL`s are last when ascending, so nothing comes after them.
+				(Order::Ascending, SortValue::Null) =>
+				{
+					query.push(sql::FALSE);
+				},
+				(Order::Ascending, _) =>
+				{
+					query.push('(').push(column).push('>');
+					value.push_bind(query);
+					query
+						.push(sql::OR)
+						.push(column)
+						.push(sql::IS)
+						.push(sql::NULL)
+						.push(')');
+				},
+
+				// `NULL`s are first when descending, so everything else comes after them.
+				(Order::Descending, SortValue::Null) =>
+				{
+					query
+						.push(column)
+						.push(sql::IS)
+						.push(sql::NOT)
+						.push(sql::NULL);
+				},
+				(Order::Descending, _) =>
+				{
+					query.push(column).push('<');
+					value.push_bind(query);
+				},
+			}
+
+			query.push(')');
+		}
+
+		query.push(')');
+		Ok(())
+	}
+}
@@ -0,0 +1,52 @@
+use sqlx::{Executor, Postgres, Transaction};
+
+use super::Error;
+
+/// Implementors of this trait are capable of being deleted from the [`Postgres`] database in a way
+/// that can be undone.
+///
+/// Entities which were soft deleted are hidden from retrieval, unless they are
+/// [explicitly included](super::RetrieveOptions::include_deleted), and so are the entities which
+/// belong to them (e.g. the [`Job`](clinvoice_schema::Job)s of an
+/// [`Organization`](clinvoice_schema::Organization)). Unlike
+/// [`Deletable::delete`](clinvoice_adapter::Deletable::delete), nothing is removed from the
+/// database.
+///
+/// Every entity of [`clinvoice_schema`] can be soft deleted. The [`Adjustment`](super::Adjustment)s,
+/// [`BillingRate`](super::BillingRate)s, [`IssuedInvoice`](super::IssuedInvoice)s,
+/// [`Payment`](super::Payment)s, and [`TaxRate`](super::TaxRate)s of this crate cannot: they are
+/// records of what was billed and paid, so they stay as they were even when the
+/// [`Job`](clinvoice_schema::Job) they belong to is soft deleted.
+#[async_trait::async_trait]
+pub trait SoftDeletable
+{
+	/// The type of data that is to be [`soft_delete`](SoftDeletable::soft_delete)d.
+	type Entity: Sync;
+
+	/// Soft delete the `entities` (via `connection`).
+	///
+	/// Entities which were already soft deleted are unaffected.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send;
+
+	/// Restore the `entities` (via `connection`) which were
+	/// [`soft_delete`](SoftDeletable::soft_delete)d, so that they can be retrieved again.
+	///
+	/// Entities which were not soft deleted are unaffected. The entities which belong to them, and
+	/// were soft deleted along with them, are restored too.
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send;
+}
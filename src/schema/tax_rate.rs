@@ -0,0 +1,351 @@
+mod deletable;
+mod retrievable;
+mod updatable;
+
+use core::fmt::Display;
+
+use clinvoice_adapter::fmt::sql;
+use clinvoice_finance::Decimal;
+use clinvoice_match::{Match, MatchOption, MatchStr};
+use clinvoice_schema::{Expense, Id, Job, Location};
+use sqlx::{postgres::PgRow, Executor, Postgres, QueryBuilder, Result, Row};
+
+use super::{util, Error, TaxedAmount};
+
+/// The alias of the currency of the [`TaxedAmount`] columns.
+const CURRENCY: &str = "currency";
+
+/// The alias of the [`TaxedAmount::gross`] column.
+const GROSS: &str = "gross";
+
+/// The alias of the [`TaxedAmount::net`] column.
+const NET: &str = "net";
+
+/// The alias of the sum of the [`TaxRate::percentage`]s which apply to something.
+const PERCENTAGE: &str = "percentage";
+
+/// The alias of the subquery which sums the [`PERCENTAGE`].
+const PERCENTAGE_ALIAS: &str = "TX";
+
+/// The alias of the [`TaxedAmount::tax`] column.
+const TAX: &str = "tax";
+
+/// A sales tax (e.g. VAT) which can be attached to [`Job`]s and [`Expense`]s.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TaxRate
+{
+	/// The reference number of this [`TaxRate`], which is unique among all [`TaxRate`]s.
+	pub id: Id,
+
+	/// The [`Id`] of the [`Location`] where this [`TaxRate`] is levied, if it is specific to one.
+	pub location_id: Option<Id>,
+
+	/// The name of the [`TaxRate`] (e.g. "VAT"), as it should appear on an invoice.
+	pub name: String,
+
+	/// The percentage of the net amount which is owed in tax (e.g. `20` for 20%).
+	pub percentage: Decimal,
+}
+
+/// A [`TaxRate`] with [matchable](clinvoice_match) fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchTaxRate
+{
+	#[allow(missing_docs)]
+	pub id: Match<Id>,
+
+	#[allow(missing_docs)]
+	pub location_id: MatchOption<Id>,
+
+	#[allow(missing_docs)]
+	pub name: MatchStr<String>,
+
+	#[allow(missing_docs)]
+	pub percentage: Match<Decimal>,
+}
+
+impl From<Id> for MatchTaxRate
+{
+	fn from(id: Id) -> Self
+	{
+		Self {
+			id: id.into(),
+			..Default::default()
+		}
+	}
+}
+
+/// Implementor of the [`Deletable`](clinvoice_adapter::Deletable),
+/// [`Retrievable`](clinvoice_adapter::Retrievable), and [`Updatable`](clinvoice_adapter::Updatable)
+/// traits for [`TaxRate`]s stored in the [`Postgres`] database.
+pub struct PgTaxRate;
+
+impl PgTaxRate
+{
+	/// Create a new [`TaxRate`] (via `connection`), which is levied at the `jurisdiction` (if any).
+	pub async fn create<'c, TConn>(
+		connection: TConn,
+		name: String,
+		percentage: Decimal,
+		jurisdiction: Option<&Location>,
+	) -> Result<TaxRate, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let location_id = jurisdiction.map(|l| l.id);
+		let row = sqlx::query!(
+			"INSERT INTO tax_rates
+				(location_id, name, percentage)
+			VALUES
+				($1,          $2,   $3)
+			RETURNING id;",
+			location_id,
+			name,
+			percentage,
+		)
+		.fetch_one(connection)
+		.await
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"location_id" => location_id.map(|id| id.to_string()),
+				"percentage" => Some(percentage.to_string()),
+				_ => None,
+			})
+		})?;
+
+		Ok(TaxRate {
+			id: row.id,
+			location_id,
+			name,
+			percentage,
+		})
+	}
+
+	/// Attach the `tax_rates` to the `expense` (via `connection`). Any of the `tax_rates` which
+	/// were already attached are ignored.
+	pub async fn attach_to_expense<'c, TConn>(
+		connection: TConn,
+		expense: &Expense,
+		tax_rates: &[TaxRate],
+	) -> Result<(), Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let ids: Vec<_> = tax_rates.iter().map(|r| r.id).collect();
+		sqlx::query!(
+			"INSERT INTO expense_tax_rates (expense_id, tax_rate_id)
+				SELECT $1, unnest($2::bigint[])
+				ON CONFLICT DO NOTHING;",
+			expense.id,
+			&ids,
+		)
+		.execute(connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+
+	/// Attach the `tax_rates` to the `job` (via `connection`). Any of the `tax_rates` which were
+	/// already attached are ignored.
+	pub async fn attach_to_job<'c, TConn>(
+		connection: TConn,
+		job: &Job,
+		tax_rates: &[TaxRate],
+	) -> Result<(), Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let ids: Vec<_> = tax_rates.iter().map(|r| r.id).collect();
+		sqlx::query!(
+			"INSERT INTO job_tax_rates (job_id, tax_rate_id)
+				SELECT $1, unnest($2::bigint[])
+				ON CONFLICT DO NOTHING;",
+			job.id,
+			&ids,
+		)
+		.execute(connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+
+	/// Detach the `tax_rates` from the `expense` (via `connection`).
+	pub async fn detach_from_expense<'c, TConn>(
+		connection: TConn,
+		expense: &Expense,
+		tax_rates: &[TaxRate],
+	) -> Result<(), Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let ids: Vec<_> = tax_rates.iter().map(|r| r.id).collect();
+		sqlx::query!(
+			"DELETE FROM expense_tax_rates WHERE expense_id = $1 AND tax_rate_id = ANY($2);",
+			expense.id,
+			&ids,
+		)
+		.execute(connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+
+	/// Detach the `tax_rates` from the `job` (via `connection`).
+	pub async fn detach_from_job<'c, TConn>(
+		connection: TConn,
+		job: &Job,
+		tax_rates: &[TaxRate],
+	) -> Result<(), Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		let ids: Vec<_> = tax_rates.iter().map(|r| r.id).collect();
+		sqlx::query!(
+			"DELETE FROM job_tax_rates WHERE job_id = $1 AND tax_rate_id = ANY($2);",
+			job.id,
+			&ids,
+		)
+		.execute(connection)
+		.await
+		.map_err(|e| util::validation_or(e, |_| None))?;
+
+		Ok(())
+	}
+
+	/// Append a join to the `query` which sums the [`TaxRate::percentage`]s that are attached (via
+	/// the `link_table`) to the entity whose `id` is stored in the `link_column`.
+	pub(super) fn push_percentage_join(
+		query: &mut QueryBuilder<Postgres>,
+		link_table: &str,
+		link_column: &str,
+		id: impl Display,
+	)
+	{
+		query
+			.push(" CROSS JOIN LATERAL (SELECT COALESCE(sum(TR.percentage), 0)")
+			.push(sql::AS)
+			.push(PERCENTAGE)
+			.push(sql::FROM)
+			.push(link_table)
+			.push(" L JOIN tax_rates TR ON (TR.id = L.tax_rate_id) WHERE L.")
+			.push(link_column)
+			.push(" = ")
+			.push(id)
+			.push(')')
+			.push(sql::AS)
+			.push(PERCENTAGE_ALIAS);
+	}
+
+	/// Append the columns of a [`TaxedAmount`] to the `query`, where the `net` amount is in the
+	/// `currency`, and the [`PgTaxRate::push_percentage_join`] was made.
+	pub(super) fn push_taxed_amount_columns(
+		query: &mut QueryBuilder<Postgres>,
+		net: impl Display,
+		currency: impl Display,
+	)
+	{
+		let tax = format!("round({net} * {PERCENTAGE_ALIAS}.{PERCENTAGE} / 100, 2)");
+		query
+			.push(currency)
+			.push(sql::AS)
+			.push(CURRENCY)
+			.push(',')
+			.push(&net)
+			.push(sql::AS)
+			.push(NET)
+			.push(',')
+			.push(&tax)
+			.push(sql::AS)
+			.push(TAX)
+			.push(',')
+			.push(net)
+			.push('+')
+			.push(tax)
+			.push(sql::AS)
+			.push(GROSS);
+	}
+
+	/// Create a [`TaxedAmount`] from some `row` which selected the
+	/// [`PgTaxRate::push_taxed_amount_columns`] and the `id` column.
+	pub(super) fn row_to_taxed_amount(row: &PgRow, id: &str) -> Result<TaxedAmount>
+	{
+		let currency = row.try_get::<String, _>(CURRENCY)?;
+		let money = |column| {
+			row.try_get(column)
+				.and_then(|a| util::money_from(a, &currency))
+		};
+
+		Ok(TaxedAmount {
+			gross: money(GROSS)?,
+			id: row.try_get(id)?,
+			net: money(NET)?,
+			tax: money(TAX)?,
+		})
+	}
+
+	/// Create a [`TaxRate`] from some `row` which [`Retrievable::retrieve`] selected.
+	///
+	/// [`Retrievable::retrieve`]: clinvoice_adapter::Retrievable::retrieve
+	pub(super) fn row_to_view(row: &PgRow) -> Result<TaxRate>
+	{
+		Ok(TaxRate {
+			id: row.try_get("id")?,
+			location_id: row.try_get("location_id")?,
+			name: row.try_get("name")?,
+			percentage: row.try_get("percentage")?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::schema::LocationAdapter;
+	use clinvoice_finance::Decimal;
+	use pretty_assertions::assert_eq;
+
+	use super::PgTaxRate;
+	use crate::schema::{util, PgLocation};
+
+	#[tokio::test]
+	async fn create()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+
+		let tax_rate = PgTaxRate::create(
+			&connection,
+			"VAT".into(),
+			Decimal::new(20_0, 1),
+			Some(&earth),
+		)
+		.await
+		.unwrap();
+
+		let row = sqlx::query!(
+			"SELECT location_id, name, percentage FROM tax_rates WHERE id = $1;",
+			tax_rate.id,
+		)
+		.fetch_one(&connection)
+		.await
+		.unwrap();
+
+		assert_eq!(tax_rate.location_id, row.location_id);
+		assert_eq!(tax_rate.name, row.name);
+		assert_eq!(tax_rate.percentage, row.percentage);
+
+		// Tax cannot be negative.
+		assert!(
+			PgTaxRate::create(&connection, "Rebate".into(), Decimal::NEGATIVE_ONE, None)
+				.await
+				.is_err()
+		);
+	}
+}
@@ -0,0 +1,40 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Retrievable, WriteWhereClause};
+use futures::{future, TryStreamExt};
+use sqlx::{Pool, Postgres, QueryBuilder, Result};
+
+use super::{MatchTaxRate, PgTaxRate, TaxRate};
+use crate::PgSchema;
+
+/// Implementors of this trait are capable of being retrieved from a [`Database`].
+#[async_trait::async_trait]
+impl Retrievable for PgTaxRate
+{
+	/// The [`Database`] where data of type [`Updatable::Entity`] is being stored.
+	type Db = Postgres;
+	/// The type of data that is to be [`update`](Deletable::update)d.
+	type Entity = TaxRate;
+	/// The type used for [match](clinvoice_match)ing.
+	type Match = MatchTaxRate;
+
+	/// Retrieve all [`TaxRate`]s (via `connection`) that match the `match_condition`.
+	///
+	/// The [`TaxRate`]s are sorted by their `name`.
+	async fn retrieve(
+		connection: &Pool<Postgres>,
+		match_condition: &Self::Match,
+	) -> Result<Vec<Self::Entity>>
+	{
+		let mut query =
+			QueryBuilder::new("SELECT R.id, R.location_id, R.name, R.percentage FROM tax_rates R");
+
+		PgSchema::write_where_clause(Default::default(), 'R', match_condition, &mut query);
+
+		query
+			.push(" ORDER BY R.name, R.id;")
+			.prepare()
+			.fetch(connection)
+			.and_then(|row| future::ready(PgTaxRate::row_to_view(&row)))
+			.try_collect()
+			.await
+	}
+}
@@ -0,0 +1,144 @@
+use clinvoice_adapter::{fmt::QueryBuilderExt, Updatable};
+use sqlx::{Postgres, QueryBuilder, Result, Transaction};
+
+use super::{PgTaxRate, TaxRate};
+use crate::schema::util;
+
+#[async_trait::async_trait]
+impl Updatable for PgTaxRate
+{
+	type Db = Postgres;
+	type Entity = TaxRate;
+
+	async fn update<'e, 'i, TIter>(
+		connection: &mut Transaction<Self::Db>,
+		entities: TIter,
+	) -> Result<()>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		let mut peekable_entities = entities.clone().peekable();
+
+		// There is nothing to do.
+		if peekable_entities.peek().is_none()
+		{
+			return Ok(());
+		}
+
+		let mut query = QueryBuilder::new(
+			"UPDATE tax_rates AS R SET
+				location_id = V.location_id,
+				name = V.name,
+				percentage = V.percentage
+			FROM (",
+		);
+
+		query
+			.push_values(peekable_entities, |mut q, e| {
+				q.push_bind(e.id)
+					.push_bind(e.location_id)
+					.push_bind(&e.name)
+					.push_bind(e.percentage);
+			})
+			.push(
+				") AS V (id, location_id, name, percentage)
+				WHERE R.id = V.id;",
+			)
+			.prepare()
+			.execute(connection)
+			.await
+			.map_err(|e| {
+				util::validation_or(e, |field| {
+					util::sole_value_of(entities, |r| match field
+					{
+						"location_id" => r.location_id.map(|id| id.to_string()),
+						"percentage" => Some(r.percentage.to_string()),
+						_ => None,
+					})
+				})
+			})?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::{Deletable, Retrievable, Updatable};
+	use clinvoice_finance::Decimal;
+	use clinvoice_match::{Match, MatchStr};
+	use pretty_assertions::assert_eq;
+
+	use crate::schema::{util, MatchTaxRate, PgTaxRate, TaxRate};
+
+	#[tokio::test]
+	async fn update()
+	{
+		let connection = util::connect().await;
+
+		let (tax_rate, tax_rate2) = futures::try_join!(
+			PgTaxRate::create(
+				&connection,
+				"Some Sales Tax".into(),
+				Decimal::new(6_25, 2),
+				None
+			),
+			PgTaxRate::create(
+				&connection,
+				"Some Other Sales Tax".into(),
+				Decimal::new(2_00, 2),
+				None
+			),
+		)
+		.unwrap();
+
+		let match_tax_rate = MatchTaxRate {
+			id: Match::Or(vec![tax_rate.id.into(), tax_rate2.id.into()]),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgTaxRate::retrieve(&connection, &match_tax_rate)
+				.await
+				.unwrap(),
+			[tax_rate2.clone(), tax_rate.clone()],
+		);
+
+		let updated = TaxRate {
+			name: "Some Increased Sales Tax".into(),
+			percentage: Decimal::new(7_00, 2),
+			..tax_rate
+		};
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgTaxRate::update(&mut transaction, [&updated].into_iter())
+				.await
+				.unwrap();
+			transaction.commit().await.unwrap();
+		}
+
+		assert_eq!(
+			PgTaxRate::retrieve(&connection, &MatchTaxRate {
+				name: MatchStr::Contains("Increased".into()),
+				percentage: Match::GreaterThan(Decimal::new(6_50, 2)),
+				..match_tax_rate.clone()
+			})
+			.await
+			.unwrap(),
+			[updated],
+		);
+
+		PgTaxRate::delete(&connection, [&tax_rate2].into_iter())
+			.await
+			.unwrap();
+
+		assert!(PgTaxRate::retrieve(&connection, &tax_rate2.id.into())
+			.await
+			.unwrap()
+			.is_empty());
+	}
+}
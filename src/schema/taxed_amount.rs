@@ -0,0 +1,28 @@
+use clinvoice_finance::Money;
+use clinvoice_schema::Id;
+
+/// The amount billed for some [`Job`](clinvoice_schema::Job) or
+/// [`Expense`](clinvoice_schema::Expense), before and after the
+/// [`TaxRate`](crate::schema::TaxRate)s which were attached to it.
+///
+/// # See also
+///
+/// * [`PgExpenses::retrieve_taxed_amounts`](crate::schema::PgExpenses::retrieve_taxed_amounts)
+/// * [`PgJob::retrieve_taxed_amounts`](crate::schema::PgJob::retrieve_taxed_amounts)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TaxedAmount
+{
+	/// The `net` amount, plus the `tax`.
+	pub gross: Money,
+
+	/// The [`Id`] of the [`Job`](clinvoice_schema::Job) or [`Expense`](clinvoice_schema::Expense)
+	/// which was taxed.
+	pub id: Id,
+
+	/// The amount before any tax.
+	pub net: Money,
+
+	/// The sum of every [`TaxRate`](crate::schema::TaxRate) applied to the `net` amount, rounded to
+	/// two decimal places.
+	pub tax: Money,
+}
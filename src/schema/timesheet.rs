@@ -3,17 +3,39 @@ mod retrievable;
 mod timesheet_adapter;
 mod updatable;
 
-use clinvoice_adapter::schema::columns::{
-	EmployeeColumns,
-	JobColumns,
-	OrganizationColumns,
-	TimesheetColumns,
+use std::collections::HashMap;
+
+use clinvoice_adapter::{
+	fmt::{QueryBuilderExt, TableToSql},
+	schema::{
+		columns::{EmployeeColumns, JobColumns, OrganizationColumns, TimesheetColumns},
+		TimesheetAdapter,
+	},
+};
+use clinvoice_finance::Money;
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Employee,
+	Expense,
+	Id,
+	Job,
+	Location,
+	Timesheet,
+};
+use sqlx::{
+	error::UnexpectedNullError,
+	postgres::PgRow,
+	Connection,
+	Executor,
+	Postgres,
+	QueryBuilder,
+	Result,
+	Row,
+	Transaction,
 };
-use clinvoice_finance::{Decimal, Money};
-use clinvoice_schema::{Expense, Timesheet};
-use sqlx::{error::UnexpectedNullError, postgres::PgRow, Error, Executor, Postgres, Result, Row};
 
-use super::{util, PgEmployee, PgJob};
+use super::{util, Error, PgEmployee, PgJob, TimesheetOverlap};
+use crate::PgSchema;
 
 /// Implementor of the [`TimesheetAdapter`](clinvoice_adapter::schema::TimesheetAdapter) for the
 /// [`Postgres`](sqlx::Postgres) database.
@@ -21,32 +43,230 @@ pub struct PgTimesheet;
 
 impl PgTimesheet
 {
-	pub(super) async fn row_to_view<
-		'c,
-		TConn,
-		TEmpColumns,
-		TJobColumns,
-		TOrgColumns,
-		TTimeColumns,
-		TXpnIdent,
-	>(
+	/// Start a timer for the `employee` to work on the `job` (via `connection`), stopping the timer
+	/// which they already had running (if any) at the same time.
+	///
+	/// The time is taken from the clock of the database, so that the timer which is stopped ends
+	/// exactly when the new one begins.
+	///
+	/// # See also
+	///
+	/// * [`PgTimesheet::stop`], to stop the timer without starting another.
+	/// * [`TimesheetAdapter::create`], which is used to create the new [`Timesheet`].
+	pub async fn start(
+		connection: &mut Transaction<'_, Postgres>,
+		employee: Employee,
+		expenses: Vec<(String, Money, String)>,
+		job: Job,
+		work_notes: String,
+	) -> Result<Timesheet, Error>
+	{
+		let (now, _) = Self::stop_now(&mut *connection, &employee).await?;
+		Self::create(connection, employee, expenses, job, now, None, work_notes)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Stop the timer which the `employee` has running (via `connection`), returning the [`Id`] of
+	/// its [`Timesheet`] or [`None`] if there was no timer running.
+	///
+	/// The time is taken from the clock of the database.
+	pub async fn stop<'c, TConn>(connection: TConn, employee: &Employee) -> Result<Option<Id>, Error>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		Self::stop_now(connection, employee)
+			.await
+			.map(|(_, id)| id)
+			.map_err(Error::from)
+	}
+
+	/// Stop the timer which the `employee` has running (via `connection`) at the current time of
+	/// the database, returning that time along with the [`Id`] of the [`Timesheet`] which was
+	/// stopped (if any).
+	///
+	/// # Errors
+	///
+	/// * A [`ValidationError`](super::ValidationError) if the timer began after the current time of
+	///   the database (e.g. because it was created by a machine whose clock was ahead).
+	async fn stop_now<'c, TConn>(
 		connection: TConn,
+		employee: &Employee,
+	) -> Result<(DateTime<Utc>, Option<Id>)>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		// NOTE: `clock_timestamp` is read once, so that the time which is returned is the same one
+		//       which the timer was stopped at. Unlike `now`, it changes within a transaction, so
+		//       timers started in the same transaction do not begin at the same time.
+		sqlx::query!(
+			r#"WITH N AS (SELECT clock_timestamp() AS now),
+			S AS (
+				UPDATE timesheets T SET time_end = N.now
+				FROM N
+				WHERE T.employee_id = $1 AND T.time_end IS NULL AND T.deleted_at IS NULL
+				RETURNING T.id
+			)
+			SELECT N.now AS "now!", (SELECT S.id FROM S) AS id FROM N;"#,
+			employee.id,
+		)
+		.fetch_one(connection)
+		.await
+		.map(|row| (row.now, row.id))
+		.map_err(|e| {
+			util::validation_or(e, |field| match field
+			{
+				"employee_id" => Some(employee.id.to_string()),
+				_ => None,
+			})
+		})
+	}
+
+	/// Turn the `error` into a [`TimesheetOverlap`] if it was caused by one of the `timesheets`
+	/// overlapping another [`Timesheet`] of the same [`Employee`].
+	///
+	/// Each of the `timesheets` is an `(id, employee_id, time_begin, time_end)`, where the `id` is
+	/// [`None`] for a [`Timesheet`] which is being created.
+	///
+	/// The statement which caused the `error` must have been rolled back, so that the conflicting
+	/// [`Timesheet`] can be found (via `connection`).
+	pub(super) async fn overlap_or<TIter>(
+		connection: &mut Transaction<'_, Postgres>,
+		error: sqlx::Error,
+		timesheets: TIter,
+	) -> sqlx::Error
+	where
+		TIter: Iterator<Item = (Option<Id>, Id, DateTime<Utc>, Option<DateTime<Utc>>)> + Send,
+	{
+		if !util::violates(&error, TimesheetOverlap::CONSTRAINT)
+		{
+			return error;
+		}
+
+		let mut query = QueryBuilder::new("WITH V (id, employee_id, time_begin, time_end) AS (");
+
+		query
+			.push_values(
+				timesheets,
+				|mut q, (id, employee_id, time_begin, time_end)| {
+					q.push_bind(id)
+						.push_bind(employee_id)
+						.push_bind(time_begin)
+						.push_bind(time_end);
+				},
+			)
+			.push(
+				// NOTE: the `timesheets` being updated are compared using their new times.
+				") SELECT V.id, V.employee_id, T.id AS conflicting_id
+				FROM V
+				JOIN (
+					SELECT id, employee_id, time_begin, time_end
+					FROM timesheets
+					WHERE deleted_at IS NULL AND id NOT IN (SELECT id FROM V WHERE id IS NOT NULL)
+					UNION ALL
+					SELECT * FROM V WHERE id IS NOT NULL
+				) T ON (
+					T.employee_id = V.employee_id AND
+					T.id IS DISTINCT FROM V.id AND
+					tstzrange(T.time_begin, T.time_end) && tstzrange(V.time_begin, V.time_end)
+				)
+				ORDER BY T.time_begin
+				LIMIT 1;",
+			);
+
+		let row = match query.prepare().fetch_optional(&mut *connection).await
+		{
+			Ok(Some(row)) => row,
+			Ok(None) => return error,
+			Err(e) => return e,
+		};
+
+		match (
+			row.try_get("conflicting_id"),
+			row.try_get("employee_id"),
+			row.try_get("id"),
+		)
+		{
+			(Ok(conflicting_id), Ok(employee_id), Ok(timesheet_id)) =>
+			{
+				sqlx::Error::Database(Box::new(TimesheetOverlap {
+					conflicting_id,
+					employee_id,
+					timesheet_id,
+				}))
+			},
+			(Err(e), ..) | (_, Err(e), _) | (.., Err(e)) => e,
+		}
+	}
+
+	/// [Restore](crate::schema::SoftDeletable::restore) the rows of the `TTable` with the `ids` (via
+	/// `connection`), along with the [`Timesheet`]s which were soft deleted at the same time as
+	/// them.
+	///
+	/// The `cascaded` query selects the `id`, `employee_id`, `time_begin`, and `time_end` of those
+	/// [`Timesheet`]s, given the `ids` as `$1`, so that they can be reported in a
+	/// [`TimesheetOverlap`] when restoring them causes one.
+	pub(super) async fn restore_cascading<TTable>(
+		connection: &mut Transaction<'_, Postgres>,
+		ids: &[Id],
+		cascaded: &str,
+	) -> Result<()>
+	where
+		TTable: TableToSql,
+	{
+		// NOTE: a savepoint, so that the `connection` can still find what an overlap conflicts with.
+		let mut savepoint = connection.begin().await?;
+
+		let error =
+			match PgSchema::set_deleted::<_, _, TTable>(&mut savepoint, ids.iter().copied(), false)
+				.await
+			{
+				Ok(_) => return savepoint.commit().await,
+				Err(e) => e,
+			};
+
+		savepoint.rollback().await?;
+		if !util::violates(&error, TimesheetOverlap::CONSTRAINT)
+		{
+			return Err(error);
+		}
+
+		let timesheets = sqlx::query(cascaded)
+			.bind(ids)
+			.try_map(
+				|row: PgRow| -> Result<(Option<Id>, Id, DateTime<Utc>, Option<DateTime<Utc>>)> {
+					Ok((
+						row.try_get("id").map(Some)?,
+						row.try_get("employee_id")?,
+						row.try_get("time_begin")?,
+						row.try_get("time_end")?,
+					))
+				},
+			)
+			.fetch_all(&mut *connection)
+			.await?;
+
+		Err(Self::overlap_or(connection, error, timesheets.into_iter()).await)
+	}
+
+	/// Create a [`Timesheet`] from some `row`, whose job's client's
+	/// [`Location`](clinvoice_schema::Location) must be one of the `locations`.
+	pub(super) fn row_to_view<TEmpColumns, TJobColumns, TOrgColumns, TTimeColumns, TXpnIdent>(
 		columns: TimesheetColumns<TTimeColumns>,
 		employee_columns: EmployeeColumns<TEmpColumns>,
 		expenses_ident: TXpnIdent,
 		job_columns: JobColumns<TJobColumns>,
 		organization_columns: OrganizationColumns<TOrgColumns>,
+		locations: &HashMap<Id, Location>,
 		row: &PgRow,
 	) -> Result<Timesheet>
 	where
-		TConn: Executor<'c, Database = Postgres>,
 		TEmpColumns: AsRef<str>,
 		TJobColumns: AsRef<str>,
 		TOrgColumns: AsRef<str>,
 		TTimeColumns: AsRef<str>,
 		TXpnIdent: AsRef<str>,
 	{
-		let job_fut = PgJob::row_to_view(connection, job_columns, organization_columns, row);
 		Ok(Timesheet {
 			employee: PgEmployee::row_to_view(employee_columns, row),
 			id: row.try_get(columns.id.as_ref())?,
@@ -55,34 +275,170 @@ impl PgTimesheet
 			work_notes: row.try_get(columns.work_notes.as_ref())?,
 			expenses: row
 				.try_get(expenses_ident.as_ref())
-				.and_then(|raw_expenses: Vec<(_, String, _, _, _)>| {
+				.and_then(|raw_expenses: Vec<(_, _, _, _, _, String)>| {
 					raw_expenses
 						.into_iter()
-						.map(|(category, cost, description, id, timesheet_id)| {
-							Ok(Expense {
-								category,
-								description,
-								id,
-								timesheet_id,
-								cost: Money {
-									amount: cost
-										.parse::<Decimal>()
-										.map_err(|e| util::finance_err_to_sqlx(e.into()))?,
-									..Default::default()
-								},
-							})
-						})
+						.map(
+							|(category, cost, description, id, timesheet_id, cost_currency)| {
+								Ok(Expense {
+									category,
+									description,
+									id,
+									timesheet_id,
+									cost: util::money_from(cost, &cost_currency)?,
+								})
+							},
+						)
 						.collect::<Result<Vec<_>>>()
 				})
 				.or_else(|e| match e
 				{
-					Error::ColumnDecode { source: s, .. } if s.is::<UnexpectedNullError>() =>
+					sqlx::Error::ColumnDecode { source: s, .. } if s.is::<UnexpectedNullError>() =>
 					{
 						Ok(Vec::new())
 					},
 					_ => Err(e),
 				})?,
-			job: job_fut.await?,
+			job: PgJob::row_to_view(job_columns, organization_columns, locations, row)?,
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::schema::TimesheetAdapter;
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		Currency,
+		Money,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::PgTimesheet;
+	use crate::schema::{util, TimesheetOverlap, ValidationError, ValidationRule};
+
+	#[tokio::test]
+	async fn start_stop()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::start(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			"Cleaning".into(),
+		)
+		.await
+		.unwrap();
+
+		let timesheet2 = PgTimesheet::start(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			"More cleaning".into(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(timesheet2.time_end, None);
+
+		// Starting a timer stops the one which was running.
+		let time_end = sqlx::query!(
+			"SELECT time_end FROM timesheets WHERE id = $1",
+			timesheet.id
+		)
+		.fetch_one(&connection)
+		.await
+		.unwrap()
+		.time_end;
+		assert_eq!(time_end, Some(timesheet2.time_begin));
+
+		// Only one timer may be running at a time, since a running timer overlaps every one which
+		// begins after it.
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			let error = PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				job.clone(),
+				Utc::now(),
+				None,
+				String::new(),
+			)
+			.await
+			.unwrap_err();
+
+			assert_eq!(
+				error
+					.as_database_error()
+					.unwrap()
+					.downcast_ref::<TimesheetOverlap>(),
+				&TimesheetOverlap {
+					conflicting_id: timesheet2.id,
+					employee_id: employee.id,
+					timesheet_id: None,
+				},
+			);
+		}
+
+		assert_eq!(
+			PgTimesheet::stop(&connection, &employee).await.unwrap(),
+			Some(timesheet2.id)
+		);
+		assert_eq!(
+			PgTimesheet::stop(&connection, &employee).await.unwrap(),
+			None
+		);
+
+		// A timer which begins after the clock of the database cannot be stopped.
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				job,
+				Utc.ymd(2999, 01, 01).and_hms(09, 00, 00),
+				None,
+				String::new(),
+			)
+			.await
+			.unwrap();
+
+			let error = PgTimesheet::stop(&mut transaction, &employee)
+				.await
+				.unwrap_err();
+
+			let validation = error
+				.as_database_error()
+				.unwrap()
+				.try_downcast_ref::<ValidationError>()
+				.unwrap();
+
+			assert_eq!(validation.constraint, "timesheets__date_integrity");
+			assert_eq!(validation.rule, ValidationRule::After("time_begin"));
+		}
+	}
+}
@@ -1,9 +1,12 @@
 use clinvoice_adapter::{schema::columns::TimesheetColumns, Deletable};
 use clinvoice_schema::{Id, Timesheet};
-use sqlx::{Executor, Postgres, Result};
+use sqlx::{Connection, Executor, Postgres, Result, Transaction};
 
 use super::PgTimesheet;
-use crate::PgSchema;
+use crate::{
+	schema::{Error, SoftDeletable},
+	PgSchema,
+};
 
 #[async_trait::async_trait]
 impl Deletable for PgTimesheet
@@ -28,6 +31,84 @@ impl Deletable for PgTimesheet
 	}
 }
 
+#[async_trait::async_trait]
+impl SoftDeletable for PgTimesheet
+{
+	type Entity = Timesheet;
+
+	/// Soft delete the `entities` (via `connection`), along with their
+	/// [`Expense`](clinvoice_schema::Expense)s which were not already soft deleted.
+	async fn soft_delete<'c, 'e, 'i, TConn, TIter>(
+		connection: TConn,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TConn: Executor<'c, Database = Postgres>,
+		TIter: Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(t: &Timesheet) -> Id
+		{
+			t.id
+		}
+
+		// TODO: use `for<'a> |e: &'a Timesheet| e.id`
+		PgSchema::set_deleted::<_, _, TimesheetColumns<char>>(connection, entities.map(mapper), true)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Restore the `entities` (via `connection`), along with the
+	/// [`Expense`](clinvoice_schema::Expense)s which were soft deleted at the same time as them.
+	///
+	/// # Errors
+	///
+	/// * A [`TimesheetOverlap`](crate::schema::TimesheetOverlap) if any of the `entities` would
+	///   overlap another [`Timesheet`] of the same [`Employee`](clinvoice_schema::Employee).
+	async fn restore<'e, 'i, TIter>(
+		connection: &mut Transaction<Postgres>,
+		entities: TIter,
+	) -> Result<(), Error>
+	where
+		'e: 'i,
+		Self::Entity: 'e,
+		TIter: Clone + Iterator<Item = &'i Self::Entity> + Send,
+	{
+		fn mapper(t: &Timesheet) -> Id
+		{
+			t.id
+		}
+
+		// NOTE: a savepoint, so that the `connection` can still find what an overlap conflicts with.
+		let mut savepoint = connection.begin().await?;
+
+		// TODO: use `for<'a> |e: &'a Timesheet| e.id`
+		let result = PgSchema::set_deleted::<_, _, TimesheetColumns<char>>(
+			&mut savepoint,
+			entities.clone().map(mapper),
+			false,
+		)
+		.await;
+
+		if let Err(e) = result
+		{
+			savepoint.rollback().await?;
+			return Err(
+				PgTimesheet::overlap_or(
+					connection,
+					e,
+					entities.map(|t| (Some(t.id), t.employee.id, t.time_begin, t.time_end)),
+				)
+				.await
+				.into(),
+			);
+		}
+
+		savepoint.commit().await.map_err(Error::from)
+	}
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -44,7 +125,7 @@ mod tests
 		Deletable,
 		Retrievable,
 	};
-	use clinvoice_finance::{Currency, ExchangeRates, Exchangeable, Money};
+	use clinvoice_finance::{Currency, Money};
 	use clinvoice_match::{Match, MatchExpense};
 	use clinvoice_schema::{
 		chrono::{TimeZone, Utc},
@@ -60,6 +141,8 @@ mod tests
 		PgLocation,
 		PgOrganization,
 		PgTimesheet,
+		SoftDeletable,
+		TimesheetOverlap,
 	};
 
 	#[tokio::test]
@@ -108,8 +191,8 @@ mod tests
 			employee.clone(),
 			Vec::new(),
 			job.clone(),
-			Utc::now(),
-			None,
+			Utc.ymd(2022, 06, 07).and_hms(09, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(17, 00, 00)),
 			"These are my work notes".into(),
 		)
 		.await
@@ -155,7 +238,6 @@ mod tests
 			.await
 			.unwrap();
 
-		let exchange_rates = ExchangeRates::new().await.unwrap();
 		assert_eq!(
 			PgTimesheet::retrieve(
 				&connection,
@@ -163,13 +245,14 @@ mod tests
 					timesheet.id.into(),
 					timesheet2.id.into(),
 					timesheet3.id.into(),
-				]).into(),
+				])
+				.into(),
 			)
 			.await
 			.unwrap()
 			.into_iter()
 			.as_slice(),
-			&[timesheet3.exchange_ref(Default::default(), &exchange_rates)],
+			&[timesheet3.clone()],
 		);
 
 		assert_eq!(
@@ -183,9 +266,75 @@ mod tests
 			})
 			.await
 			.unwrap(),
-			timesheet3
-				.expenses
-				.exchange_ref(Default::default(), &exchange_rates),
+			timesheet3.expenses,
+		);
+	}
+
+	#[tokio::test]
+	async fn restore_overlap()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+		let employee = util::create_employee(&connection).await;
+		let job = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			Utc.ymd(2022, 06, 07).and_hms(09, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(17, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		PgTimesheet::soft_delete(&mut transaction, [&timesheet].into_iter())
+			.await
+			.unwrap();
+
+		// The soft deleted timesheet does not conflict with this one.
+		let timesheet2 = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			Utc.ymd(2022, 06, 07).and_hms(12, 00, 00),
+			Some(Utc.ymd(2022, 06, 07).and_hms(13, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let mut transaction = connection.begin().await.unwrap();
+		let error = PgTimesheet::restore(&mut transaction, [&timesheet].into_iter())
+			.await
+			.unwrap_err();
+
+		assert_eq!(
+			error
+				.as_database_error()
+				.unwrap()
+				.downcast_ref::<TimesheetOverlap>(),
+			&TimesheetOverlap {
+				conflicting_id: timesheet2.id,
+				employee_id: employee.id,
+				timesheet_id: Some(timesheet.id),
+			},
 		);
 	}
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use clinvoice_adapter::{
 	fmt::{sql, QueryBuilderExt, TableToSql},
 	schema::columns::{
@@ -9,21 +11,43 @@ use clinvoice_adapter::{
 		TimesheetColumns,
 	},
 	Retrievable,
+	WriteContext,
 	WriteWhereClause,
 };
-use clinvoice_finance::{ExchangeRates, Exchangeable};
+use clinvoice_finance::Currency;
 use clinvoice_match::MatchTimesheet;
-use clinvoice_schema::Timesheet;
-use futures::{TryFutureExt, TryStreamExt};
-use sqlx::{Pool, Postgres, Result};
+use clinvoice_schema::{
+	chrono::{DateTime, Utc},
+	Timesheet,
+};
+use futures::{future, stream::BoxStream, TryStreamExt};
+use sqlx::{postgres::PgRow, Executor, Pool, Postgres, QueryBuilder, Result, Row};
 
 use super::PgTimesheet;
 use crate::{
 	fmt::PgLocationRecursiveCte,
-	schema::{util, PgLocation},
+	schema::{
+		util,
+		Error,
+		ExchangeRatesSource,
+		PgAudit,
+		PgExchangeRates,
+		PgLocation,
+		PgOrganization,
+		RatedTimesheet,
+		RetrieveOptions,
+	},
 	PgSchema,
 };
 
+const COLUMNS: TimesheetColumns<&str> = TimesheetColumns::default();
+
+const EXPENSES_AGGREGATED_IDENT: &str = "expenses_aggregated";
+
+const EMPLOYEE_COLUMNS_UNIQUE: EmployeeColumns<&str> = EmployeeColumns::unique();
+const JOB_COLUMNS_UNIQUE: JobColumns<&str> = JobColumns::unique();
+const ORGANIZATION_COLUMNS_UNIQUE: OrganizationColumns<&str> = OrganizationColumns::unique();
+
 /// Implementors of this trait are capable of being retrieved from a [`Database`].
 #[async_trait::async_trait]
 impl Retrievable for PgTimesheet
@@ -41,34 +65,336 @@ impl Retrievable for PgTimesheet
 		match_condition: &Self::Match,
 	) -> Result<Vec<Self::Entity>>
 	{
-		const COLUMNS: TimesheetColumns<&str> = TimesheetColumns::default();
+		Self::retrieve_via(connection, match_condition, &Default::default()).await
+	}
+}
 
-		const EXPENSES_AGGREGATED_IDENT: &str = "expenses_aggregated";
+impl PgTimesheet
+{
+	/// Count the [`Timesheet`]s (via `connection`) that match the `match_condition`.
+	pub async fn count(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchTimesheet,
+	) -> Result<i64, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, None, |q| {
+			// NOTE: there is a row for each expense of a timesheet
+			q.push("count(DISTINCT ")
+				.push(COLUMNS.default_scope().id)
+				.push(')');
+		});
+
+		query
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
 
-		const EMPLOYEE_COLUMNS_UNIQUE: EmployeeColumns<&str> = EmployeeColumns::unique();
-		const JOB_COLUMNS_UNIQUE: JobColumns<&str> = JobColumns::unique();
-		const ORGANIZATION_COLUMNS_UNIQUE: OrganizationColumns<&str> = OrganizationColumns::unique();
+	/// Check whether any [`Timesheet`]s (via `connection`) match the `match_condition`.
+	pub async fn exists(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchTimesheet,
+	) -> Result<bool, Error>
+	{
+		let (mut query, _) = Self::select_matching(match_condition, false, None, |q| {
+			q.push(util::EXISTS);
+		});
 
+		query
+			.push(')')
+			.prepare()
+			.fetch_one(connection)
+			.await?
+			.try_get(0)
+			.map_err(Error::from)
+	}
+
+	/// Same as [`PgTimesheet::retrieve_with_options`], except that any `connection` which can be
+	/// used more than once will do.
+	///
+	/// No matter how many [`Timesheet`]s match, they are retrieved in a fixed number of queries.
+	async fn retrieve_via<'c, TConn>(
+		connection: TConn,
+		match_condition: &MatchTimesheet,
+		options: &RetrieveOptions<TimesheetColumns<&'static str>>,
+	) -> Result<Vec<Timesheet>>
+	where
+		TConn: Copy + Executor<'c, Database = Postgres>,
+	{
+		let rows = Self::retrieve_query(match_condition, options)?
+			.prepare()
+			.fetch_all(connection)
+			.await?;
+
+		Self::rows_to_views(connection, &rows).await
+	}
+
+	/// [Retrieve](Retrievable::retrieve) all [`Timesheet`]s (via `connection`) that match the
+	/// `match_condition`, and then exchange them (including their `expenses` and `job`) into the
+	/// `currency` using the rates which were effective on each [`Timesheet`]'s `time_begin`.
+	///
+	/// # See also
+	///
+	/// * [`PgExchangeRates`], where the rates that were used are stored.
+	pub async fn retrieve_exchanged<TSource>(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchTimesheet,
+		currency: Currency,
+		source: &TSource,
+	) -> Result<Vec<Timesheet>, Error>
+	where
+		TSource: ExchangeRatesSource + ?Sized,
+	{
+		let timesheets = Self::retrieve(connection, match_condition).await?;
+		PgExchangeRates::exchange(
+			connection,
+			timesheets,
+			currency,
+			source,
+			|t| t.time_begin.naive_utc().date(),
+			|t| {
+				t.expenses
+					.iter()
+					.map(|x| x.cost.currency)
+					.chain([t.job.invoice.hourly_rate.currency])
+					.collect()
+			},
+		)
+		.await
+	}
+
+	/// [Retrieve](Retrievable::retrieve) all [`Timesheet`]s (via `connection`) that match the
+	/// `match_condition`, along with the hourly rate which each one is billed at.
+	///
+	/// # See also
+	///
+	/// * [`PgBillingRate`](crate::schema::PgBillingRate), which sets the rates of specific
+	///   [`Employee`](clinvoice_schema::Employee)s.
+	pub async fn retrieve_rated(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchTimesheet,
+	) -> Result<Vec<RatedTimesheet>, Error>
+	{
+		let timesheets = Self::retrieve(connection, match_condition).await?;
+		let ids: Vec<_> = timesheets.iter().map(|t| t.id).collect();
+
+		let mut rates: HashMap<_, _> = sqlx::query!(
+			r#"SELECT
+					timesheet_id AS "timesheet_id!",
+					hourly_rate AS "hourly_rate!",
+					hourly_rate_currency AS "hourly_rate_currency!"
+				FROM timesheet_rates
+				WHERE timesheet_id = ANY($1);"#,
+			&ids,
+		)
+		.fetch(connection)
+		.map_ok(|row| {
+			(
+				row.timesheet_id,
+				(row.hourly_rate, row.hourly_rate_currency),
+			)
+		})
+		.try_collect()
+		.await?;
+
+		timesheets
+			.into_iter()
+			.map(|timesheet| {
+				let (amount, currency) = rates.remove(&timesheet.id).ok_or(Error::NotFound)?;
+				Ok(RatedTimesheet {
+					hourly_rate: util::money_from(amount, &currency)?,
+					timesheet,
+				})
+			})
+			.collect()
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Timesheet`]s are retrieved as they
+	/// were `as_of` some time (via `connection`), rather than as they are now.
+	///
+	/// # See also
+	///
+	/// * [`RetrieveOptions::as_of`], to sort and paginate them as well.
+	pub async fn retrieve_as_of(
+		connection: &Pool<Postgres>,
+		as_of: DateTime<Utc>,
+		match_condition: &MatchTimesheet,
+	) -> Result<Vec<Timesheet>, Error>
+	{
+		Self::retrieve_with_options(connection, match_condition, &RetrieveOptions {
+			as_of: Some(as_of),
+			..Default::default()
+		})
+		.await
+	}
+
+	/// Generate the query which selects every [`Timesheet`] that matches the `match_condition`,
+	/// according to the `options`.
+	fn retrieve_query<'a>(
+		match_condition: &'a MatchTimesheet,
+		options: &RetrieveOptions<TimesheetColumns<&'static str>>,
+	) -> Result<QueryBuilder<'a, Postgres>>
+	{
+		let columns = COLUMNS.default_scope();
+		let employee_columns = EmployeeColumns::default().default_scope();
+		let job_columns = JobColumns::default().default_scope();
+		let organization_columns = OrganizationColumns::default().default_scope();
+
+		let (mut query, context) = Self::select_matching(
+			match_condition,
+			options.include_deleted,
+			options.as_of,
+			|q| {
+				let expense_columns = ExpenseColumns::default().default_scope();
+				q.push_columns(&columns)
+					.push_more_columns(&employee_columns.r#as(EMPLOYEE_COLUMNS_UNIQUE))
+					.push(",array_agg((") // NOTE: might need `",array_agg( DISTINCT ("`
+					.push_columns(&expense_columns)
+					.push(',')
+					.push(util::currency_column(expense_columns.cost))
+					.push("))");
+
+				if !options.include_deleted
+				{
+					q.push(" FILTER (WHERE ")
+						.push(ExpenseColumns::<char>::DEFAULT_ALIAS)
+						.push('.')
+						.push(util::DELETED_AT)
+						.push(sql::IS)
+						.push(sql::NULL)
+						.push(')');
+				}
+
+				q.push(sql::AS)
+					.push(EXPENSES_AGGREGATED_IDENT)
+					.push_more_columns(&job_columns.r#as(JOB_COLUMNS_UNIQUE))
+					.push(',')
+					.push(util::currency_column(job_columns.invoice_hourly_rate))
+					.push(sql::AS)
+					.push(util::currency_column(
+						JOB_COLUMNS_UNIQUE.invoice_hourly_rate,
+					))
+					.push_more_columns(&organization_columns.r#as(ORGANIZATION_COLUMNS_UNIQUE));
+			},
+		);
+
+		options.write_where(
+			context,
+			COLUMNS,
+			TimesheetColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		)?;
+
+		query
+			.push(sql::GROUP_BY)
+			.separated(',')
+			.push(columns.id)
+			.push(employee_columns.id)
+			.push(job_columns.id)
+			.push(organization_columns.id);
+
+		options.write_order(
+			COLUMNS,
+			TimesheetColumns::<char>::DEFAULT_ALIAS,
+			|c| c.id,
+			&mut query,
+		);
+
+		Ok(query)
+	}
+
+	/// Same as [`PgTimesheet::retrieve_with_options`], except that the [`Timesheet`]s are yielded
+	/// as they are retrieved instead of being collected first.
+	pub fn retrieve_stream<'a>(
+		connection: &'a Pool<Postgres>,
+		match_condition: &'a MatchTimesheet,
+		options: &'a RetrieveOptions<TimesheetColumns<&'static str>>,
+	) -> BoxStream<'a, Result<Timesheet, Error>>
+	{
+		util::stream_views(
+			connection,
+			future::ready(Self::retrieve_query(match_condition, options)),
+			move |rows| async move { Self::rows_to_views(connection, &rows).await },
+		)
+	}
+
+	/// Same as [`Retrievable::retrieve`], except that the [`Timesheet`]s are sorted and paginated
+	/// according to the `options`.
+	pub async fn retrieve_with_options(
+		connection: &Pool<Postgres>,
+		match_condition: &MatchTimesheet,
+		options: &RetrieveOptions<TimesheetColumns<&'static str>>,
+	) -> Result<Vec<Timesheet>, Error>
+	{
+		Self::retrieve_via(connection, match_condition, options)
+			.await
+			.map_err(Error::from)
+	}
+
+	/// Convert the `rows` which the [`PgTimesheet::retrieve_query`] selected into [`Timesheet`]s,
+	/// retrieving their job's client's [`Location`](clinvoice_schema::Location)s via `connection`.
+	async fn rows_to_views<'c, TConn>(connection: TConn, rows: &[PgRow]) -> Result<Vec<Timesheet>>
+	where
+		TConn: Executor<'c, Database = Postgres>,
+	{
+		PgLocation::rows_to_views(
+			connection,
+			rows,
+			|row| PgOrganization::location_id_of(&ORGANIZATION_COLUMNS_UNIQUE, row).map(Some),
+			|locations, row| {
+				Self::row_to_view(
+					COLUMNS,
+					EMPLOYEE_COLUMNS_UNIQUE,
+					EXPENSES_AGGREGATED_IDENT,
+					JOB_COLUMNS_UNIQUE,
+					ORGANIZATION_COLUMNS_UNIQUE,
+					locations,
+					row,
+				)
+			},
+		)
+		.await
+	}
+
+	/// Generate a query which `select`s something (e.g. the columns) of every [`Timesheet`] that
+	/// matches the `match_condition`.
+	///
+	/// Each [`Timesheet`] is joined with each of its `expenses`, so there may be more than one row
+	/// per [`Timesheet`]. [Soft deleted](crate::schema::SoftDeletable) [`Timesheet`]s are excluded,
+	/// unless `include_deleted` is `true`. The [`WriteContext`] of the `WHERE` clause is returned so
+	/// that more conditions can be added.
+	///
+	/// When `as_of` is given, the [`AuditedTable`](crate::schema::AuditedTable)s are selected from
+	/// as they were at that time.
+	fn select_matching<'a, TSelect>(
+		match_condition: &'a MatchTimesheet,
+		include_deleted: bool,
+		as_of: Option<DateTime<Utc>>,
+		select: TSelect,
+	) -> (QueryBuilder<'a, Postgres>, WriteContext)
+	where
+		TSelect: FnOnce(&mut QueryBuilder<'a, Postgres>),
+	{
 		let columns = COLUMNS.default_scope();
 		let employee_columns = EmployeeColumns::default().default_scope();
-		let exchange_rates_fut = ExchangeRates::new().map_err(util::finance_err_to_sqlx);
 		let expense_columns = ExpenseColumns::default().default_scope();
 		let job_columns = JobColumns::default().default_scope();
 		let location_columns = LocationColumns::default().default_scope();
 		let mut query = PgLocation::query_with_recursive(&match_condition.job.client.location);
+		if let Some(as_of) = as_of
+		{
+			query.push(',');
+			PgAudit::push_as_of(&mut query, as_of);
+		}
+
 		let organization_columns = OrganizationColumns::default().default_scope();
 
+		query.push(sql::SELECT);
+		select(&mut query);
 		query
-			.push(sql::SELECT)
-			.push_columns(&columns)
-			.push_more_columns(&employee_columns.r#as(EMPLOYEE_COLUMNS_UNIQUE))
-			.push(",array_agg((") // NOTE: might need `",array_agg( DISTINCT ("`
-			.push_columns(&expense_columns)
-			.push("))")
-			.push(sql::AS)
-			.push(EXPENSES_AGGREGATED_IDENT)
-			.push_more_columns(&job_columns.r#as(JOB_COLUMNS_UNIQUE))
-			.push_more_columns(&organization_columns.r#as(ORGANIZATION_COLUMNS_UNIQUE))
 			.push_default_from::<TimesheetColumns<char>>()
 			.push_default_equijoin::<EmployeeColumns<char>, _, _>(
 				employee_columns.id,
@@ -91,8 +417,7 @@ impl Retrievable for PgTimesheet
 				organization_columns.location_id,
 			);
 
-		let exchange_rates = exchange_rates_fut.await?;
-		PgSchema::write_where_clause(
+		let context = PgSchema::write_where_clause(
 			PgSchema::write_where_clause(
 				PgSchema::write_where_clause(
 					PgSchema::write_where_clause(
@@ -107,15 +432,11 @@ impl Retrievable for PgTimesheet
 						&mut query,
 					),
 					ExpenseColumns::<char>::DEFAULT_ALIAS,
-					&match_condition
-						.expenses
-						.exchange_ref(Default::default(), &exchange_rates),
+					&match_condition.expenses,
 					&mut query,
 				),
 				JobColumns::<char>::DEFAULT_ALIAS,
-				&match_condition
-					.job
-					.exchange_ref(Default::default(), &exchange_rates),
+				&match_condition.job,
 				&mut query,
 			),
 			OrganizationColumns::<char>::DEFAULT_ALIAS,
@@ -123,31 +444,14 @@ impl Retrievable for PgTimesheet
 			&mut query,
 		);
 
-		query
-			.push(sql::GROUP_BY)
-			.separated(',')
-			.push(columns.id)
-			.push(employee_columns.id)
-			.push(job_columns.id)
-			.push(organization_columns.id);
+		let context = util::write_not_deleted(
+			context,
+			TimesheetColumns::<char>::DEFAULT_ALIAS,
+			include_deleted,
+			&mut query,
+		);
 
-		query
-			.prepare()
-			.fetch(connection)
-			.and_then(|row| async move {
-				PgTimesheet::row_to_view(
-					connection,
-					COLUMNS,
-					EMPLOYEE_COLUMNS_UNIQUE,
-					EXPENSES_AGGREGATED_IDENT,
-					JOB_COLUMNS_UNIQUE,
-					ORGANIZATION_COLUMNS_UNIQUE,
-					&row,
-				)
-				.await
-			})
-			.try_collect()
-			.await
+		(query, context)
 	}
 }
 
@@ -155,6 +459,7 @@ impl Retrievable for PgTimesheet
 mod tests
 {
 	use core::time::Duration;
+	use std::collections::HashSet;
 
 	use clinvoice_adapter::{
 		schema::{
@@ -166,8 +471,7 @@ mod tests
 		},
 		Retrievable,
 	};
-	use clinvoice_finance::{ExchangeRates, Exchangeable};
-	use clinvoice_match::{Match, MatchSet, MatchTimesheet};
+	use clinvoice_match::{Match, MatchJob, MatchSet, MatchStr, MatchTimesheet};
 	use clinvoice_schema::{
 		chrono::{TimeZone, Utc},
 		Currency,
@@ -175,9 +479,110 @@ mod tests
 		InvoiceDate,
 		Money,
 	};
+	use futures::TryStreamExt;
 	use pretty_assertions::assert_eq;
 
-	use crate::schema::{util, PgEmployee, PgJob, PgLocation, PgOrganization, PgTimesheet};
+	use crate::schema::{
+		util,
+		BillingRateTarget,
+		PgBillingRate,
+		PgEmployee,
+		PgJob,
+		PgLocation,
+		PgOrganization,
+		PgTimesheet,
+		RatedTimesheet,
+	};
+
+	#[tokio::test]
+	async fn count()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			vec![
+				(
+					"Flight".into(),
+					Money::new(300_56, 2, Currency::Usd),
+					"Trip to Hawaii for research".into(),
+				),
+				(
+					"Food".into(),
+					Money::new(10_00, 2, Currency::Usd),
+					"Lunch in Hawaii".into(),
+				),
+			],
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			Utc.ymd(1990, 07, 12).and_hms(16, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		let match_condition = MatchTimesheet {
+			employee: Match::from(employee.id).into(),
+			..Default::default()
+		};
+
+		// The timesheet with two expenses must only be counted once
+		assert_eq!(
+			PgTimesheet::count(&connection, &match_condition)
+				.await
+				.unwrap(),
+			2
+		);
+		assert!(PgTimesheet::exists(&connection, &match_condition)
+			.await
+			.unwrap());
+
+		let match_condition = MatchTimesheet {
+			work_notes: MatchStr::from("Does not exist".to_string()),
+			..match_condition
+		};
+
+		assert_eq!(
+			PgTimesheet::count(&connection, &match_condition)
+				.await
+				.unwrap(),
+			0
+		);
+		assert!(!PgTimesheet::exists(&connection, &match_condition)
+			.await
+			.unwrap());
+	}
 
 	#[tokio::test]
 	async fn retrieve()
@@ -287,22 +692,239 @@ mod tests
 		transaction.commit().await.unwrap();
 		// }}}
 
-		let exchange_rates = ExchangeRates::new().await.unwrap();
-
 		assert_eq!(
 			PgTimesheet::retrieve(&connection, &MatchTimesheet {
 				expenses: MatchSet::Not(MatchSet::Contains(Default::default()).into()),
 				employee: Match::Or(vec![
 					timesheet.employee.id.into(),
 					timesheet2.employee.id.into(),
-				]).into(),
+				])
+				.into(),
 				..Default::default()
 			})
 			.await
 			.unwrap()
 			.into_iter()
 			.as_slice(),
-			&[timesheet.exchange(Default::default(), &exchange_rates)],
+			&[timesheet.clone()],
+		);
+
+		let match_both = MatchTimesheet {
+			id: Match::Or(vec![timesheet.id.into(), timesheet2.id.into()]),
+			..Default::default()
+		};
+
+		assert_eq!(
+			PgTimesheet::retrieve_stream(&connection, &match_both, &Default::default())
+				.try_collect::<HashSet<_>>()
+				.await
+				.unwrap(),
+			[timesheet, timesheet2].into_iter().collect(),
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_round_trips()
+	{
+		let connection = util::connect().await;
+
+		let earth = PgLocation::create(&connection, "Earth".into(), None)
+			.await
+			.unwrap();
+		let employee = util::create_employee(&connection).await;
+
+		let mut ids = Vec::new();
+		let mut round_trips = Vec::new();
+
+		for i in 0..5
+		{
+			let location = PgLocation::create(&connection, format!("City {i}"), Some(earth.clone()))
+				.await
+				.unwrap();
+
+			let organization =
+				PgOrganization::create(&connection, location, format!("Organization {i}"))
+					.await
+					.unwrap();
+
+			let job = PgJob::create(
+				&connection,
+				organization,
+				None,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Duration::from_secs(900),
+				Invoice {
+					date: None,
+					hourly_rate: Money::new(20_00, 2, Currency::Usd),
+				},
+				String::new(),
+				"Do something".into(),
+			)
+			.await
+			.unwrap();
+
+			// {{{
+			let mut transaction = connection.begin().await.unwrap();
+
+			let timesheet = PgTimesheet::create(
+				&mut transaction,
+				employee.clone(),
+				Vec::new(),
+				job,
+				Utc.ymd(1990, 07, 12).and_hms(15, i, 00),
+				Some(Utc.ymd(1990, 07, 12).and_hms(15, i, 30)),
+				String::new(),
+			)
+			.await
+			.unwrap();
+
+			transaction.commit().await.unwrap();
+			// }}}
+
+			ids.push(timesheet.id.into());
+
+			let counter = util::RoundTrips::new(&connection);
+			let retrieved = PgTimesheet::retrieve_via(
+				&counter,
+				&MatchTimesheet {
+					id: Match::Or(ids.clone()),
+					..Default::default()
+				},
+				&Default::default(),
+			)
+			.await
+			.unwrap();
+
+			assert_eq!(retrieved.len(), ids.len());
+			round_trips.push(counter.count());
+		}
+
+		// The number of round-trips must not grow with the number of rows retrieved.
+		assert!(
+			round_trips.windows(2).all(|w| w[0] == w[1]),
+			"{round_trips:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn retrieve_rated()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let senior = PgEmployee::create(
+			&connection,
+			"My Name".into(),
+			"Employed".into(),
+			"Senior Engineer".into(),
+		)
+		.await
+		.unwrap();
+		let senior2 = PgEmployee::create(
+			&connection,
+			"Another Name".into(),
+			"Employed".into(),
+			"Senior Engineer".into(),
+		)
+		.await
+		.unwrap();
+		let janitor = PgEmployee::create(
+			&connection,
+			"Yet Another Name".into(),
+			"Employed".into(),
+			"Janitor".into(),
+		)
+		.await
+		.unwrap();
+		let job = util::create_job(
+			&connection,
+			organization,
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Money::new(20_00, 2, Currency::Usd),
+		)
+		.await;
+
+		futures::try_join!(
+			PgBillingRate::create(
+				&connection,
+				&job,
+				BillingRateTarget::Title(senior.title.clone()),
+				Money::new(50_00, 2, Currency::Usd),
+			),
+			PgBillingRate::create(
+				&connection,
+				&job,
+				BillingRateTarget::Employee(senior2.id),
+				Money::new(40_00, 2, Currency::Usd),
+			),
+		)
+		.unwrap();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let mut timesheets = Vec::new();
+		for employee in [senior, senior2, janitor]
+		{
+			timesheets.push(
+				PgTimesheet::create(
+					&mut transaction,
+					employee,
+					Vec::new(),
+					job.clone(),
+					Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+					Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+					String::new(),
+				)
+				.await
+				.unwrap(),
+			);
+		}
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		assert_eq!(
+			PgTimesheet::retrieve_rated(&connection, &MatchTimesheet {
+				job: MatchJob {
+					id: job.id.into(),
+					..Default::default()
+				},
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.collect::<HashSet<_>>(),
+			timesheets
+				.into_iter()
+				.zip([
+					// the rate of their title
+					Money::new(50_00, 2, Currency::Usd),
+					// the rate of the employee takes precedence over their title
+					Money::new(40_00, 2, Currency::Usd),
+					// the rate of the job
+					Money::new(20_00, 2, Currency::Usd),
+				])
+				.map(|(timesheet, hourly_rate)| RatedTimesheet {
+					hourly_rate,
+					timesheet,
+				})
+				.collect::<HashSet<_>>(),
+		);
+
+		assert_eq!(
+			PgJob::retrieve_billable_time(&connection, &MatchJob {
+				id: job.id.into(),
+				..Default::default()
+			})
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|b| b.amount)
+			.collect::<Vec<_>>(),
+			[Money::new(110_00, 2, Currency::Usd)],
 		);
 	}
 }
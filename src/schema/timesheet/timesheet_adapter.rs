@@ -6,10 +6,13 @@ use clinvoice_schema::{
 	Job,
 	Timesheet,
 };
-use sqlx::{Postgres, Result, Transaction};
+use sqlx::{Connection, Postgres, Result, Transaction};
 
 use super::PgTimesheet;
-use crate::{fmt::DateTimeExt, schema::PgExpenses};
+use crate::{
+	fmt::DateTimeExt,
+	schema::{util, PgExpenses},
+};
 
 #[async_trait::async_trait]
 impl TimesheetAdapter for PgTimesheet
@@ -24,7 +27,9 @@ impl TimesheetAdapter for PgTimesheet
 		work_notes: String,
 	) -> Result<Timesheet>
 	{
-		let row = sqlx::query!(
+		// NOTE: a savepoint, so that the `connection` can still find what an overlap conflicts with.
+		let mut savepoint = connection.begin().await?;
+		let result = sqlx::query!(
 			"INSERT INTO timesheets
 				(employee_id, job_id, time_begin, time_end, work_notes)
 			VALUES
@@ -36,8 +41,34 @@ impl TimesheetAdapter for PgTimesheet
 			time_end,
 			work_notes,
 		)
-		.fetch_one(&mut *connection)
-		.await?;
+		.fetch_one(&mut savepoint)
+		.await;
+
+		let row = match result
+		{
+			Ok(row) => row,
+			Err(e) =>
+			{
+				savepoint.rollback().await?;
+				let error = PgTimesheet::overlap_or(
+					connection,
+					e,
+					[(None, employee.id, time_begin, time_end)].into_iter(),
+				)
+				.await;
+
+				return Err(util::validation_or(error, |field| match field
+				{
+					"employee_id" => Some(employee.id.to_string()),
+					"job_id" => Some(job.id.to_string()),
+					"time_begin" => Some(time_begin.to_string()),
+					"time_end" => time_end.map(|t| t.to_string()),
+					_ => None,
+				}));
+			},
+		};
+
+		savepoint.commit().await?;
 
 		let expenses_db = PgExpenses::create(connection, expenses, row.id).await?;
 
@@ -75,7 +106,7 @@ mod tests
 	use pretty_assertions::assert_eq;
 
 	use super::{PgTimesheet, TimesheetAdapter};
-	use crate::schema::{util, PgEmployee, PgJob, PgLocation, PgOrganization};
+	use crate::schema::{util, PgEmployee, PgJob, PgLocation, PgOrganization, TimesheetOverlap};
 
 	#[tokio::test]
 	async fn retrieve()
@@ -210,4 +241,144 @@ mod tests
 		assert_eq!(timesheet2_db.time_end, timesheet2.time_end);
 		assert_eq!(timesheet2_db.work_notes, timesheet2.work_notes);
 	}
+
+	#[tokio::test]
+	async fn create_overlap()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job, job2) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization.clone(),
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			Some(Utc.ymd(1990, 07, 12).and_hms(16, 00, 00)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		// The same employee cannot work on another job at the same time.
+		let error = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job2.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 30, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap_err();
+
+		assert_eq!(
+			error
+				.as_database_error()
+				.unwrap()
+				.downcast_ref::<TimesheetOverlap>(),
+			&TimesheetOverlap {
+				conflicting_id: timesheet.id,
+				employee_id: employee.id,
+				timesheet_id: None,
+			},
+		);
+
+		// Work can begin as soon as the other timesheet ends, and the failed attempt did not abort
+		// the transaction.
+		PgTimesheet::create(
+			&mut transaction,
+			employee,
+			Vec::new(),
+			job2,
+			Utc.ymd(1990, 07, 12).and_hms(16, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+	}
+
+	#[tokio::test]
+	async fn create_running_overlap()
+	{
+		let connection = util::connect().await;
+
+		let organization = util::create_organization(&connection).await;
+
+		let (employee, job) = futures::join!(
+			util::create_employee(&connection),
+			util::create_job(
+				&connection,
+				organization,
+				Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+				Money::new(20_00, 2, Currency::Usd),
+			),
+		);
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			Utc.ymd(1990, 07, 12).and_hms(15, 00, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		// An employee can only have one running timesheet, even if the other began later.
+		let error = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			Utc.ymd(1990, 07, 12).and_hms(14, 30, 00),
+			None,
+			String::new(),
+		)
+		.await
+		.unwrap_err();
+
+		assert_eq!(
+			error
+				.as_database_error()
+				.unwrap()
+				.downcast_ref::<TimesheetOverlap>(),
+			&TimesheetOverlap {
+				conflicting_id: timesheet.id,
+				employee_id: employee.id,
+				timesheet_id: None,
+			},
+		);
+		// }}}
+	}
 }
@@ -1,10 +1,10 @@
 use clinvoice_adapter::{schema::columns::TimesheetColumns, Updatable};
 use clinvoice_schema::{Expense, Timesheet};
-use sqlx::{Postgres, Result, Transaction};
+use sqlx::{Connection, Postgres, Result, Transaction};
 
 use super::PgTimesheet;
 use crate::{
-	schema::{PgEmployee, PgExpenses, PgJob},
+	schema::{util, PgEmployee, PgExpenses, PgJob},
 	PgSchema,
 };
 
@@ -31,7 +31,9 @@ impl Updatable for PgTimesheet
 			return Ok(());
 		}
 
-		PgSchema::update(connection, TimesheetColumns::default(), |query| {
+		// NOTE: a savepoint, so that the `connection` can still find what an overlap conflicts with.
+		let mut savepoint = connection.begin().await?;
+		let result = PgSchema::update(&mut savepoint, TimesheetColumns::default(), |query| {
 			query.push_values(peekable_entities, |mut q, e| {
 				q.push_bind(e.employee.id)
 					.push_bind(e.id)
@@ -41,7 +43,33 @@ impl Updatable for PgTimesheet
 					.push_bind(&e.work_notes);
 			});
 		})
-		.await?;
+		.await;
+
+		if let Err(e) = result
+		{
+			savepoint.rollback().await?;
+			let error = PgTimesheet::overlap_or(
+				connection,
+				e,
+				entities
+					.clone()
+					.map(|t| (Some(t.id), t.employee.id, t.time_begin, t.time_end)),
+			)
+			.await;
+
+			return Err(util::validation_or(error, |field| {
+				util::sole_value_of(entities.clone(), |t| match field
+				{
+					"employee_id" => Some(t.employee.id.to_string()),
+					"job_id" => Some(t.job.id.to_string()),
+					"time_begin" => Some(t.time_begin.to_string()),
+					"time_end" => t.time_end.map(|d| d.to_string()),
+					_ => None,
+				})
+			}));
+		}
+
+		savepoint.commit().await?;
 
 		let employees = entities.clone().map(|e| &e.employee);
 
@@ -82,7 +110,16 @@ mod tests
 
 	use crate::{
 		fmt::DateTimeExt,
-		schema::{util, PgEmployee, PgExpenses, PgJob, PgLocation, PgOrganization, PgTimesheet},
+		schema::{
+			util,
+			PgEmployee,
+			PgExpenses,
+			PgJob,
+			PgLocation,
+			PgOrganization,
+			PgTimesheet,
+			TimesheetOverlap,
+		},
 	};
 
 	#[tokio::test]
@@ -186,12 +223,11 @@ mod tests
 			transaction.commit().await.unwrap();
 		}
 
-		let db_timesheet =
-			PgTimesheet::retrieve(&connection, &timesheet.id.into())
-				.await
-				.unwrap()
-				.pop()
-				.unwrap();
+		let db_timesheet = PgTimesheet::retrieve(&connection, &timesheet.id.into())
+			.await
+			.unwrap()
+			.pop()
+			.unwrap();
 
 		assert_eq!(timesheet.id, db_timesheet.id);
 		assert_eq!(timesheet.employee, db_timesheet.employee);
@@ -204,4 +240,81 @@ mod tests
 		assert_eq!(timesheet.time_end.pg_sanitize(), db_timesheet.time_end);
 		assert_eq!(timesheet.work_notes, db_timesheet.work_notes);
 	}
+
+	#[tokio::test]
+	async fn update_overlap()
+	{
+		let connection = util::connect().await;
+
+		let employee = util::create_employee(&connection).await;
+		let job = PgLocation::create(&connection, "Earth".into(), None)
+			.and_then(|earth| PgOrganization::create(&connection, earth, "Some Organization".into()))
+			.and_then(|organization| {
+				PgJob::create(
+					&connection,
+					organization,
+					None,
+					chrono::Utc::now(),
+					Duration::from_secs(900),
+					Default::default(),
+					Default::default(),
+					Default::default(),
+				)
+			})
+			.await
+			.unwrap();
+
+		let now = chrono::Utc::now();
+
+		// {{{
+		let mut transaction = connection.begin().await.unwrap();
+
+		let timesheet = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job.clone(),
+			now - chrono::Duration::hours(2),
+			Some(now - chrono::Duration::hours(1)),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		let mut timesheet2 = PgTimesheet::create(
+			&mut transaction,
+			employee.clone(),
+			Vec::new(),
+			job,
+			now - chrono::Duration::hours(1),
+			Some(now),
+			String::new(),
+		)
+		.await
+		.unwrap();
+
+		transaction.commit().await.unwrap();
+		// }}}
+
+		timesheet2.time_begin = timesheet.time_begin + chrono::Duration::minutes(30);
+
+		{
+			let mut transaction = connection.begin().await.unwrap();
+			let error = PgTimesheet::update(&mut transaction, [&timesheet2].into_iter())
+				.await
+				.unwrap_err();
+
+			assert_eq!(
+				error
+					.as_database_error()
+					.unwrap()
+					.downcast_ref::<TimesheetOverlap>(),
+				&TimesheetOverlap {
+					conflicting_id: timesheet.id,
+					employee_id: employee.id,
+					timesheet_id: Some(timesheet2.id),
+				},
+			);
+		}
+	}
 }
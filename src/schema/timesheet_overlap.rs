@@ -0,0 +1,89 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use std::{borrow::Cow, error::Error};
+
+use clinvoice_schema::Id;
+use sqlx::error::DatabaseError;
+
+use super::error::EXCLUSION_VIOLATION;
+
+/// A [`Timesheet`](clinvoice_schema::Timesheet) could not be created, updated, or restored, because
+/// an [`Employee`](clinvoice_schema::Employee) cannot work two [`Timesheet`]s at the same time.
+///
+/// Returned inside of an [`Error::Database`](sqlx::Error::Database) (or an
+/// [`ExclusionViolation`](super::Error::ExclusionViolation)), from which it may be retrieved using
+/// [`DatabaseError::try_downcast_ref`].
+///
+/// [`Timesheet`]: clinvoice_schema::Timesheet
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TimesheetOverlap
+{
+	/// The [`Id`] of the [`Timesheet`](clinvoice_schema::Timesheet) which was already being worked
+	/// on during the same time.
+	pub conflicting_id: Id,
+
+	/// The [`Id`] of the [`Employee`](clinvoice_schema::Employee) whose timesheets overlap.
+	pub employee_id: Id,
+
+	/// The [`Id`] of the [`Timesheet`](clinvoice_schema::Timesheet) which was being updated or
+	/// restored, or [`None`] if it was being created.
+	pub timesheet_id: Option<Id>,
+}
+
+impl TimesheetOverlap
+{
+	/// The name of the constraint which prevents [`TimesheetOverlap`]s.
+	pub(super) const CONSTRAINT: &'static str = "timesheets__no_overlap";
+}
+
+impl DatabaseError for TimesheetOverlap
+{
+	fn message(&self) -> &str
+	{
+		"the timesheet overlaps another timesheet of the same employee"
+	}
+
+	fn code(&self) -> Option<Cow<'_, str>>
+	{
+		Some(EXCLUSION_VIOLATION.into())
+	}
+
+	fn as_error(&self) -> &(dyn Error + Send + Sync + 'static)
+	{
+		self
+	}
+
+	fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static)
+	{
+		self
+	}
+
+	fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static>
+	{
+		self
+	}
+
+	fn constraint(&self) -> Option<&str>
+	{
+		Some(Self::CONSTRAINT)
+	}
+}
+
+impl Display for TimesheetOverlap
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult
+	{
+		match self.timesheet_id
+		{
+			Some(id) => write!(f, "timesheet #{id}"),
+			None => write!(f, "the new timesheet"),
+		}?;
+
+		write!(
+			f,
+			" overlaps timesheet #{} of employee #{}",
+			self.conflicting_id, self.employee_id
+		)
+	}
+}
+
+impl Error for TimesheetOverlap {}
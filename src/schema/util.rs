@@ -1,10 +1,67 @@
-use core::time::Duration;
+use core::{fmt::Display, future::Future, time::Duration};
 use std::io;
 
-use clinvoice_finance::Error as FinanceError;
-use sqlx::{postgres::types::PgInterval, Error, Result};
+use clinvoice_adapter::{
+	fmt::{sql, QueryBuilderExt, SnakeCase},
+	WriteContext,
+};
+use clinvoice_finance::{Currency, Decimal, Error as FinanceError, Money};
+use futures::{
+	channel::mpsc::{self, Sender},
+	future,
+	stream::{self, BoxStream, TryChunksError},
+	FutureExt,
+	SinkExt,
+	StreamExt,
+	TryStreamExt,
+};
+use sqlx::{
+	postgres::{types::PgInterval, PgDatabaseError, PgRow},
+	Error,
+	Pool,
+	Postgres,
+	QueryBuilder,
+	Result,
+	Row,
+};
 #[cfg(test)]
-use {lazy_static::lazy_static, sqlx::PgPool};
+use {
+	super::{PgEmployee, PgJob, PgLocation, PgOrganization},
+	clinvoice_adapter::schema::{EmployeeAdapter, JobAdapter, LocationAdapter, OrganizationAdapter},
+	clinvoice_schema::{
+		chrono::{DateTime, Utc},
+		Employee,
+		Invoice,
+		Job,
+		Organization,
+	},
+	core::sync::atomic::{AtomicUsize, Ordering},
+	futures::future::BoxFuture,
+	lazy_static::lazy_static,
+	sqlx::{
+		postgres::{PgQueryResult, PgStatement, PgTypeInfo},
+		Describe,
+		Either,
+		Execute,
+		Executor,
+		PgPool,
+	},
+};
+
+use super::ValidationError;
+
+/// Selects the number of rows which match a query, in place of their columns.
+pub(super) const COUNT: &str = "count(*)";
+
+/// The column which is set when a row is [soft deleted](super::SoftDeletable::soft_delete).
+pub(super) const DELETED_AT: &str = "deleted_at";
+
+/// Selects whether any rows match a query, in place of their columns. The query must be followed by
+/// a `)`.
+pub(super) const EXISTS: &str = "EXISTS (SELECT 1";
+
+/// The maximum number of rows which [`stream_views`] converts at once.
+const STREAM_CHUNK_SIZE: usize = 100;
 
 #[cfg(test)]
 pub(super) async fn connect() -> PgPool
@@ -16,6 +73,145 @@ pub(super) async fn connect() -> PgPool
 	PgPool::connect_lazy(&URL).unwrap()
 }
 
+/// Create an [`Employee`] for tests which need one, but do not care about its details.
+#[cfg(test)]
+pub(super) async fn create_employee(connection: &PgPool) -> Employee
+{
+	PgEmployee::create(
+		connection,
+		"My Name".into(),
+		"Employed".into(),
+		"Janitor".into(),
+	)
+	.await
+	.unwrap()
+}
+
+/// Create a [`Job`] for the `client`, which was opened at `date_open` and is billed at the
+/// `hourly_rate`, for tests which do not care about its other details.
+#[cfg(test)]
+pub(super) async fn create_job(
+	connection: &PgPool,
+	client: Organization,
+	date_open: DateTime<Utc>,
+	hourly_rate: Money,
+) -> Job
+{
+	PgJob::create(
+		connection,
+		client,
+		None,
+		date_open,
+		Duration::from_secs(900),
+		Invoice {
+			date: None,
+			hourly_rate,
+		},
+		String::new(),
+		"Do something".into(),
+	)
+	.await
+	.unwrap()
+}
+
+/// Create an [`Organization`] (along with its [`Location`](clinvoice_schema::Location)) for tests
+/// which need one, but do not care about its details.
+#[cfg(test)]
+pub(super) async fn create_organization(connection: &PgPool) -> Organization
+{
+	let earth = PgLocation::create(connection, "Earth".into(), None)
+		.await
+		.unwrap();
+
+	PgOrganization::create(connection, earth, "Some Organization".into())
+		.await
+		.unwrap()
+}
+
+/// An [`Executor`] which counts the number of round-trips made to the database via a [`PgPool`].
+#[cfg(test)]
+#[derive(Debug)]
+pub(super) struct RoundTrips<'p>
+{
+	count: AtomicUsize,
+	pool: &'p PgPool,
+}
+
+#[cfg(test)]
+impl<'p> RoundTrips<'p>
+{
+	/// Start counting the round-trips made via the `pool`.
+	pub(super) const fn new(pool: &'p PgPool) -> Self
+	{
+		Self {
+			count: AtomicUsize::new(0),
+			pool,
+		}
+	}
+
+	/// Get the number of round-trips made so far.
+	pub(super) fn count(&self) -> usize
+	{
+		self.count.load(Ordering::SeqCst)
+	}
+}
+
+#[cfg(test)]
+impl<'c> Executor<'c> for &'c RoundTrips<'_>
+{
+	type Database = Postgres;
+
+	fn fetch_many<'e, 'q: 'e, E: 'q>(
+		self,
+		query: E,
+	) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>>>
+	where
+		'c: 'e,
+		E: Execute<'q, Postgres>,
+	{
+		self.count.fetch_add(1, Ordering::SeqCst);
+		self.pool.fetch_many(query)
+	}
+
+	fn fetch_optional<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<Option<PgRow>>>
+	where
+		'c: 'e,
+		E: Execute<'q, Postgres>,
+	{
+		self.count.fetch_add(1, Ordering::SeqCst);
+		self.pool.fetch_optional(query)
+	}
+
+	fn prepare_with<'e, 'q: 'e>(
+		self,
+		sql: &'q str,
+		parameters: &'e [PgTypeInfo],
+	) -> BoxFuture<'e, Result<PgStatement<'q>>>
+	where
+		'c: 'e,
+	{
+		self.count.fetch_add(1, Ordering::SeqCst);
+		self.pool.prepare_with(sql, parameters)
+	}
+
+	fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<Postgres>>>
+	where
+		'c: 'e,
+	{
+		self.count.fetch_add(1, Ordering::SeqCst);
+		self.pool.describe(sql)
+	}
+}
+
+/// Get the name of the column which stores the [`Currency`] of the amount stored in some other
+/// `column` (e.g. `cost` → `cost_currency`).
+pub(super) fn currency_column<T>(column: T) -> SnakeCase<T, &'static str>
+where
+	T: Display,
+{
+	SnakeCase::from((column, "currency"))
+}
+
 pub(super) fn duration_from(interval: PgInterval) -> Result<Duration>
 {
 	if interval.months > 0
@@ -50,17 +246,180 @@ pub(super) fn duration_from(interval: PgInterval) -> Result<Duration>
 }
 
 /// Map some [error](clinvoice_finance::Error) `e` to an [`Error`].
+///
+/// The `e` is kept as the source of the [`Error`], so that it can be recovered as a
+/// [`schema::Error::Finance`](super::Error::Finance).
 pub(super) fn finance_err_to_sqlx(e: FinanceError) -> Error
 {
 	match e
 	{
-		FinanceError::Decimal(e2) => Error::Decode(e2.into()),
-		FinanceError::Decode(..) => Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)),
-		FinanceError::Io(e2) => Error::Io(e2),
-		FinanceError::Reqwest(e2) => Error::Io(io::Error::new(io::ErrorKind::Other, e2)),
-		FinanceError::UnsupportedCurrency(_) => Error::Decode(e.into()),
-		FinanceError::Zip(e2) => Error::Io(io::Error::new(io::ErrorKind::InvalidData, e2)),
+		FinanceError::Decimal(_) | FinanceError::UnsupportedCurrency(_) => Error::Decode(e.into()),
+		FinanceError::Decode(..) | FinanceError::Zip(_) =>
+		{
+			Error::Io(io::Error::new(io::ErrorKind::InvalidData, e))
+		},
+		FinanceError::Io(ref e2) => Error::Io(io::Error::new(e2.kind(), e)),
+		FinanceError::Reqwest(_) => Error::Io(io::Error::new(io::ErrorKind::Other, e)),
+	}
+}
+
+/// Turn the `error` into a [`ValidationError`] if it was caused by one of the constraints of the
+/// schema, using `value_of` to get the value of the field which was rejected (by its name).
+pub(super) fn validation_or<F>(error: Error, value_of: F) -> Error
+where
+	F: FnOnce(&str) -> Option<String>,
+{
+	// NOTE: errors which were already turned into something else (e.g. a `TimesheetOverlap`) are
+	//       left alone.
+	match error
+		.as_database_error()
+		.filter(|e| e.try_downcast_ref::<PgDatabaseError>().is_some())
+		.and_then(ValidationError::from_database_error)
+	{
+		Some(e) => Error::Database(Box::new(e.or_value(value_of))),
+		None => error,
+	}
+}
+
+/// Get a value using `value_of` if there is exactly one of the `entities`, so that
+/// [`validation_or`] can report the value which was rejected when they are written. When there
+/// are more, the database does not say which of them was rejected, so [`None`] is returned.
+pub(super) fn sole_value_of<'i, T, TIter, F>(mut entities: TIter, value_of: F) -> Option<String>
+where
+	T: 'i,
+	TIter: Iterator<Item = &'i T>,
+	F: FnOnce(&'i T) -> Option<String>,
+{
+	match (entities.next(), entities.next())
+	{
+		(Some(entity), None) => value_of(entity),
+		_ => None,
+	}
+}
+
+/// Whether the `error` was caused by violating the `constraint` with the given name.
+pub(super) fn violates(error: &Error, constraint: &str) -> bool
+{
+	error
+		.as_database_error()
+		.and_then(|e| e.constraint())
+		.map_or(false, |c| c == constraint)
+}
+
+/// Append a condition to the `query` (in the given `context`) which excludes the rows of the table
+/// with the `alias` that were [soft deleted](super::SoftDeletable::soft_delete), unless
+/// `include_deleted` is `true`.
+pub(super) fn write_not_deleted<TAlias>(
+	context: WriteContext,
+	alias: TAlias,
+	include_deleted: bool,
+	query: &mut QueryBuilder<Postgres>,
+) -> WriteContext
+where
+	TAlias: Display,
+{
+	if include_deleted
+	{
+		return context;
 	}
+
+	query
+		.push(context)
+		.push(alias)
+		.push('.')
+		.push(DELETED_AT)
+		.push(sql::IS)
+		.push(sql::NULL);
+
+	WriteContext::AcceptingAnotherWhereCondition
+}
+
+/// Create [`Money`] from the `amount` and `currency` which were stored in the database.
+pub(super) fn money_from(amount: Decimal, currency: &str) -> Result<Money>
+{
+	currency
+		.parse::<Currency>()
+		.map(|c| Money {
+			amount,
+			currency: c,
+		})
+		.map_err(finance_err_to_sqlx)
+}
+
+/// Get the [`Money`] stored in some `amount` column of a `row` (and its [`currency_column`]).
+pub(super) fn money_from_row(row: &PgRow, amount: &str) -> Result<Money>
+{
+	let currency = row.try_get::<String, _>(currency_column(amount).to_string().as_str())?;
+	row.try_get(amount).and_then(|a| money_from(a, &currency))
+}
+
+/// Create a [`Stream`](futures::Stream) of the entities which the `query` selects (via
+/// `connection`), which yields them as the rows arrive instead of after all of them have.
+///
+/// The rows are given to `rows_to_views` up to [`STREAM_CHUNK_SIZE`] at a time, so that it can
+/// retrieve anything else the entities need (e.g. their [`Location`](clinvoice_schema::Location)s)
+/// in one query per chunk.
+pub(super) fn stream_views<'a, T, TQuery, TRowsToViews, TViews>(
+	connection: &'a Pool<Postgres>,
+	query: TQuery,
+	rows_to_views: TRowsToViews,
+) -> BoxStream<'a, Result<T, super::Error>>
+where
+	T: Send + 'a,
+	TQuery: Future<Output = Result<QueryBuilder<'a, Postgres>>> + Send + 'a,
+	TRowsToViews: Fn(Vec<PgRow>) -> TViews + Send + Sync + 'a,
+	TViews: Future<Output = Result<Vec<T>>> + Send + 'a,
+{
+	/// Send each entity which the `query` selects to the `sender`, until there are none left or
+	/// the receiver has been dropped.
+	async fn send<'a, T, TQuery, TRowsToViews, TViews>(
+		connection: &'a Pool<Postgres>,
+		query: TQuery,
+		rows_to_views: TRowsToViews,
+		sender: &mut Sender<Result<T, super::Error>>,
+	) -> Result<()>
+	where
+		TQuery: Future<Output = Result<QueryBuilder<'a, Postgres>>>,
+		TRowsToViews: Fn(Vec<PgRow>) -> TViews,
+		TViews: Future<Output = Result<Vec<T>>>,
+	{
+		let mut query = query.await?;
+		let mut chunks = query
+			.prepare()
+			.fetch(connection)
+			.try_chunks(STREAM_CHUNK_SIZE);
+
+		while let Some(rows) = chunks.try_next().await.map_err(|TryChunksError(_, e)| e)?
+		{
+			for view in rows_to_views(rows).await?
+			{
+				// The stream was dropped, so there is nobody left to send to.
+				if sender.send(Ok(view)).await.is_err()
+				{
+					return Ok(());
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	// NOTE: the rows are fetched by a query which borrows the `QueryBuilder`, so both have to be
+	//       owned by a future rather than by the stream. The stream polls that future alongside
+	//       the channel which it sends the entities through.
+	let (mut sender, receiver) = mpsc::channel(0);
+	let producer = async move {
+		if let Err(e) = send(connection, query, rows_to_views, &mut sender).await
+		{
+			sender.send(Err(e.into())).await.ok();
+		}
+	};
+
+	stream::select(
+		receiver,
+		producer.into_stream().filter_map(|()| future::ready(None)),
+	)
+	.boxed()
 }
 
 #[cfg(test)]
@@ -0,0 +1,680 @@
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use std::{borrow::Cow, error::Error};
+
+use sqlx::{error::DatabaseError, postgres::PgDatabaseError};
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of a
+/// `foreign_key_violation`.
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of a
+/// `unique_violation`.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// Each constraint of the schema, along with the field it constrains and the [`ValidationRule`]
+/// which it enforces. The table (or domain) of the constraint is the part of its name before `__`.
+///
+/// For foreign keys, the [`ValidationRule`] is the one which applies when the row with the foreign
+/// key is written. When the row it refers to is written instead, the rule is
+/// [`ValidationRule::NotReferenced`].
+///
+/// `invoices__numbers_gap_free` is not declared as a constraint, but its trigger raises errors
+/// which name it as one.
+const CONSTRAINTS: &[(&str, &str, ValidationRule)] = &[
+	(
+		"audit_log__operation_is_known",
+		"operation",
+		ValidationRule::Format,
+	),
+	("audit_log__pk", "id", ValidationRule::Unique),
+	(
+		"audit_log__values_of_operation",
+		"new_values",
+		ValidationRule::ConsistentWith("operation"),
+	),
+	(
+		"billing_rates__currency_of_job_fk",
+		"hourly_rate_currency",
+		ValidationRule::ConsistentWith("job_id"),
+	),
+	(
+		"billing_rates__employee_fk",
+		"employee_id",
+		ValidationRule::Exists,
+	),
+	(
+		"billing_rates__hourly_rate_is_not_negative",
+		"hourly_rate",
+		ValidationRule::AtLeast(0),
+	),
+	(
+		"billing_rates__is_variant",
+		"employee_id",
+		ValidationRule::OneOf(&["employee_id", "employee_title"]),
+	),
+	(
+		"billing_rates__job_employee_uq",
+		"employee_id",
+		ValidationRule::Unique,
+	),
+	("billing_rates__job_fk", "job_id", ValidationRule::Exists),
+	(
+		"billing_rates__job_title_uq",
+		"employee_title",
+		ValidationRule::Unique,
+	),
+	("billing_rates__pk", "id", ValidationRule::Unique),
+	(
+		"contact_information__address_fk",
+		"address_id",
+		ValidationRule::Exists,
+	),
+	(
+		"contact_information__email_is_valid",
+		"email",
+		ValidationRule::Format,
+	),
+	(
+		"contact_information__is_variant",
+		"address_id",
+		ValidationRule::OneOf(&["address_id", "email", "other", "phone"]),
+	),
+	(
+		"contact_information__phone_is_valid",
+		"phone",
+		ValidationRule::Format,
+	),
+	("contact_information__pk", "label", ValidationRule::Unique),
+	("currency__is_code", "currency", ValidationRule::Format),
+	("employees__pk", "id", ValidationRule::Unique),
+	("exchange_rates__pk", "currency", ValidationRule::Unique),
+	(
+		"exchange_rates__rate_is_positive",
+		"rate",
+		ValidationRule::GreaterThan(0),
+	),
+	(
+		"expense_tax_rates__expense_fk",
+		"expense_id",
+		ValidationRule::Exists,
+	),
+	(
+		"expense_tax_rates__pk",
+		"tax_rate_id",
+		ValidationRule::Unique,
+	),
+	(
+		"expense_tax_rates__tax_rate_fk",
+		"tax_rate_id",
+		ValidationRule::Exists,
+	),
+	("expenses__pk", "id", ValidationRule::Unique),
+	(
+		"expenses__timesheet_fk",
+		"timesheet_id",
+		ValidationRule::Exists,
+	),
+	(
+		"invoice_lines__adjustment_fk",
+		"adjustment_id",
+		ValidationRule::Exists,
+	),
+	(
+		"invoice_lines__expense_fk",
+		"expense_id",
+		ValidationRule::Exists,
+	),
+	(
+		"invoice_lines__expense_uq",
+		"expense_id",
+		ValidationRule::Unique,
+	),
+	(
+		"invoice_lines__invoice_fk",
+		"invoice_id",
+		ValidationRule::Exists,
+	),
+	(
+		"invoice_lines__is_variant",
+		"adjustment_id",
+		ValidationRule::OneOf(&["adjustment_id", "expense_id", "timesheet_id"]),
+	),
+	("invoice_lines__pk", "id", ValidationRule::Unique),
+	(
+		"invoice_lines__timesheet_fk",
+		"timesheet_id",
+		ValidationRule::Exists,
+	),
+	(
+		"invoice_lines__timesheet_uq",
+		"timesheet_id",
+		ValidationRule::Unique,
+	),
+	(
+		"invoice_numbers__number_is_positive",
+		"number",
+		ValidationRule::AtLeast(0),
+	),
+	("invoice_numbers__pk", "year", ValidationRule::Unique),
+	(
+		"invoices__date_integrity",
+		"date_paid",
+		ValidationRule::After("date_issued"),
+	),
+	("invoices__id_job_uq", "id", ValidationRule::Unique),
+	("invoices__job_fk", "job_id", ValidationRule::Exists),
+	(
+		"invoices__number_is_positive",
+		"number",
+		ValidationRule::GreaterThan(0),
+	),
+	(
+		"invoices__numbers_gap_free",
+		"number",
+		ValidationRule::MostRecent,
+	),
+	("invoices__pk", "id", ValidationRule::Unique),
+	(
+		"invoices__year_is_issued_year",
+		"year",
+		ValidationRule::ConsistentWith("date_issued"),
+	),
+	("invoices__year_number_uq", "number", ValidationRule::Unique),
+	(
+		"job_adjustments__amount_has_currency",
+		"amount_currency",
+		ValidationRule::ConsistentWith("amount"),
+	),
+	(
+		"job_adjustments__currency_of_job_fk",
+		"amount_currency",
+		ValidationRule::ConsistentWith("job_id"),
+	),
+	(
+		"job_adjustments__is_variant",
+		"amount",
+		ValidationRule::OneOf(&["amount", "percentage"]),
+	),
+	("job_adjustments__job_fk", "job_id", ValidationRule::Exists),
+	(
+		"job_adjustments__percentage_is_at_least_minus_100",
+		"percentage",
+		ValidationRule::AtLeast(-100),
+	),
+	("job_adjustments__pk", "id", ValidationRule::Unique),
+	("job_tax_rates__job_fk", "job_id", ValidationRule::Exists),
+	("job_tax_rates__pk", "tax_rate_id", ValidationRule::Unique),
+	(
+		"job_tax_rates__tax_rate_fk",
+		"tax_rate_id",
+		ValidationRule::Exists,
+	),
+	("jobs__client_fk", "client_id", ValidationRule::Exists),
+	(
+		"jobs__date_integrity",
+		"date_close",
+		ValidationRule::After("date_open"),
+	),
+	("jobs__id_currency_uq", "id", ValidationRule::Unique),
+	(
+		"jobs__invoice_date_integrity",
+		"invoice_date_paid",
+		ValidationRule::After("invoice_date_issued"),
+	),
+	("jobs__pk", "id", ValidationRule::Unique),
+	(
+		"locations__not_outside_self",
+		"outer_id",
+		ValidationRule::NotSelf,
+	),
+	("locations__outer_fk", "outer_id", ValidationRule::Exists),
+	("locations__pk", "id", ValidationRule::Unique),
+	(
+		"organizations__location_fk",
+		"location_id",
+		ValidationRule::Exists,
+	),
+	("organizations__pk", "id", ValidationRule::Unique),
+	(
+		"payments__amount_is_positive",
+		"amount",
+		ValidationRule::GreaterThan(0),
+	),
+	(
+		"payments__invoice_of_job_fk",
+		"invoice_id",
+		ValidationRule::ConsistentWith("job_id"),
+	),
+	("payments__job_fk", "job_id", ValidationRule::Exists),
+	("payments__pk", "id", ValidationRule::Unique),
+	(
+		"tax_rates__location_fk",
+		"location_id",
+		ValidationRule::Exists,
+	),
+	(
+		"tax_rates__percentage_is_not_negative",
+		"percentage",
+		ValidationRule::AtLeast(0),
+	),
+	("tax_rates__pk", "id", ValidationRule::Unique),
+	(
+		"timesheets__date_integrity",
+		"time_end",
+		ValidationRule::After("time_begin"),
+	),
+	(
+		"timesheets__employee_fk",
+		"employee_id",
+		ValidationRule::Exists,
+	),
+	(
+		"timesheets__employee_job_time_uq",
+		"time_begin",
+		ValidationRule::Unique,
+	),
+	("timesheets__job_fk", "job_id", ValidationRule::Exists),
+	(
+		"timesheets__no_overlap",
+		"time_begin",
+		ValidationRule::NoOverlap,
+	),
+	("timesheets__pk", "id", ValidationRule::Unique),
+];
+
+/// A rule which the value of some field must follow.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ValidationRule
+{
+	/// The value must be later than the value of the field with this name.
+	After(&'static str),
+
+	/// The value must be greater than or equal to this.
+	AtLeast(i64),
+
+	/// The value must agree with the value of the field with this name (e.g. the `year` of an
+	/// invoice must be the year its `date_issued` is in).
+	ConsistentWith(&'static str),
+
+	/// The value must refer to something which exists.
+	Exists,
+
+	/// The value must be in the expected format (e.g. an email address must contain an `@`).
+	Format,
+
+	/// The value must be greater than this.
+	GreaterThan(i64),
+
+	/// Only the row with the most recent value may be deleted.
+	MostRecent,
+
+	/// The value must not overlap the value of another row.
+	NoOverlap,
+
+	/// The field must not refer to the value when it is changed or deleted.
+	NotReferenced,
+
+	/// The value must not refer to the row it is a part of.
+	NotSelf,
+
+	/// Exactly one of the fields with these names must be set.
+	OneOf(&'static [&'static str]),
+
+	/// The value must not be the same as the value of any other row.
+	Unique,
+}
+
+/// Some value was rejected by one of the constraints of the database (e.g. `jobs__date_integrity`).
+///
+/// Every write which one of the constraints rejects returns this inside of an
+/// [`Error::Database`](sqlx::Error::Database), from which it may be retrieved using
+/// [`DatabaseError::try_downcast_ref`]. Errors which were not already turned into a
+/// [`ValidationError`] may be converted using [`ValidationError::from_database_error`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ValidationError
+{
+	/// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) of the
+	/// original error.
+	code: Option<String>,
+
+	/// The name of the constraint which was violated.
+	pub constraint: &'static str,
+
+	/// The name of the field which was rejected.
+	pub field: &'static str,
+
+	/// A human-readable description of what went wrong.
+	message: String,
+
+	/// The rule which the `field` did not follow.
+	pub rule: ValidationRule,
+
+	/// The name of the table (or domain) which the `field` is a part of.
+	pub table: &'static str,
+
+	/// The value which was rejected, if it is known.
+	pub value: Option<String>,
+}
+
+impl ValidationError
+{
+	/// Create a [`ValidationError`] from an `error` which the database returned, if it was caused
+	/// by one of the constraints of the schema.
+	///
+	/// The [`value`](ValidationError::value) is only known for foreign keys and unique constraints,
+	/// since only their violations report it.
+	pub fn from_database_error(error: &dyn DatabaseError) -> Option<Self>
+	{
+		let constraint = error.constraint()?;
+		let &(constraint, field, rule) = CONSTRAINTS.iter().find(|(c, ..)| *c == constraint)?;
+
+		let code = error.code().map(Cow::into_owned);
+		let key = match code.as_deref()
+		{
+			Some(FOREIGN_KEY_VIOLATION | UNIQUE_VIOLATION) => error
+				.try_downcast_ref::<PgDatabaseError>()
+				.and_then(PgDatabaseError::detail)
+				.and_then(parse_key),
+			_ => None,
+		};
+
+		let rule = match (code.as_deref(), &key)
+		{
+			// NOTE: when a row which is still referred to is written, the key is that of the row.
+			(Some(FOREIGN_KEY_VIOLATION), Some((columns, _))) if !columns.contains(&field) =>
+			{
+				ValidationRule::NotReferenced
+			},
+			_ => rule,
+		};
+
+		let value = key.map(
+			|(columns, values)| match columns.iter().position(|c| c == field)
+			{
+				Some(i) if values.len() == columns.len() => values[i].to_owned(),
+				_ => values.join(", "),
+			},
+		);
+
+		Some(Self::new(code, constraint, field, rule, value))
+	}
+
+	/// Create a new [`ValidationError`], along with its message.
+	fn new(
+		code: Option<String>,
+		constraint: &'static str,
+		field: &'static str,
+		rule: ValidationRule,
+		value: Option<String>,
+	) -> Self
+	{
+		let table = constraint.split_once("__").map_or(constraint, |(t, _)| t);
+		let message = match rule
+		{
+			ValidationRule::After(other) => format!("{table}.{field} must be after {other}"),
+			ValidationRule::AtLeast(n) => format!("{table}.{field} must be at least {n}"),
+			ValidationRule::ConsistentWith(other) =>
+			{
+				format!("{table}.{field} must be consistent with {other}")
+			},
+			ValidationRule::Exists => format!("{table}.{field} must refer to something which exists"),
+			ValidationRule::Format => format!("{table}.{field} is not in a valid format"),
+			ValidationRule::GreaterThan(n) => format!("{table}.{field} must be greater than {n}"),
+			ValidationRule::MostRecent =>
+			{
+				format!("only the {table} with the most recent {field} can be deleted")
+			},
+			ValidationRule::NoOverlap => format!("{table}.{field} must not overlap another row"),
+			ValidationRule::NotReferenced => format!("it is still referred to by {table}.{field}"),
+			ValidationRule::NotSelf => format!("{table}.{field} must not refer to itself"),
+			ValidationRule::OneOf(fields) =>
+			{
+				format!("exactly one of {table}.{} must be set", fields.join(", "))
+			},
+			ValidationRule::Unique => format!("{table}.{field} must be unique"),
+		};
+
+		Self {
+			code,
+			constraint,
+			field,
+			message,
+			rule,
+			table,
+			value,
+		}
+	}
+
+	/// Set the [`value`](ValidationError::value) of this error, if it is not already known, using
+	/// `value_of` (which gets the value of some field by its name).
+	pub(super) fn or_value<F>(mut self, value_of: F) -> Self
+	where
+		F: FnOnce(&str) -> Option<String>,
+	{
+		if self.value.is_none()
+		{
+			self.value = value_of(self.field);
+		}
+
+		self
+	}
+}
+
+impl DatabaseError for ValidationError
+{
+	fn message(&self) -> &str
+	{
+		&self.message
+	}
+
+	fn code(&self) -> Option<Cow<'_, str>>
+	{
+		self.code.as_deref().map(Cow::Borrowed)
+	}
+
+	fn as_error(&self) -> &(dyn Error + Send + Sync + 'static)
+	{
+		self
+	}
+
+	fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static)
+	{
+		self
+	}
+
+	fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static>
+	{
+		self
+	}
+
+	fn constraint(&self) -> Option<&str>
+	{
+		Some(self.constraint)
+	}
+}
+
+impl Display for ValidationError
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult
+	{
+		self.message.fmt(f)?;
+
+		if let Some(ref value) = self.value
+		{
+			write!(f, " (got {value})")?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Error for ValidationError {}
+
+/// Get the columns and values of the key in the `detail` of a foreign key or unique violation,
+/// which looks like `Key (a, b)=(1, 2) …`.
+fn parse_key(detail: &str) -> Option<(Vec<&str>, Vec<&str>)>
+{
+	let (columns, values) = detail.split_once(")=(")?;
+	let columns = &columns[columns.find('(')? + 1..];
+	let values = &values[..values.rfind(')')?];
+
+	Some((columns.split(", ").collect(), values.split(", ").collect()))
+}
+
+#[cfg(test)]
+mod tests
+{
+	use core::time::Duration;
+
+	use clinvoice_adapter::{
+		schema::{ContactAdapter, JobAdapter},
+		Deletable,
+		Initializable,
+		Updatable,
+	};
+	use clinvoice_finance::{Currency, Money};
+	use clinvoice_schema::{
+		chrono::{TimeZone, Utc},
+		ContactKind,
+		Invoice,
+	};
+	use pretty_assertions::assert_eq;
+
+	use super::{ValidationError, ValidationRule, CONSTRAINTS};
+	use crate::{
+		schema::{util, PgContact, PgJob, PgLocation},
+		PgSchema,
+	};
+
+	#[tokio::test]
+	async fn constraints()
+	{
+		let connection = util::connect().await;
+		PgSchema::init(&connection).await.unwrap();
+
+		let mut found: Vec<_> = sqlx::query!(
+			r#"SELECT C.conname AS "name!"
+				FROM pg_constraint C
+				JOIN pg_namespace N ON (N.oid = C.connamespace)
+				WHERE N.nspname = current_schema() AND C.contype <> 'n';"#
+		)
+		.fetch_all(&connection)
+		.await
+		.unwrap()
+		.into_iter()
+		.map(|row| row.name)
+		.collect();
+
+		// NOTE: raised by the trigger of the same name (with a matching `CONSTRAINT`), since a
+		//       constraint cannot check that deleting a row leaves no gap behind it.
+		found.push("invoices__numbers_gap_free".into());
+		found.sort_unstable();
+
+		let mut known: Vec<_> = CONSTRAINTS.iter().map(|(c, ..)| *c).collect();
+		known.sort_unstable();
+
+		assert_eq!(found, known);
+	}
+
+	#[tokio::test]
+	async fn from_database_error()
+	{
+		let connection = util::connect().await;
+		let organization = util::create_organization(&connection).await;
+		let earth = organization.location.clone();
+
+		let date_close = Utc.ymd(1990, 07, 11).and_hms(14, 10, 00);
+		let error = PgJob::create(
+			&connection,
+			organization,
+			Some(date_close),
+			Utc.ymd(1990, 07, 12).and_hms(14, 10, 00),
+			Duration::from_secs(900),
+			Invoice {
+				date: None,
+				hourly_rate: Money::new(20_00, 2, Currency::Usd),
+			},
+			String::new(),
+			"Do something".into(),
+		)
+		.await
+		.unwrap_err();
+
+		let validation = error
+			.as_database_error()
+			.unwrap()
+			.try_downcast_ref::<ValidationError>()
+			.unwrap();
+
+		assert_eq!(validation.constraint, "jobs__date_integrity");
+		assert_eq!(validation.field, "date_close");
+		assert_eq!(validation.rule, ValidationRule::After("date_open"));
+		assert_eq!(validation.table, "jobs");
+		assert_eq!(validation.value, Some(date_close.to_string()));
+		assert_eq!(
+			validation.to_string(),
+			format!("jobs.date_close must be after date_open (got {date_close})")
+		);
+
+		let error = PgContact::create(
+			&connection,
+			ContactKind::Phone("abc".into()),
+			"Phone".into(),
+		)
+		.await
+		.unwrap_err();
+
+		let validation = error
+			.as_database_error()
+			.unwrap()
+			.try_downcast_ref::<ValidationError>()
+			.unwrap();
+
+		assert_eq!(validation.field, "phone");
+		assert_eq!(validation.rule, ValidationRule::Format);
+		assert_eq!(validation.value.as_deref(), Some("abc"));
+
+		let error = sqlx::query!("DELETE FROM locations WHERE id = $1;", earth.id)
+			.execute(&connection)
+			.await
+			.unwrap_err();
+
+		let validation =
+			ValidationError::from_database_error(error.as_database_error().unwrap()).unwrap();
+
+		assert_eq!(validation.constraint, "organizations__location_fk");
+		assert_eq!(validation.field, "location_id");
+		assert_eq!(validation.rule, ValidationRule::NotReferenced);
+		assert_eq!(validation.value, Some(earth.id.to_string()));
+
+		let error = PgLocation::delete(&connection, [&earth].into_iter())
+			.await
+			.unwrap_err();
+
+		assert_eq!(
+			error
+				.as_database_error()
+				.unwrap()
+				.try_downcast_ref::<ValidationError>(),
+			Some(&validation)
+		);
+
+		let mut inside_self = earth.clone();
+		inside_self.outer = Some(earth.clone().into());
+
+		let mut transaction = connection.begin().await.unwrap();
+		let error = PgLocation::update(&mut transaction, [&inside_self].into_iter())
+			.await
+			.unwrap_err();
+
+		let validation = error
+			.as_database_error()
+			.unwrap()
+			.try_downcast_ref::<ValidationError>()
+			.unwrap();
+
+		assert_eq!(validation.constraint, "locations__not_outside_self");
+		assert_eq!(validation.rule, ValidationRule::NotSelf);
+		assert_eq!(validation.value, Some(earth.id.to_string()));
+	}
+}
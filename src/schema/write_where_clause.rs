@@ -13,6 +13,7 @@ use clinvoice_adapter::{
 	WriteContext,
 	WriteWhereClause,
 };
+use clinvoice_finance::Money;
 use clinvoice_match::{
 	Match,
 	MatchContact,
@@ -29,8 +30,22 @@ use clinvoice_match::{
 };
 use sqlx::{Database, Executor, Postgres, QueryBuilder, Result};
 
-use super::{PgLocation, PgSchema};
-use crate::fmt::{PgInterval, PgTimestampTz};
+use super::{
+	util,
+	AdjustmentColumns,
+	BillingRateColumns,
+	IssuedInvoiceColumns,
+	MatchAdjustment,
+	MatchBillingRate,
+	MatchIssuedInvoice,
+	MatchPayment,
+	MatchTaxRate,
+	PaymentColumns,
+	PgLocation,
+	PgSchema,
+	TaxRateColumns,
+};
+use crate::fmt::{PgInterval, PgMoney, PgTimestampTz};
 
 /// Write [`Match::Any`], [`MatchStr::Any`], [`MatchOption::Any`], or [`MatchSet::Any`] in a way
 /// that will produce valid syntax.
@@ -326,6 +341,96 @@ where
 	}
 }
 
+impl WriteWhereClause<Postgres, &Match<PgMoney>> for PgSchema
+{
+	/// Write a condition that the [`Money`] stored in the `ident` column (and its
+	/// [`util::currency_column`]) matches the `match_condition`.
+	///
+	/// No exchanging is done; [`Money`] only matches other [`Money`] of the same
+	/// [`Currency`](clinvoice_finance::Currency). In particular, a [`Match::InRange`] whose bounds
+	/// have different currencies matches nothing, since the [`Money`] would have to share the
+	/// currency of both bounds.
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &Match<PgMoney>,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		/// Write `({currency_column} = {currency} AND {ident} {comparator} {amount})` for each of the
+		/// `money` (which all share one `comparator`).
+		fn write_money_comparison<TIdent>(
+			query: &mut QueryBuilder<Postgres>,
+			context: WriteContext,
+			ident: TIdent,
+			comparator: &str,
+			money: &[&Money],
+		) where
+			TIdent: Copy + Display,
+		{
+			write_context_scope_start::<_, false>(query, context);
+
+			money.iter().for_each(|m| {
+				query
+					.push(util::currency_column(ident))
+					.push('=')
+					.push_bind(m.currency.to_string())
+					.push(sql::AND);
+			});
+
+			query.push(ident).push(' ').push(comparator).push(' ');
+
+			if let Some((first, rest)) = money.split_first()
+			{
+				query.push_bind(first.amount);
+				rest.iter().for_each(|m| {
+					query.push(sql::AND).push_bind(m.amount);
+				});
+			}
+
+			write_context_scope_end(query);
+		}
+
+		match match_condition
+		{
+			Match::And(conditions) => write_boolean_group::<_, _, _, _, true>(
+				query,
+				context,
+				ident,
+				&mut conditions.iter().filter(|m| *m != &Match::Any),
+			),
+			Match::Any => write_any(query, context),
+			Match::EqualTo(PgMoney(money)) =>
+			{
+				write_money_comparison(query, context, ident, "=", &[money])
+			},
+			Match::GreaterThan(PgMoney(money)) =>
+			{
+				write_money_comparison(query, context, ident, ">", &[money])
+			},
+			Match::InRange(PgMoney(low), PgMoney(high)) =>
+			{
+				write_money_comparison(query, context, ident, sql::BETWEEN, &[low, high])
+			},
+			Match::LessThan(PgMoney(money)) =>
+			{
+				write_money_comparison(query, context, ident, "<", &[money])
+			},
+			Match::Not(condition) => write_negated(query, context, ident, condition.deref()),
+			Match::Or(conditions) => write_boolean_group::<_, _, _, _, false>(
+				query,
+				context,
+				ident,
+				&mut conditions.iter().filter(|m| *m != &Match::Any),
+			),
+		};
+
+		WriteContext::AcceptingAnotherWhereCondition
+	}
+}
+
 impl WriteWhereClause<Postgres, &MatchSet<MatchExpense>> for PgSchema
 {
 	fn write_where_clause<TIdent>(
@@ -516,9 +621,8 @@ impl WriteWhereClause<Postgres, &MatchExpense> for PgSchema
 						&match_condition.category,
 						query,
 					),
-					// NOTE: `cost` is stored as text on the DB
-					columns.typecast("numeric").cost,
-					&match_condition.cost.map_ref(|c| c.amount),
+					columns.cost,
+					&match_condition.cost.map_ref(|c| PgMoney(*c)),
 					query,
 				),
 				columns.description,
@@ -557,9 +661,8 @@ impl WriteWhereClause<Postgres, &MatchInvoice> for PgSchema
 				&match_condition.date_paid,
 				query,
 			),
-			// NOTE: `hourly_rate` is stored as text on the DB
-			columns.typecast("numeric").invoice_hourly_rate,
-			&match_condition.hourly_rate.map_ref(|r| r.amount),
+			columns.invoice_hourly_rate,
+			&match_condition.hourly_rate.map_ref(|r| PgMoney(*r)),
 			query,
 		)
 	}
@@ -672,3 +775,229 @@ impl WriteWhereClause<Postgres, &MatchTimesheet> for PgSchema
 		)
 	}
 }
+
+impl WriteWhereClause<Postgres, &MatchIssuedInvoice> for PgSchema
+{
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &MatchIssuedInvoice,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		let columns = IssuedInvoiceColumns::default().scope(ident);
+
+		PgSchema::write_where_clause(
+			PgSchema::write_where_clause(
+				PgSchema::write_where_clause(
+					PgSchema::write_where_clause(
+						PgSchema::write_where_clause(
+							PgSchema::write_where_clause(
+								context,
+								columns.date_issued,
+								&match_condition.date_issued.map_ref(|d| PgTimestampTz(*d)),
+								query,
+							),
+							columns.date_paid,
+							&match_condition.date_paid.map_ref(|d| PgTimestampTz(*d)),
+							query,
+						),
+						columns.id,
+						&match_condition.id,
+						query,
+					),
+					columns.job_id,
+					&match_condition.job_id,
+					query,
+				),
+				columns.number,
+				&match_condition.number,
+				query,
+			),
+			columns.year,
+			&match_condition.year,
+			query,
+		)
+	}
+}
+
+impl WriteWhereClause<Postgres, &MatchPayment> for PgSchema
+{
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &MatchPayment,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		let columns = PaymentColumns::default().scope(ident);
+
+		PgSchema::write_where_clause(
+			PgSchema::write_where_clause(
+				PgSchema::write_where_clause(
+					PgSchema::write_where_clause(
+						PgSchema::write_where_clause(
+							PgSchema::write_where_clause(
+								PgSchema::write_where_clause(
+									context,
+									columns.amount,
+									&match_condition.amount.map_ref(|a| PgMoney(*a)),
+									query,
+								),
+								columns.date,
+								&match_condition.date.map_ref(|d| PgTimestampTz(*d)),
+								query,
+							),
+							columns.id,
+							&match_condition.id,
+							query,
+						),
+						columns.invoice_id,
+						&match_condition.invoice_id,
+						query,
+					),
+					columns.job_id,
+					&match_condition.job_id,
+					query,
+				),
+				columns.method,
+				&match_condition.method,
+				query,
+			),
+			columns.reference,
+			&match_condition.reference,
+			query,
+		)
+	}
+}
+
+impl WriteWhereClause<Postgres, &MatchTaxRate> for PgSchema
+{
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &MatchTaxRate,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		let columns = TaxRateColumns::default().scope(ident);
+
+		PgSchema::write_where_clause(
+			PgSchema::write_where_clause(
+				PgSchema::write_where_clause(
+					PgSchema::write_where_clause(context, columns.id, &match_condition.id, query),
+					columns.location_id,
+					&match_condition.location_id,
+					query,
+				),
+				columns.name,
+				&match_condition.name,
+				query,
+			),
+			columns.percentage,
+			&match_condition.percentage,
+			query,
+		)
+	}
+}
+
+impl WriteWhereClause<Postgres, &MatchAdjustment> for PgSchema
+{
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &MatchAdjustment,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		let columns = AdjustmentColumns::default().scope(ident);
+
+		PgSchema::write_where_clause(
+			PgSchema::write_where_clause(
+				PgSchema::write_where_clause(context, columns.id, &match_condition.id, query),
+				columns.job_id,
+				&match_condition.job_id,
+				query,
+			),
+			columns.reason,
+			&match_condition.reason,
+			query,
+		)
+	}
+}
+
+impl WriteWhereClause<Postgres, &MatchBillingRate> for PgSchema
+{
+	fn write_where_clause<TIdent>(
+		context: WriteContext,
+		ident: TIdent,
+		match_condition: &MatchBillingRate,
+		query: &mut QueryBuilder<Postgres>,
+	) -> WriteContext
+	where
+		TIdent: Copy + Display,
+	{
+		let columns = BillingRateColumns::default().scope(ident);
+
+		PgSchema::write_where_clause(
+			PgSchema::write_where_clause(
+				PgSchema::write_where_clause(
+					PgSchema::write_where_clause(
+						context,
+						columns.employee_id,
+						&match_condition.employee_id,
+						query,
+					),
+					columns.hourly_rate,
+					&match_condition.hourly_rate.map_ref(|r| PgMoney(*r)),
+					query,
+				),
+				columns.id,
+				&match_condition.id,
+				query,
+			),
+			columns.job_id,
+			&match_condition.job_id,
+			query,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use clinvoice_adapter::{WriteContext, WriteWhereClause};
+	use clinvoice_finance::{Currency, Money};
+	use clinvoice_match::Match;
+	use sqlx::{Postgres, QueryBuilder};
+
+	use crate::{fmt::PgMoney, PgSchema};
+
+	#[test]
+	fn money_in_range()
+	{
+		let mut query = QueryBuilder::<Postgres>::new(String::new());
+		PgSchema::write_where_clause(
+			WriteContext::BeforeWhereClause,
+			"cost",
+			&Match::InRange(
+				PgMoney(Money::new(1_00, 2, Currency::Usd)),
+				PgMoney(Money::new(5_00, 2, Currency::Eur)),
+			),
+			&mut query,
+		);
+
+		// The currency must be both `USD` and `EUR`, so nothing can match.
+		let sql = query.sql();
+		assert!(sql.contains("cost_currency=$1"), "{sql}");
+		assert!(sql.contains("cost_currency=$2"), "{sql}");
+	}
+}